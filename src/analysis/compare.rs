@@ -0,0 +1,486 @@
+use crate::file_ops::copy::copy_recursive;
+use crate::jobs::job::{BatchResult, JobContext};
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime}
+};
+
+// FAT зберігає час з точністю до 2 секунд
+const MTIME_TOLERANCE: Duration = Duration::from_secs(2);
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum DiffKind {
+    LeftOnly,
+    RightOnly,
+    Different
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct EntryInfo {
+    pub is_dir: bool,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CompareEntry {
+    pub relative: PathBuf,
+    pub kind: DiffKind,
+    pub left: Option<EntryInfo>,
+    pub right: Option<EntryInfo>,
+}
+
+#[derive(Debug, Default)]
+pub struct CompareResult {
+    pub entries: Vec<CompareEntry>,
+    pub identical: u64,
+    // папки, які не вдалося прочитати, пропускаються разом з вмістом
+    pub errors: Vec<(PathBuf, String)>,
+    pub cancelled: bool,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum SyncMode {
+    MirrorToRight,
+    MirrorToLeft,
+    TwoWay
+}
+
+impl SyncMode {
+    pub const ALL: [SyncMode; 3] = [SyncMode::MirrorToRight, SyncMode::MirrorToLeft, SyncMode::TwoWay];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SyncMode::MirrorToRight => "Віддзеркалити ліву → праву",
+            SyncMode::MirrorToLeft => "Віддзеркалити праву → ліву",
+            SyncMode::TwoWay => "Двостороння синхронізація",
+        }
+    }
+
+    pub fn removes(self, kind: DiffKind) -> bool {
+        matches!((self, kind), (SyncMode::MirrorToRight, DiffKind::RightOnly) | (SyncMode::MirrorToLeft, DiffKind::LeftOnly))
+    }
+}
+
+fn entry_info(path: &Path) -> Option<EntryInfo> {
+    let metadata = path.symlink_metadata().ok()?;
+
+    Some(EntryInfo {
+        is_dir: metadata.is_dir(),
+        size: metadata.len(),
+        modified: metadata.modified().ok(),
+    })
+}
+
+fn list_dir(root: &Path, relative: &Path) -> io::Result<BTreeMap<PathBuf, EntryInfo>> {
+    Ok(fs::read_dir(root.join(relative))?
+        .filter_map(|dir_entry| dir_entry.ok())
+        .filter_map(|dir_entry| Some((relative.join(dir_entry.file_name()), entry_info(&dir_entry.path())?)))
+        .collect())
+}
+
+// нечитабельна або віртуальна папка не повинна виглядати порожньою, інакше синхронізація видалить усе з іншого боку
+fn check_root(root: &Path) -> io::Result<()> {
+    if !root.is_absolute() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{}: не локальна папка", root.display())));
+    }
+
+    fs::read_dir(root).map(|_| ()).map_err(|err| io::Error::new(err.kind(), format!("{}: {}", root.display(), err)))
+}
+
+fn same_time(left: Option<SystemTime>, right: Option<SystemTime>) -> bool {
+    match (left, right) {
+        (Some(left), Some(right)) => left.duration_since(right).or_else(|_| right.duration_since(left)).is_ok_and(|delta| delta <= MTIME_TOLERANCE),
+        _ => left == right,
+    }
+}
+
+fn same_content(left: &Path, right: &Path) -> io::Result<bool> {
+    let hash = |path: &Path| -> io::Result<blake3::Hash> {
+        let mut hasher = blake3::Hasher::new();
+        io::copy(&mut File::open(path)?, &mut hasher)?;
+
+        Ok(hasher.finalize())
+    };
+
+    Ok(hash(left)? == hash(right)?)
+}
+
+// папка, що є лише з одного боку, показується одним записом без вмісту
+pub fn compare_folders(left: &Path, right: &Path, compare_content: bool, job: &mut JobContext<u64>) -> io::Result<CompareResult> {
+    check_root(left)?;
+    check_root(right)?;
+
+    let mut result = CompareResult::default();
+    let mut stack = vec![PathBuf::new()];
+    let mut compared = 0;
+
+    while let Some(relative) = stack.pop() {
+        if job.is_cancelled() {
+            result.cancelled = true;
+            break;
+        }
+
+        let (mut left_entries, right_entries) = match (list_dir(left, &relative), list_dir(right, &relative)) {
+            (Ok(left_entries), Ok(right_entries)) => (left_entries, right_entries),
+            (Err(err), _) | (_, Err(err)) => {
+                result.errors.push((relative, err.to_string()));
+                continue;
+            }
+        };
+
+        for (path, right_info) in right_entries {
+            let left_info = left_entries.remove(&path);
+
+            let kind = match left_info {
+                None => Some(DiffKind::RightOnly),
+                Some(left_info) if left_info.is_dir != right_info.is_dir => Some(DiffKind::Different),
+                Some(left_info) if left_info.is_dir => {
+                    stack.push(path.clone());
+                    None
+                },
+                Some(left_info) if left_info.size != right_info.size => Some(DiffKind::Different),
+                Some(_) if compare_content => match same_content(&left.join(&path), &right.join(&path)) {
+                    Ok(true) => None,
+                    _ => Some(DiffKind::Different),
+                },
+                Some(left_info) if !same_time(left_info.modified, right_info.modified) => Some(DiffKind::Different),
+                Some(_) => None,
+            };
+
+            compared += 1;
+
+            match kind {
+                Some(kind) => result.entries.push(CompareEntry { relative: path, kind, left: left_info, right: Some(right_info) }),
+                None if !right_info.is_dir => result.identical += 1,
+                None => {},
+            }
+        }
+
+        for (path, left_info) in left_entries {
+            result.entries.push(CompareEntry { relative: path, kind: DiffKind::LeftOnly, left: Some(left_info), right: None });
+        }
+
+        job.report(compared);
+    }
+
+    result.entries.sort_by(|a, b| a.relative.cmp(&b.relative));
+    Ok(result)
+}
+
+fn remove_path(path: &Path) -> io::Result<()> {
+    match path.symlink_metadata() {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+fn sibling(target: &Path, suffix: &str) -> PathBuf {
+    let file_name = target.file_name().unwrap_or_default().to_string_lossy();
+
+    target.with_file_name(format!(".{}.{}", file_name, suffix))
+}
+
+// копія спершу створюється поруч під тимчасовим ім'ям, тож при помилці ціль лишається цілою.
+// файл підміняється атомарно, а папку перед підміною відсуваємо вбік і повертаємо, якщо rename не вдався
+fn replace_with(source: &Path, target: &Path) -> io::Result<()> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let temp = sibling(target, "sync-tmp");
+    remove_path(&temp)?;
+
+    copy_recursive(source, &temp).inspect_err(|_| {
+        let _ = remove_path(&temp);
+    })?;
+
+    let target_is_dir = target.symlink_metadata().is_ok_and(|metadata| metadata.is_dir());
+    let source_is_dir = temp.symlink_metadata().is_ok_and(|metadata| metadata.is_dir());
+
+    if !target_is_dir && !source_is_dir {
+        return fs::rename(&temp, target).inspect_err(|_| {
+            let _ = remove_path(&temp);
+        });
+    }
+
+    let backup = sibling(target, "sync-old");
+    remove_path(&backup)?;
+
+    let moved_aside = target.symlink_metadata().is_ok();
+
+    if moved_aside {
+        fs::rename(target, &backup).inspect_err(|_| {
+            let _ = remove_path(&temp);
+        })?;
+    }
+
+    if let Err(err) = fs::rename(&temp, target) {
+        if moved_aside {
+            let _ = fs::rename(&backup, target);
+        }
+
+        let _ = remove_path(&temp);
+        return Err(err);
+    }
+
+    remove_path(&backup)
+}
+
+fn sync_entry(left: &Path, right: &Path, entry: &CompareEntry, mode: SyncMode) -> io::Result<()> {
+    let left_path = left.join(&entry.relative);
+    let right_path = right.join(&entry.relative);
+
+    // між порівнянням і синхронізацією файли могли змінитись, тоді рішення вже не чинне
+    if entry_info(&left_path) != entry.left || entry_info(&right_path) != entry.right {
+        return Err(io::Error::other("змінено після порівняння"));
+    }
+
+    match (mode, entry.kind) {
+        (SyncMode::MirrorToRight, DiffKind::RightOnly) => remove_path(&right_path),
+        (SyncMode::MirrorToRight, _) => replace_with(&left_path, &right_path),
+        (SyncMode::MirrorToLeft, DiffKind::LeftOnly) => remove_path(&left_path),
+        (SyncMode::MirrorToLeft, _) => replace_with(&right_path, &left_path),
+        (SyncMode::TwoWay, DiffKind::LeftOnly) => replace_with(&left_path, &right_path),
+        (SyncMode::TwoWay, DiffKind::RightOnly) => replace_with(&right_path, &left_path),
+        // при двосторонній синхронізації перемагає новіша версія
+        (SyncMode::TwoWay, DiffKind::Different) => {
+            let left_modified = entry.left.and_then(|info| info.modified);
+            let right_modified = entry.right.and_then(|info| info.modified);
+
+            match (left_modified, right_modified) {
+                (Some(left_modified), Some(right_modified)) if left_modified > right_modified => replace_with(&left_path, &right_path),
+                (Some(left_modified), Some(right_modified)) if right_modified > left_modified => replace_with(&right_path, &left_path),
+                _ => Err(io::Error::other("конфлікт: обидві версії змінені одночасно")),
+            }
+        }
+    }
+}
+
+pub fn sync_folders(left: &Path, right: &Path, entries: Vec<CompareEntry>, mode: SyncMode, job: &mut JobContext<u64>) -> BatchResult {
+    let mut result = BatchResult::default();
+
+    for entry in entries {
+        if job.is_cancelled() {
+            result.cancelled = true;
+            break;
+        }
+
+        match sync_entry(left, right, &entry, mode) {
+            Ok(()) => result.done += 1,
+            Err(err) => result.errors.push((entry.relative, err.to_string())),
+        }
+
+        job.report(result.done);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jobs::job::Job;
+
+    use eframe::egui;
+
+    use std::{env, thread};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("file_explorer_compare_{}_{}", name, std::process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("left")).unwrap();
+        fs::create_dir_all(dir.join("right")).unwrap();
+
+        dir
+    }
+
+    fn run<T: Send + 'static>(work: impl FnOnce(&mut JobContext<u64>) -> T + Send + 'static) -> T {
+        let mut job = Job::spawn(&egui::Context::default(), work);
+
+        while !job.is_finished() {
+            thread::sleep(Duration::from_millis(10));
+            job.poll();
+        }
+
+        job.result.unwrap()
+    }
+
+    fn compare(dir: &Path, compare_content: bool) -> CompareResult {
+        let (left, right) = (dir.join("left"), dir.join("right"));
+
+        run(move |job| compare_folders(&left, &right, compare_content, job)).unwrap()
+    }
+
+    fn kinds(result: &CompareResult) -> Vec<(PathBuf, DiffKind)> {
+        result.entries.iter().map(|entry| (entry.relative.clone(), entry.kind)).collect()
+    }
+
+    #[test]
+    fn reports_one_sided_and_different_entries() {
+        let dir = temp_dir("diff");
+
+        fs::create_dir_all(dir.join("left/common")).unwrap();
+        fs::create_dir_all(dir.join("right/common")).unwrap();
+        fs::create_dir_all(dir.join("left/only_dir/inner")).unwrap();
+        fs::write(dir.join("left/only_dir/inner/x"), "x").unwrap();
+        fs::write(dir.join("left/common/same"), "same").unwrap();
+        fs::write(dir.join("right/common/same"), "same").unwrap();
+        fs::write(dir.join("left/common/size"), "short").unwrap();
+        fs::write(dir.join("right/common/size"), "longer").unwrap();
+        fs::write(dir.join("right/right_only"), "r").unwrap();
+        fs::write(dir.join("left/kind"), "file").unwrap();
+        fs::create_dir(dir.join("right/kind")).unwrap();
+
+        let result = compare(&dir, false);
+
+        assert_eq!(kinds(&result), vec![
+            (PathBuf::from("common/size"), DiffKind::Different),
+            (PathBuf::from("kind"), DiffKind::Different),
+            (PathBuf::from("only_dir"), DiffKind::LeftOnly),
+            (PathBuf::from("right_only"), DiffKind::RightOnly),
+        ]);
+        assert_eq!(result.identical, 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn content_comparison_finds_same_size_changes() {
+        let dir = temp_dir("content");
+
+        fs::write(dir.join("left/a"), "aaaa").unwrap();
+        fs::write(dir.join("right/a"), "bbbb").unwrap();
+
+        // час зміни однаковий, тож без порівняння вмісту різниці не видно
+        let modified = fs::metadata(dir.join("left/a")).unwrap().modified().unwrap();
+        File::options().write(true).open(dir.join("right/a")).unwrap().set_modified(modified).unwrap();
+
+        assert!(compare(&dir, false).entries.is_empty());
+        assert_eq!(kinds(&compare(&dir, true)), vec![(PathBuf::from("a"), DiffKind::Different)]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn times_within_tolerance_are_equal() {
+        let now = SystemTime::now();
+
+        assert!(same_time(Some(now), Some(now + Duration::from_secs(2))));
+        assert!(!same_time(Some(now), Some(now + Duration::from_secs(3))));
+        assert!(!same_time(Some(now), None));
+    }
+
+    #[test]
+    fn replace_with_overwrites_files_and_folders() {
+        let dir = temp_dir("replace");
+
+        fs::create_dir_all(dir.join("left/folder")).unwrap();
+        fs::write(dir.join("left/folder/new"), "new").unwrap();
+        fs::create_dir_all(dir.join("right/folder")).unwrap();
+        fs::write(dir.join("right/folder/old"), "old").unwrap();
+        fs::write(dir.join("left/file"), "left").unwrap();
+        fs::write(dir.join("right/file"), "right").unwrap();
+
+        replace_with(&dir.join("left/folder"), &dir.join("right/folder")).unwrap();
+        replace_with(&dir.join("left/file"), &dir.join("right/file")).unwrap();
+        replace_with(&dir.join("left/file"), &dir.join("right/nested/copy")).unwrap();
+
+        assert_eq!(fs::read_to_string(dir.join("right/folder/new")).unwrap(), "new");
+        assert!(!dir.join("right/folder/old").exists());
+        assert_eq!(fs::read_to_string(dir.join("right/file")).unwrap(), "left");
+        assert_eq!(fs::read_to_string(dir.join("right/nested/copy")).unwrap(), "left");
+
+        // тимчасових копій не лишається
+        let mut names: Vec<_> = fs::read_dir(dir.join("right")).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+        names.sort();
+        assert_eq!(names, ["file", "folder", "nested"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn failed_copy_keeps_target() {
+        let dir = temp_dir("failed");
+
+        fs::write(dir.join("right/file"), "right").unwrap();
+
+        assert!(replace_with(&dir.join("left/missing"), &dir.join("right/file")).is_err());
+        assert_eq!(fs::read_to_string(dir.join("right/file")).unwrap(), "right");
+        assert_eq!(fs::read_dir(dir.join("right")).unwrap().count(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn mirror_makes_right_match_left() {
+        let dir = temp_dir("mirror");
+
+        fs::write(dir.join("left/a"), "a").unwrap();
+        fs::write(dir.join("left/b"), "new").unwrap();
+        fs::write(dir.join("right/b"), "old!").unwrap();
+        fs::write(dir.join("right/c"), "c").unwrap();
+
+        let entries = compare(&dir, false).entries;
+        let (left, right) = (dir.join("left"), dir.join("right"));
+        let result = run(move |job| sync_folders(&left, &right, entries, SyncMode::MirrorToRight, job));
+
+        assert_eq!(result.done, 3);
+        assert!(compare(&dir, true).entries.is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn missing_or_virtual_root_is_an_error() {
+        let dir = temp_dir("roots");
+        let (left, missing) = (dir.join("left"), dir.join("missing"));
+
+        assert!(run(move |job| compare_folders(&left, &missing, false, job)).is_err());
+
+        let left = dir.join("left");
+
+        assert!(run(move |job| compare_folders(&left, Path::new("sftp:/host"), false, job)).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn changed_entries_are_not_synced() {
+        let dir = temp_dir("changed");
+
+        fs::write(dir.join("left/a"), "a").unwrap();
+        fs::write(dir.join("right/b"), "b").unwrap();
+
+        let entries = compare(&dir, false).entries;
+
+        // після порівняння файл праворуч змінився, видаляти його не можна
+        fs::write(dir.join("right/b"), "changed").unwrap();
+
+        let (left, right) = (dir.join("left"), dir.join("right"));
+        let result = run(move |job| sync_folders(&left, &right, entries, SyncMode::MirrorToRight, job));
+
+        assert_eq!(result.done, 1);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(fs::read_to_string(dir.join("right/b")).unwrap(), "changed");
+        assert_eq!(fs::read_to_string(dir.join("right/a")).unwrap(), "a");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn only_mirror_of_one_sided_entries_removes() {
+        assert!(SyncMode::MirrorToRight.removes(DiffKind::RightOnly));
+        assert!(SyncMode::MirrorToLeft.removes(DiffKind::LeftOnly));
+        assert!(!SyncMode::MirrorToRight.removes(DiffKind::LeftOnly));
+        assert!(!SyncMode::TwoWay.removes(DiffKind::RightOnly));
+    }
+}
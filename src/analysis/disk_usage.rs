@@ -0,0 +1,293 @@
+use crate::jobs::job::JobContext;
+
+use rayon::prelude::*;
+
+use std::{
+    cmp::Reverse,
+    collections::HashSet,
+    fs::{self, Metadata},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+        Mutex
+    },
+    thread,
+    time::Duration
+};
+
+#[derive(Debug, Clone)]
+pub struct UsageNode {
+    pub name: String,
+    pub path: PathBuf,
+    pub size: u64,
+    pub files: u64,
+    pub is_dir: bool,
+    pub children: Vec<UsageNode>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UsageProgress {
+    pub bytes: u64,
+    pub files: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    bytes: AtomicU64,
+    files: AtomicU64,
+}
+
+// спільний стан обходу: пристрій кореня і вже пораховані жорсткі посилання
+struct ScanState {
+    counters: Arc<Counters>,
+    cancelled: Arc<AtomicBool>,
+    device: Option<u64>,
+    seen: Mutex<HashSet<(u64, u64)>>,
+}
+
+impl UsageNode {
+    pub fn node(&self, index_path: &[usize]) -> Option<&UsageNode> {
+        index_path.iter().try_fold(self, |node, &index| node.children.get(index))
+    }
+
+    // видаляє вузол з дерева і віднімає його розмір у всіх предків
+    pub fn remove(&mut self, index_path: &[usize]) -> Option<UsageNode> {
+        let (&last, parents) = index_path.split_last()?;
+
+        let mut node = &mut *self;
+        let mut ancestors = vec![];
+
+        for &index in parents {
+            ancestors.push(index);
+            node = node.children.get_mut(index)?;
+        }
+
+        if last >= node.children.len() {
+            return None;
+        }
+
+        let removed = node.children.remove(last);
+
+        let mut node = &mut *self;
+        node.size -= removed.size;
+        node.files -= removed.files;
+
+        for index in ancestors {
+            node = &mut node.children[index];
+            node.size -= removed.size;
+            node.files -= removed.files;
+        }
+
+        Some(removed)
+    }
+}
+
+// каталоги обходяться паралельно через rayon, потік задачі лише звітує про прогрес
+pub fn scan_usage(path: &Path, job: &mut JobContext<UsageProgress>) -> UsageNode {
+    let counters = Arc::new(Counters::default());
+    let cancelled = job.cancel_flag();
+
+    let handle = {
+        let counters = counters.clone();
+        let path = path.to_path_buf();
+
+        thread::spawn(move || {
+            let state = ScanState {
+                counters,
+                cancelled,
+                device: fs::metadata(&path).ok().and_then(|metadata| device_id(&metadata)),
+                seen: Mutex::new(HashSet::new()),
+            };
+
+            scan_node(&path, &state)
+        })
+    };
+
+    while !handle.is_finished() {
+        job.report(UsageProgress {
+            bytes: counters.bytes.load(Ordering::Relaxed),
+            files: counters.files.load(Ordering::Relaxed),
+        });
+
+        thread::sleep(Duration::from_millis(50));
+    }
+
+    handle.join().unwrap_or_else(|_| empty_node(path, true))
+}
+
+fn empty_node(path: &Path, is_dir: bool) -> UsageNode {
+    UsageNode {
+        name: path.file_name().map_or_else(|| path.to_string_lossy().to_string(), |name| name.to_string_lossy().to_string()),
+        path: path.to_path_buf(),
+        size: 0,
+        files: 0,
+        is_dir,
+        children: Vec::new(),
+    }
+}
+
+// місце, яке файл справді займає на диску, а не його логічна довжина
+#[cfg(unix)]
+fn disk_size(metadata: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+
+    metadata.blocks() * 512
+}
+
+#[cfg(not(unix))]
+fn disk_size(metadata: &Metadata) -> u64 {
+    metadata.len()
+}
+
+#[cfg(unix)]
+fn device_id(metadata: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some(metadata.dev())
+}
+
+#[cfg(not(unix))]
+fn device_id(_metadata: &Metadata) -> Option<u64> {
+    None
+}
+
+// файл з кількома жорсткими посиланнями рахується лише при першій зустрічі
+#[cfg(unix)]
+fn is_seen_hardlink(metadata: &Metadata, state: &ScanState) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    metadata.nlink() > 1 && !state.seen.lock().is_ok_and(|mut seen| seen.insert((metadata.dev(), metadata.ino())))
+}
+
+#[cfg(not(unix))]
+fn is_seen_hardlink(_metadata: &Metadata, _state: &ScanState) -> bool {
+    false
+}
+
+// символьні посилання рахуються як файли, щоб не рахувати ціль двічі;
+// в інші файлові системи (змонтовані всередині) не заходимо
+fn scan_node(path: &Path, state: &ScanState) -> UsageNode {
+    let mut node = empty_node(path, true);
+
+    if state.cancelled.load(Ordering::Relaxed) {
+        return node;
+    }
+
+    let Ok(read_dir) = fs::read_dir(path) else {
+        return node;
+    };
+
+    let entries: Vec<PathBuf> = read_dir.filter_map(|dir_entry| dir_entry.ok()).map(|dir_entry| dir_entry.path()).collect();
+
+    node.children = entries
+        .par_iter()
+        .filter_map(|path| {
+            let metadata = path.symlink_metadata().ok()?;
+
+            if state.device.is_some() && device_id(&metadata) != state.device {
+                return None;
+            }
+
+            if metadata.is_dir() {
+                return Some(scan_node(path, state));
+            }
+
+            if is_seen_hardlink(&metadata, state) {
+                return None;
+            }
+
+            let size = disk_size(&metadata);
+
+            state.counters.bytes.fetch_add(size, Ordering::Relaxed);
+            state.counters.files.fetch_add(1, Ordering::Relaxed);
+
+            Some(UsageNode {
+                size,
+                files: 1,
+                ..empty_node(path, false)
+            })
+        })
+        .collect();
+
+    node.size = node.children.iter().map(|child| child.size).sum();
+    node.files = node.children.iter().map(|child| child.files).sum();
+    node.children.sort_by_key(|child| Reverse(child.size));
+
+    node
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jobs::job::Job;
+
+    use eframe::egui;
+
+    use std::env;
+
+    fn scan(path: &Path) -> UsageNode {
+        let path = path.to_path_buf();
+        let mut job = Job::spawn(&egui::Context::default(), move |job| scan_usage(&path, job));
+
+        while !job.is_finished() {
+            thread::sleep(Duration::from_millis(10));
+            job.poll();
+        }
+
+        job.result.unwrap()
+    }
+
+    #[test]
+    fn hardlinks_are_counted_once() {
+        let dir = env::temp_dir().join(format!("file_explorer_usage_{}", std::process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("inner")).unwrap();
+        fs::write(dir.join("a.bin"), vec![1; 64 * 1024]).unwrap();
+        fs::write(dir.join("inner/b.bin"), vec![2; 16 * 1024]).unwrap();
+        fs::hard_link(dir.join("a.bin"), dir.join("inner/a_link.bin")).unwrap();
+
+        let tree = scan(&dir);
+
+        assert_eq!(tree.files, 2);
+        assert_eq!(tree.size, tree.children.iter().map(|child| child.size).sum::<u64>());
+        assert!(tree.size >= 80 * 1024);
+        // найбільший вузол іде першим
+        assert!(tree.children.windows(2).all(|pair| pair[0].size >= pair[1].size));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn remove_subtracts_size_from_ancestors() {
+        let leaf = |size| UsageNode {
+            size,
+            files: 1,
+            ..empty_node(Path::new("leaf"), false)
+        };
+
+        let mut tree = UsageNode {
+            size: 30,
+            files: 3,
+            children: vec![
+                UsageNode {
+                    size: 20,
+                    files: 2,
+                    children: vec![leaf(15), leaf(5)],
+                    ..empty_node(Path::new("dir"), true)
+                },
+                leaf(10),
+            ],
+            ..empty_node(Path::new("root"), true)
+        };
+
+        let removed = tree.remove(&[0, 1]).unwrap();
+
+        assert_eq!(removed.size, 5);
+        assert_eq!((tree.size, tree.files), (25, 2));
+        assert_eq!(tree.node(&[0]).map(|node| node.size), Some(15));
+        assert!(tree.remove(&[5]).is_none());
+    }
+}
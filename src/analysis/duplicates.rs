@@ -0,0 +1,417 @@
+use crate::jobs::job::{BatchResult, JobContext};
+
+use rayon::prelude::*;
+
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    fs::{self, File},
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    time::SystemTime
+};
+
+// для попереднього порівняння вистачає початку файлу
+const PARTIAL_HASH_SIZE: u64 = 4096;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateFile {
+    pub path: PathBuf,
+    // час зміни на момент пошуку, щоб не чіпати файл, змінений після нього
+    pub modified: Option<SystemTime>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    pub size: u64,
+    pub files: Vec<DuplicateFile>,
+}
+
+impl DuplicateGroup {
+    pub fn reclaimable(&self) -> u64 {
+        self.size * (self.files.len() as u64).saturating_sub(1)
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum DuplicateStage {
+    Scanning,
+    PartialHash,
+    FullHash
+}
+
+impl DuplicateStage {
+    pub fn label(self) -> &'static str {
+        match self {
+            DuplicateStage::Scanning => "Пошук файлів",
+            DuplicateStage::PartialHash => "Порівняння початку файлів",
+            DuplicateStage::FullHash => "Порівняння вмісту",
+        }
+    }
+}
+
+// оброблені групи визначаються файлом, який у них залишено
+#[derive(Debug, Default)]
+pub struct ResolveResult {
+    pub batch: BatchResult,
+    pub resolved: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DuplicateProgress {
+    pub stage: DuplicateStage,
+    pub done: u64,
+    pub total: u64,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum DuplicateAction {
+    Trash,
+    Hardlink,
+    Symlink
+}
+
+impl DuplicateAction {
+    pub const ALL: [DuplicateAction; 3] = [DuplicateAction::Trash, DuplicateAction::Hardlink, DuplicateAction::Symlink];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DuplicateAction::Trash => "Перемістити в кошик",
+            DuplicateAction::Hardlink => "Замінити жорстким посиланням",
+            DuplicateAction::Symlink => "Замінити символьним посиланням",
+        }
+    }
+}
+
+// спершу групуємо за розміром, потім за хешем початку і лише потім за хешем усього файлу
+pub fn find_duplicates(root: &Path, job: &mut JobContext<DuplicateProgress>) -> Vec<DuplicateGroup> {
+    let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+    let mut modified = HashMap::new();
+    let mut seen_inodes = HashSet::new();
+    let mut stack = vec![root.to_path_buf()];
+    let mut scanned = 0;
+
+    while let Some(dir) = stack.pop() {
+        if job.is_cancelled() {
+            return Vec::new();
+        }
+
+        let Ok(read_dir) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for dir_entry in read_dir.filter_map(|dir_entry| dir_entry.ok()) {
+            let path = dir_entry.path();
+
+            let Ok(metadata) = path.symlink_metadata() else {
+                continue;
+            };
+
+            if metadata.is_dir() {
+                stack.push(path);
+            } else if metadata.is_file() && metadata.len() > 0 && seen_inodes.insert(file_id(&path, &metadata)) {
+                modified.insert(path.clone(), metadata.modified().ok());
+                by_size.entry(metadata.len()).or_default().push(path);
+                scanned += 1;
+            }
+        }
+
+        job.report(DuplicateProgress { stage: DuplicateStage::Scanning, done: scanned, total: 0 });
+    }
+
+    let candidates: Vec<Vec<PathBuf>> = by_size.into_values().filter(|files| files.len() > 1).collect();
+
+    let candidates = split_by_hash(candidates, DuplicateStage::PartialHash, Some(PARTIAL_HASH_SIZE), job);
+    let candidates = split_by_hash(candidates, DuplicateStage::FullHash, None, job);
+
+    let mut groups: Vec<DuplicateGroup> = candidates
+        .into_iter()
+        .map(|mut files| {
+            files.sort();
+
+            DuplicateGroup {
+                size: files.first().and_then(|file| file.metadata().ok()).map_or(0, |metadata| metadata.len()),
+                files: files
+                    .into_iter()
+                    .map(|path| DuplicateFile { modified: modified.get(&path).copied().flatten(), path })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    groups.sort_by_key(|group| Reverse(group.reclaimable()));
+    groups
+}
+
+// файли, що вже є жорсткими посиланнями один на одного, не займають зайвого місця
+#[cfg(unix)]
+fn file_id(_path: &Path, metadata: &fs::Metadata) -> (u64, u64) {
+    use std::os::unix::fs::MetadataExt;
+
+    (metadata.dev(), metadata.ino())
+}
+
+#[cfg(not(unix))]
+fn file_id(path: &Path, _metadata: &fs::Metadata) -> PathBuf {
+    path.to_path_buf()
+}
+
+fn split_by_hash(groups: Vec<Vec<PathBuf>>, stage: DuplicateStage, limit: Option<u64>, job: &mut JobContext<DuplicateProgress>) -> Vec<Vec<PathBuf>> {
+    let files: Vec<(usize, PathBuf)> = groups
+        .into_iter()
+        .enumerate()
+        .flat_map(|(group, files)| files.into_iter().map(move |file| (group, file)))
+        .collect();
+
+    let total = files.len() as u64;
+    let done = AtomicU64::new(0);
+    let cancelled = job.cancel_flag();
+
+    // хешування йде паралельно, тому прогрес звітує окремий потік через лічильник
+    let hashes: Vec<Option<(usize, blake3::Hash, PathBuf)>> = std::thread::scope(|scope| {
+        let worker = scope.spawn(|| {
+            files
+                .into_par_iter()
+                .map(|(group, file)| {
+                    if cancelled.load(Ordering::Relaxed) {
+                        return None;
+                    }
+
+                    let hash = hash_file(&file, limit).ok();
+                    done.fetch_add(1, Ordering::Relaxed);
+
+                    hash.map(|hash| (group, hash, file))
+                })
+                .collect()
+        });
+
+        while !worker.is_finished() {
+            job.report(DuplicateProgress { stage, done: done.load(Ordering::Relaxed), total });
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+
+        worker.join().unwrap_or_default()
+    });
+
+    let mut by_hash: HashMap<(usize, blake3::Hash), Vec<PathBuf>> = HashMap::new();
+
+    for (group, hash, file) in hashes.into_iter().flatten() {
+        by_hash.entry((group, hash)).or_default().push(file);
+    }
+
+    by_hash.into_values().filter(|files| files.len() > 1).collect()
+}
+
+fn hash_file(path: &Path, limit: Option<u64>) -> io::Result<blake3::Hash> {
+    let mut hasher = blake3::Hasher::new();
+    let file = File::open(path)?;
+
+    match limit {
+        Some(limit) => io::copy(&mut file.take(limit), &mut hasher)?,
+        None => io::copy(&mut io::BufReader::with_capacity(256 * 1024, file), &mut hasher)?,
+    };
+
+    Ok(hasher.finalize())
+}
+
+// нове посилання створюється поруч під тимчасовим ім'ям і атомарно підміняє дублікат
+fn replace_with_link(keep: &Path, duplicate: &Path, action: DuplicateAction) -> io::Result<()> {
+    let file_name = duplicate.file_name().unwrap_or_default().to_string_lossy();
+    let temp = duplicate.with_file_name(format!(".{}.dedup-tmp", file_name));
+
+    match action {
+        DuplicateAction::Hardlink => fs::hard_link(keep, &temp)?,
+        #[cfg(unix)]
+        DuplicateAction::Symlink => std::os::unix::fs::symlink(fs::canonicalize(keep)?, &temp)?,
+        #[cfg(windows)]
+        DuplicateAction::Symlink => std::os::windows::fs::symlink_file(fs::canonicalize(keep)?, &temp)?,
+        _ => return Err(io::Error::new(io::ErrorKind::Unsupported, "непідтримувана дія")),
+    }
+
+    fs::rename(&temp, duplicate).inspect_err(|_| {
+        let _ = fs::remove_file(&temp);
+    })
+}
+
+// між пошуком і застосуванням файл могли змінити, тоді він уже не дублікат
+fn check_unchanged(file: &DuplicateFile, size: u64) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(&file.path)?;
+
+    if !metadata.is_file() || metadata.len() != size || metadata.modified().ok() != file.modified {
+        return Err(io::Error::other("файл змінився після пошуку"));
+    }
+
+    Ok(())
+}
+
+pub fn resolve_duplicates(groups: Vec<(u64, DuplicateFile, Vec<DuplicateFile>)>, action: DuplicateAction, job: &mut JobContext<u64>) -> ResolveResult {
+    let mut result = ResolveResult::default();
+
+    for (size, keep, duplicates) in groups {
+        let mut failed = false;
+
+        for duplicate in duplicates {
+            if job.is_cancelled() {
+                result.batch.cancelled = true;
+                return result;
+            }
+
+            let resolved = check_unchanged(&keep, size).and_then(|_| check_unchanged(&duplicate, size)).and_then(|_| match action {
+                DuplicateAction::Trash => trash::delete(&duplicate.path).map_err(|err| io::Error::other(err.to_string())),
+                _ => replace_with_link(&keep.path, &duplicate.path, action),
+            });
+
+            match resolved {
+                Ok(()) => result.batch.done += 1,
+                Err(err) => {
+                    failed = true;
+                    result.batch.errors.push((duplicate.path, err.to_string()));
+                }
+            }
+
+            job.report(result.batch.done);
+        }
+
+        if !failed {
+            result.resolved.push(keep.path);
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jobs::job::Job;
+
+    use eframe::egui;
+
+    use std::{env, thread, time::Duration};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("file_explorer_duplicates_{}_{}", name, std::process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    fn run<P: Send + 'static, T: Send + 'static>(work: impl FnOnce(&mut JobContext<P>) -> T + Send + 'static) -> T {
+        let mut job = Job::spawn(&egui::Context::default(), work);
+
+        while !job.is_finished() {
+            thread::sleep(Duration::from_millis(10));
+            job.poll();
+        }
+
+        job.result.unwrap()
+    }
+
+    fn sorted(mut groups: Vec<Vec<PathBuf>>) -> Vec<Vec<PathBuf>> {
+        groups.iter_mut().for_each(|files| files.sort());
+        groups.sort();
+        groups
+    }
+
+    #[test]
+    fn split_by_hash_separates_different_content() {
+        let dir = temp_dir("split");
+
+        // однаковий початок, різний кінець
+        let mut long = vec![0; PARTIAL_HASH_SIZE as usize];
+        fs::write(dir.join("a"), [long.as_slice(), b"1"].concat()).unwrap();
+        fs::write(dir.join("b"), [long.as_slice(), b"1"].concat()).unwrap();
+        long.push(b'2');
+        fs::write(dir.join("c"), &long).unwrap();
+        fs::write(dir.join("d"), "x").unwrap();
+        fs::write(dir.join("e"), "y").unwrap();
+
+        let groups = vec![
+            vec![dir.join("a"), dir.join("b"), dir.join("c")],
+            vec![dir.join("d"), dir.join("e")],
+        ];
+
+        let partial = run(move |job| split_by_hash(groups, DuplicateStage::PartialHash, Some(PARTIAL_HASH_SIZE), job));
+        assert_eq!(sorted(partial.clone()), vec![vec![dir.join("a"), dir.join("b"), dir.join("c")]]);
+
+        let full = run(move |job| split_by_hash(partial, DuplicateStage::FullHash, None, job));
+        assert_eq!(sorted(full), vec![vec![dir.join("a"), dir.join("b")]]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn same_content_in_different_groups_is_not_merged() {
+        let dir = temp_dir("groups");
+
+        for name in ["a", "b", "c", "d"] {
+            fs::write(dir.join(name), "same").unwrap();
+        }
+
+        let groups = vec![vec![dir.join("a"), dir.join("b")], vec![dir.join("c"), dir.join("d")]];
+        let split = run(move |job| split_by_hash(groups, DuplicateStage::FullHash, None, job));
+
+        assert_eq!(sorted(split), vec![vec![dir.join("a"), dir.join("b")], vec![dir.join("c"), dir.join("d")]]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn find_duplicates_skips_existing_hardlinks() {
+        let dir = temp_dir("find");
+
+        fs::create_dir(dir.join("inner")).unwrap();
+        fs::write(dir.join("a"), "content").unwrap();
+        fs::write(dir.join("inner/b"), "content").unwrap();
+        fs::write(dir.join("other"), "another").unwrap();
+        fs::hard_link(dir.join("a"), dir.join("a_link")).unwrap();
+
+        let root = dir.clone();
+        let groups = run(move |job| find_duplicates(&root, job));
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].size, 7);
+        assert_eq!(groups[0].files.len(), 2);
+        assert!(groups[0].files.iter().all(|file| file.modified.is_some()));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn changed_files_are_skipped() {
+        let dir = temp_dir("changed");
+
+        for name in ["keep", "same", "changed"] {
+            fs::write(dir.join(name), "data").unwrap();
+        }
+
+        let file = |name: &str| DuplicateFile {
+            path: dir.join(name),
+            modified: fs::metadata(dir.join(name)).unwrap().modified().ok(),
+        };
+
+        let groups = vec![(4, file("keep"), vec![file("same")]), (4, file("keep"), vec![file("changed")])];
+        fs::write(dir.join("changed"), "edited").unwrap();
+
+        let result = run(move |job| resolve_duplicates(groups, DuplicateAction::Hardlink, job));
+
+        assert_eq!(result.batch.done, 1);
+        assert_eq!(result.batch.errors.len(), 1);
+        assert_eq!(result.batch.errors[0].0, dir.join("changed"));
+        assert_eq!(result.resolved, vec![dir.join("keep")]);
+        assert_eq!(fs::read_to_string(dir.join("changed")).unwrap(), "edited");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+
+            assert_eq!(fs::metadata(dir.join("same")).unwrap().ino(), fs::metadata(dir.join("keep")).unwrap().ino());
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,5 @@
+pub mod compare;
+pub mod disk_usage;
+pub mod duplicates;
+pub mod text_diff;
+pub mod treemap;
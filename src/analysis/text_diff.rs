@@ -0,0 +1,183 @@
+use similar::{ChangeTag, TextDiff};
+
+use std::{
+    fs,
+    io,
+    path::Path,
+    time::Duration
+};
+
+// дуже різні великі файли порівнюються наближено, щоб не чекати вічно
+const DIFF_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum DiffLineKind {
+    Equal,
+    Delete,
+    Insert
+}
+
+// emphasized — частина рядка, яка змінилась усередині рядка
+#[derive(Debug, Clone)]
+pub struct DiffSegment {
+    pub text: String,
+    pub emphasized: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub old_number: Option<usize>,
+    pub new_number: Option<usize>,
+    pub segments: Vec<DiffSegment>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TextDiffResult {
+    pub lines: Vec<DiffLine>,
+    pub inserted: usize,
+    pub deleted: usize,
+}
+
+fn read_text(path: &Path) -> io::Result<String> {
+    let data = fs::read(path)?;
+
+    if data.contains(&0) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("{} — двійковий файл", path.display())));
+    }
+
+    Ok(String::from_utf8_lossy(&data).to_string())
+}
+
+pub fn diff_files(old: &Path, new: &Path) -> io::Result<TextDiffResult> {
+    Ok(diff_text(&read_text(old)?, &read_text(new)?))
+}
+
+pub fn diff_text(old: &str, new: &str) -> TextDiffResult {
+    let diff = TextDiff::configure().timeout(DIFF_TIMEOUT).diff_lines(old, new);
+    let mut result = TextDiffResult::default();
+
+    for op in diff.ops() {
+        for change in diff.iter_inline_changes(op) {
+            let kind = match change.tag() {
+                ChangeTag::Equal => DiffLineKind::Equal,
+                ChangeTag::Delete => {
+                    result.deleted += 1;
+                    DiffLineKind::Delete
+                },
+                ChangeTag::Insert => {
+                    result.inserted += 1;
+                    DiffLineKind::Insert
+                },
+            };
+
+            let segments = change
+                .iter_strings_lossy()
+                .map(|(emphasized, text)| DiffSegment {
+                    text: text.trim_end_matches(['\n', '\r']).to_string(),
+                    emphasized,
+                })
+                .collect();
+
+            result.lines.push(DiffLine {
+                kind,
+                old_number: change.old_index().map(|index| index + 1),
+                new_number: change.new_index().map(|index| index + 1),
+                segments,
+            });
+        }
+    }
+
+    result
+}
+
+// для вигляду поруч: видалені рядки блоку стають навпроти доданих
+pub fn side_by_side(lines: &[DiffLine]) -> Vec<(Option<&DiffLine>, Option<&DiffLine>)> {
+    let mut rows = Vec::new();
+    let mut index = 0;
+
+    while index < lines.len() {
+        if lines[index].kind == DiffLineKind::Equal {
+            rows.push((Some(&lines[index]), Some(&lines[index])));
+            index += 1;
+            continue;
+        }
+
+        let block_end = lines[index..]
+            .iter()
+            .position(|line| line.kind == DiffLineKind::Equal)
+            .map_or(lines.len(), |offset| index + offset);
+
+        let block = &lines[index..block_end];
+        let deleted: Vec<&DiffLine> = block.iter().filter(|line| line.kind == DiffLineKind::Delete).collect();
+        let inserted: Vec<&DiffLine> = block.iter().filter(|line| line.kind == DiffLineKind::Insert).collect();
+
+        for row in 0..deleted.len().max(inserted.len()) {
+            rows.push((deleted.get(row).copied(), inserted.get(row).copied()));
+        }
+
+        index = block_end;
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(line: Option<&DiffLine>) -> Option<String> {
+        line.map(|line| line.segments.iter().map(|segment| segment.text.as_str()).collect())
+    }
+
+    #[test]
+    fn counts_and_numbers_lines() {
+        let result = diff_text("a\nb\nc\n", "a\nB\nc\nd\n");
+
+        assert_eq!((result.deleted, result.inserted), (1, 2));
+
+        let kinds: Vec<_> = result.lines.iter().map(|line| (line.kind, line.old_number, line.new_number)).collect();
+        assert_eq!(kinds, vec![
+            (DiffLineKind::Equal, Some(1), Some(1)),
+            (DiffLineKind::Delete, Some(2), None),
+            (DiffLineKind::Insert, None, Some(2)),
+            (DiffLineKind::Equal, Some(3), Some(3)),
+            (DiffLineKind::Insert, None, Some(4)),
+        ]);
+    }
+
+    #[test]
+    fn changed_part_of_line_is_emphasized() {
+        let result = diff_text("let value = 1;\n", "let value = 2;\n");
+        let inserted = result.lines.iter().find(|line| line.kind == DiffLineKind::Insert).unwrap();
+
+        assert!(inserted.segments.iter().any(|segment| segment.emphasized && segment.text == "2;"));
+        assert!(inserted.segments.iter().any(|segment| !segment.emphasized && segment.text.starts_with("let")));
+    }
+
+    #[test]
+    fn side_by_side_pairs_deleted_with_inserted() {
+        let result = diff_text("same\nold1\nold2\nend\n", "same\nnew1\nend\nextra\n");
+        let rows: Vec<_> = side_by_side(&result.lines).into_iter().map(|(left, right)| (text(left), text(right))).collect();
+
+        let row = |left: Option<&str>, right: Option<&str>| (left.map(String::from), right.map(String::from));
+
+        assert_eq!(rows, vec![
+            row(Some("same"), Some("same")),
+            row(Some("old1"), Some("new1")),
+            row(Some("old2"), None),
+            row(Some("end"), Some("end")),
+            row(None, Some("extra")),
+        ]);
+    }
+
+    #[test]
+    fn binary_files_are_rejected() {
+        let path = std::env::temp_dir().join(format!("file_explorer_text_diff_{}", std::process::id()));
+        fs::write(&path, b"text\0binary").unwrap();
+
+        assert_eq!(diff_files(&path, &path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
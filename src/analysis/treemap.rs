@@ -0,0 +1,123 @@
+use eframe::egui::{pos2, Rect};
+
+// squarified treemap (Bruls, Huizing, van Wijk): прямокутники якомога ближчі до квадратів.
+// розміри мають бути відсортовані за спаданням, результат іде в тому ж порядку
+pub fn squarify(sizes: &[u64], rect: Rect) -> Vec<Rect> {
+    let total: u64 = sizes.iter().sum();
+
+    if total == 0 || rect.area() <= 0.0 {
+        return vec![Rect::NOTHING; sizes.len()];
+    }
+
+    let scale = rect.area() as f64 / total as f64;
+    let areas: Vec<f64> = sizes.iter().map(|&size| size as f64 * scale).collect();
+
+    let mut rects = Vec::with_capacity(sizes.len());
+    let mut remaining = rect;
+    let mut row_start = 0;
+
+    while row_start < areas.len() {
+        let side = remaining.width().min(remaining.height()) as f64;
+        let mut row_end = row_start + 1;
+
+        while row_end < areas.len() && worst(&areas[row_start..=row_end], side) <= worst(&areas[row_start..row_end], side) {
+            row_end += 1;
+        }
+
+        remaining = layout_row(&areas[row_start..row_end], remaining, &mut rects);
+        row_start = row_end;
+    }
+
+    rects
+}
+
+fn worst(row: &[f64], side: f64) -> f64 {
+    let sum: f64 = row.iter().sum();
+    let max = row.iter().cloned().fold(f64::MIN, f64::max);
+    let min = row.iter().cloned().fold(f64::MAX, f64::min);
+
+    if sum <= 0.0 || min <= 0.0 {
+        return f64::MAX;
+    }
+
+    let side = side * side;
+    let sum = sum * sum;
+
+    (side * max / sum).max(sum / (side * min))
+}
+
+// ряд займає смугу вздовж коротшої сторони, решта прямокутника повертається
+fn layout_row(row: &[f64], rect: Rect, rects: &mut Vec<Rect>) -> Rect {
+    let sum: f64 = row.iter().sum();
+
+    if rect.width() >= rect.height() {
+        let width = (sum / rect.height() as f64) as f32;
+        let mut y = rect.top();
+
+        for &area in row {
+            let height = if width > 0.0 { area as f32 / width } else { 0.0 };
+            rects.push(Rect::from_min_max(pos2(rect.left(), y), pos2(rect.left() + width, y + height)));
+            y += height;
+        }
+
+        Rect::from_min_max(pos2(rect.left() + width, rect.top()), rect.max)
+    } else {
+        let height = (sum / rect.width() as f64) as f32;
+        let mut x = rect.left();
+
+        for &area in row {
+            let width = if height > 0.0 { area as f32 / height } else { 0.0 };
+            rects.push(Rect::from_min_max(pos2(x, rect.top()), pos2(x + width, rect.top() + height)));
+            x += width;
+        }
+
+        Rect::from_min_max(pos2(rect.left(), rect.top() + height), rect.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(rect: &Rect) -> f32 {
+        rect.width() * rect.height()
+    }
+
+    #[test]
+    fn areas_are_proportional_and_inside() {
+        let bounds = Rect::from_min_max(pos2(0.0, 0.0), pos2(600.0, 400.0));
+        let sizes = [6, 6, 4, 3, 2, 2, 1];
+        let rects = squarify(&sizes, bounds);
+
+        assert_eq!(rects.len(), sizes.len());
+
+        let scale = area(&bounds) / sizes.iter().sum::<u64>() as f32;
+
+        for (rect, &size) in rects.iter().zip(&sizes) {
+            assert!((area(rect) - size as f32 * scale).abs() < 1.0);
+            assert!(bounds.expand(0.01).contains_rect(*rect));
+        }
+
+        let total: f32 = rects.iter().map(area).sum();
+        assert!((total - area(&bounds)).abs() < 1.0);
+    }
+
+    #[test]
+    fn rectangles_do_not_overlap() {
+        let rects = squarify(&[50, 30, 10, 5, 5], Rect::from_min_max(pos2(0.0, 0.0), pos2(300.0, 200.0)));
+
+        for (i, a) in rects.iter().enumerate() {
+            for b in &rects[i + 1..] {
+                assert!(!a.shrink(0.01).intersects(b.shrink(0.01)));
+            }
+        }
+    }
+
+    #[test]
+    fn empty_input_gives_empty_rects() {
+        let bounds = Rect::from_min_max(pos2(0.0, 0.0), pos2(10.0, 10.0));
+
+        assert!(squarify(&[0, 0], bounds).iter().all(|rect| *rect == Rect::NOTHING));
+        assert!(squarify(&[], bounds).is_empty());
+    }
+}
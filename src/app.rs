@@ -1,114 +1,4785 @@
-use crate::disk::disk_list::get_disk_list;
+use crate::analysis::{
+    compare::{compare_folders, sync_folders, CompareEntry, CompareResult, DiffKind, EntryInfo, SyncMode},
+    disk_usage::{scan_usage, UsageNode, UsageProgress},
+    duplicates::{find_duplicates, resolve_duplicates, DuplicateAction, DuplicateGroup, DuplicateProgress, ResolveResult},
+    text_diff::{diff_files, side_by_side, DiffLine, DiffLineKind, TextDiffResult},
+    treemap::squarify
+};
+use crate::apps::{
+    desktop_entry::DesktopEntry,
+    mime_apps::{applications, apps_for_mime, default_app, set_default_app}
+};
+use crate::archive::{
+    extract_into,
+    extract::{extract_archive, ConflictPolicy},
+    format::{archive_stem, ArchiveFormat},
+    writer::{compress, parse_patterns, CompressOptions, CompressProgress}
+};
+use crate::disk::{
+    disk_list::get_disk_list,
+    udisks::{run_action, DriveAction, DriveWatcher, RemovableDrive}
+};
+use crate::file_ops::{
+    copy::transfer_local,
+    create::{create_file, create_folder, create_folder_in, create_from_template, templates},
+    rename::{rename_in_place, renamed_path}
+};
+use crate::icons::Icon;
+use crate::jobs::job::{BatchResult, Job};
+use crate::listing::{
+    format::{format_size, format_time},
+    sort::{sort_entries, SortKey}
+};
+use crate::mime::{detect_by_name, file_type, from_mime, FileType};
+use crate::notifications::toasts::Toasts;
+use crate::preview::content::{Preview, PreviewContent, PREVIEW_LIMIT, PREVIEW_REFRESH};
+use crate::process::launcher::Launcher;
+use crate::properties::{
+    accounts::{current_uid, groups, users, Account},
+    info::{mode_string, read_properties, FileProperties},
+    permissions::{apply_recursive, PermissionChange, RecursiveTarget},
+    size::{folder_size, FolderSize}
+};
+use crate::remote::{
+    credentials::{delete_secret, load_secret, save_secret},
+    profiles::{load_profiles, save_profiles, RemoteKind, RemoteProfile},
+    ssh_config::host_aliases
+};
+use crate::navigation::path_bar::{breadcrumbs, completions, expand_path, subfolders};
+use crate::settings::config::{config_path, load_settings, save_settings, Settings, SIDEBAR_WIDTH_RANGE};
+use crate::sidebar::{
+    bookmarks::{load_bookmarks, save_bookmarks, Bookmark},
+    folder_tree::FolderTree,
+    places::places
+};
+use crate::tabs::tab::{Tab, ViewMode};
+use crate::vfs::{
+    fetch_to_cache, Vfs, VfsEntry, VfsRegistry,
+    sftp::{connect, unknown_host_key, SftpAuth, SftpParams, SftpVfs, UnknownHostKey},
+    transfer::{transfer, TransferProgress},
+    trash::TRASH_ROOT
+};
+use crate::xdg::home_dir;
+
+use eframe::{
+    egui, 
+    egui::vec2, 
+    egui::pos2, 
+    egui::Pos2, 
+    egui::RichText, 
+    egui::CursorIcon::PointingHand,
+    egui::TopBottomPanel,
+    egui::SidePanel,
+    egui::Margin,
+    egui::Rect,
+    egui::text::CCursor,
+    egui::text::CCursorRange,
+    egui::text::LayoutJob
+};
+
+use std::{
+    collections::HashMap,
+    env,
+    fs,
+    path::Path,
+    path::PathBuf,
+    process::Command,
+    env::consts::OS,
+    io,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime}
+};
+
+// вміст віртуальної папки перечитується у фоні не частіше, ніж раз на цей проміжок
+const LISTING_REFRESH: Duration = Duration::from_secs(3);
+
+#[derive(PartialEq, Debug)]
+enum PopupType {
+    Rename,
+    RenameBookmark,
+    Settings,
+    DiskInfo,
+    Properties,
+    None
+}
+
+// індекс закладки, яку перетягують для зміни порядку
+struct DraggedBookmark(usize);
+
+// програми для меню "Відкрити за допомогою", збираються у фоні при першому показі меню для файлу
+#[derive(Debug)]
+struct OpenWithMenu {
+    path: PathBuf,
+    mime: String,
+    associated: Vec<DesktopEntry>,
+    others: Vec<DesktopEntry>,
+    default_id: Option<String>,
+    set_default: bool,
+}
+
+impl OpenWithMenu {
+    // тип файлу і mimeapps.list читаються з диска, тому меню будується не в потоці інтерфейсу
+    fn load(path: PathBuf, all_apps: &[DesktopEntry]) -> OpenWithMenu {
+        let mime = file_type(&path).mime;
+        let associated = apps_for_mime(&mime, all_apps);
+
+        OpenWithMenu {
+            default_id: default_app(&mime, all_apps).map(|entry| entry.id),
+            others: all_apps
+                .iter()
+                .filter(|entry| !entry.no_display && !associated.contains(entry))
+                .cloned()
+                .collect(),
+            associated,
+            path,
+            mime,
+            set_default: false,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum PropertiesTab {
+    General,
+    Permissions
+}
+
+#[derive(Debug)]
+struct PropertiesDialog {
+    tab: PropertiesTab,
+    properties: FileProperties,
+    // розмір папки рахується у фоні, поки відкрите вікно
+    folder_size: Option<Job<FolderSize, FolderSize>>,
+    mode: u32,
+    uid: u32,
+    gid: u32,
+    users: Vec<Account>,
+    groups: Vec<Account>,
+    recursive: bool,
+    recursive_target: RecursiveTarget,
+    apply_job: Option<Job<u64, BatchResult>>,
+    error: Option<String>,
+}
+
+impl PropertiesDialog {
+    fn cancel_jobs(&self) {
+        if let Some(job) = &self.folder_size {
+            job.cancel();
+        }
+
+        if let Some(job) = &self.apply_job {
+            job.cancel();
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum UsageSort {
+    Size,
+    Name,
+    Files
+}
+
+// index_path — індекси дітей від кореня сканування до вузла
+#[derive(Debug)]
+enum UsageAction {
+    Focus(Vec<usize>),
+    Select(Vec<usize>),
+    Open(PathBuf),
+    Trash(Vec<usize>)
+}
+
+#[derive(Debug)]
+struct DiskUsageView {
+    root: PathBuf,
+    job: Job<UsageProgress, UsageNode>,
+    tree: Option<UsageNode>,
+    focus: Vec<usize>,
+    selected: Option<Vec<usize>>,
+    sort: UsageSort,
+    error: Option<String>,
+}
+
+#[derive(Debug)]
+struct DuplicateChoice {
+    group: DuplicateGroup,
+    // індекс файлу, який залишиться
+    keep: usize,
+    selected: bool,
+}
+
+#[derive(Debug)]
+struct DuplicatesView {
+    root: PathBuf,
+    job: Job<DuplicateProgress, Vec<DuplicateGroup>>,
+    choices: Option<Vec<DuplicateChoice>>,
+    action: DuplicateAction,
+    resolve_job: Option<Job<u64, ResolveResult>>,
+}
+
+#[derive(Debug)]
+struct CompareView {
+    left_input: String,
+    right_input: String,
+    // папки, для яких отримано поточний результат
+    left: PathBuf,
+    right: PathBuf,
+    compare_content: bool,
+    job: Option<Job<u64, io::Result<CompareResult>>>,
+    result: Option<CompareResult>,
+    error: Option<String>,
+    selected: Vec<bool>,
+    sync_mode: SyncMode,
+    // синхронізація з видаленням чекає підтвердження
+    pending_sync: Option<Vec<CompareEntry>>,
+    sync_job: Option<Job<u64, BatchResult>>,
+}
+
+impl CompareView {
+    fn cancel_jobs(&self) {
+        if let Some(job) = &self.job {
+            job.cancel();
+        }
+
+        if let Some(job) = &self.sync_job {
+            job.cancel();
+        }
+    }
+}
+
+#[derive(Debug)]
+struct DiffView {
+    left: PathBuf,
+    right: PathBuf,
+    side_by_side: bool,
+    job: Job<(), Result<TextDiffResult, String>>,
+    result: Option<Result<TextDiffResult, String>>,
+}
+
+#[derive(Debug)]
+struct CompressDialog {
+    sources: Vec<PathBuf>,
+    target_dir: PathBuf,
+    // назва архіву без розширення, розширення залежить від формату
+    name: String,
+    format: ArchiveFormat,
+    level: u32,
+    exclude: String,
+    job: Option<Job<CompressProgress, BatchResult>>,
+}
+
+#[derive(Debug)]
+struct ExtractDialog {
+    archive: PathBuf,
+    target_input: String,
+    conflict: ConflictPolicy,
+    job: Option<Job<u64, BatchResult>>,
+}
+
+#[derive(Debug, Default)]
+struct ConnectDialog {
+    host: String,
+    user: String,
+    port: String,
+    use_password: bool,
+    key_path: String,
+    // пароль користувача або пароль до ключа
+    secret: String,
+    // аліаси з ~/.ssh/config, читаються при відкритті діалогу
+    aliases: Vec<String>,
+    error: Option<String>,
+    job: Option<Job<(), io::Result<SftpVfs>>>,
+    // параметри останньої спроби, щоб повторити її після підтвердження ключа
+    params: Option<SftpParams>,
+    host_key: Option<UnknownHostKey>,
+}
+
+// додавання або зміна профілю WebDAV чи S3
+#[derive(Debug, Default)]
+struct ProfileDialog {
+    // індекс профілю, що редагується; None — новий профіль
+    index: Option<usize>,
+    profile: RemoteProfile,
+    // порожній при редагуванні означає "лишити збережений"
+    secret: String,
+    error: Option<String>,
+}
+
+type ProfileConnectJob = Job<(), io::Result<Arc<dyn Vfs>>>;
+
+type Listing = (Arc<dyn Vfs>, Vec<VfsEntry>);
+
+type ListingJob = Job<(), io::Result<Listing>>;
+
+// архіви і сервери читаються у фоні; поки йде оновлення, показується попередній вміст
+#[derive(Debug, Default)]
+struct DirListing {
+    job: Option<ListingJob>,
+    result: Option<Result<Listing, String>>,
+    // None — вміст треба перечитати
+    loaded_at: Option<Instant>,
+}
+
+// шлях, час зміни з лістингу і тип, визначений за вмістом
+type TypeJob = Job<(), Vec<(PathBuf, Option<SystemTime>, FileType)>>;
+
+// після монтування результат містить нову точку монтування
+type DriveJob = Job<(), io::Result<Option<PathBuf>>>;
+
+// копіювання між локальними папками і серверами, що виконується у фоні
+#[derive(Debug)]
+struct TransferView {
+    title: String,
+    target_dir: PathBuf,
+    is_move: bool,
+    job: Job<TransferProgress, BatchResult>,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy, Hash)]
+enum Pane {
+    Left,
+    Right
+}
+
+impl Pane {
+    fn other(self) -> Pane {
+        match self {
+            Pane::Left => Pane::Right,
+            Pane::Right => Pane::Left,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FileExplorerApp {
+    settings: Settings,
+    start_dir_input: String,
+    resize_sidebar: bool,
+    tabs: Vec<Tab>,
+    active_tab: usize,
+    commander_mode: bool,
+    second_pane: Tab,
+    active_pane: Pane,
+    disk_list: Vec<char>,
+    // знімні пристрої з UDisks2, зокрема ще не змонтовані
+    removable_drives: Vec<RemovableDrive>,
+    drive_watcher: DriveWatcher,
+    drive_job: Option<(DriveAction, RemovableDrive, DriveJob)>,
+    folder_tree: FolderTree,
+    bookmarks: Vec<Bookmark>,
+    // місця і наявність папок закладок перевіряються лише при оновленні, а не кожен кадр
+    places: Vec<(String, PathBuf)>,
+    bookmark_dirs: Vec<bool>,
+    renamed_bookmark: usize,
+    is_main_context_menu_open: bool,
+    // шаблони з ~/Templates, читаються при відкритті контекстного меню
+    templates: Vec<PathBuf>,
+    interact_pointer_pos: Pos2,
+    popup_type: PopupType,
+    search: String,
+    rename: String,
+    inline_rename: Option<PathBuf>,
+    inline_rename_focus: bool,
+    path_bar_editing: bool,
+    path_bar_focus: bool,
+    path_bar_input: String,
+    path_bar_popup_rect: Option<Rect>,
+    // доповнення рахуються лише після зміни введеного тексту
+    path_bar_completions: Option<(String, Vec<String>)>,
+    history_popup_opened_by_press: bool,
+    // тип визначаємо один раз на файл, а не кожен кадр
+    file_types: HashMap<PathBuf, (Option<SystemTime>, FileType)>,
+    // файли без відомого розширення, вміст яких треба прочитати у фоні
+    type_queue: Vec<(PathBuf, Option<SystemTime>)>,
+    type_job: Option<TypeJob>,
+    open_with: Option<OpenWithMenu>,
+    open_with_job: Option<Job<(), OpenWithMenu>>,
+    // усі .desktop файли читаються один раз за запуск
+    applications: Option<Arc<Vec<DesktopEntry>>>,
+    applications_job: Option<Job<(), Vec<DesktopEntry>>>,
+    launcher: Launcher,
+    toasts: Toasts,
+    preview: Option<Preview>,
+    // шлях, для якого у фоні читається перегляд; None у результаті означає, що файл не змінився
+    preview_job: Option<(PathBuf, Job<(), Option<Preview>>)>,
+    preview_checked: Instant,
+    quick_look: bool,
+    properties: Option<PropertiesDialog>,
+    disk_usage: Option<DiskUsageView>,
+    duplicates: Option<DuplicatesView>,
+    compare: Option<CompareView>,
+    // файл, вибраний для порівняння з наступним
+    diff_candidate: Option<PathBuf>,
+    diff: Option<DiffView>,
+    vfs: VfsRegistry,
+    // скопійований елемент архіву, який можна вставити в звичайну папку
+    archive_clipboard: Option<PathBuf>,
+    compress: Option<CompressDialog>,
+    extract: Option<ExtractDialog>,
+    connect: Option<ConnectDialog>,
+    // корінь і домашня папка кожного підключеного сервера
+    remotes: Vec<(PathBuf, PathBuf)>,
+    profiles: Vec<RemoteProfile>,
+    profile_dialog: Option<ProfileDialog>,
+    // профіль, до якого зараз підключаємось
+    connecting: Option<(PathBuf, ProfileConnectJob)>,
+    transfers: Vec<TransferView>,
+    // віддалені файли, що завантажуються в кеш перед відкриттям
+    downloads: Vec<Job<(), io::Result<PathBuf>>>,
+    // вміст віртуальних папок, відкритих у вкладках
+    listings: HashMap<PathBuf, DirListing>,
+}
+
+impl Default for FileExplorerApp {
+    fn default() -> Self {
+        let path = env::current_dir().unwrap_or_default();
+        let settings = load_settings();
+
+        let mut tabs: Vec<Tab> = if settings.restore_session {
+            settings.session.tabs
+                .iter()
+                .filter(|tab_dir| tab_dir.is_dir())
+                .map(|tab_dir| Tab::new(tab_dir.clone()))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        if tabs.is_empty() {
+            let start_dir = settings.start_dir.clone().filter(|start_dir| start_dir.is_dir());
+
+            tabs.push(Tab::new(start_dir.unwrap_or(path.clone())));
+        }
+
+        for tab in &mut tabs {
+            tab.view_mode = settings.view_mode;
+        }
+
+        let active_tab = settings.session.active_tab.min(tabs.len() - 1);
+
+        Self {
+            start_dir_input: settings.start_dir.as_ref().map(|start_dir| start_dir.to_string_lossy().to_string()).unwrap_or_default(),
+            settings,
+            resize_sidebar: false,
+            tabs,
+            active_tab,
+            commander_mode: false,
+            second_pane: Tab::new(path.clone()),
+            active_pane: Pane::Left,
+            disk_list: get_disk_list(),
+            removable_drives: Vec::new(),
+            drive_watcher: DriveWatcher::default(),
+            drive_job: None,
+            folder_tree: FolderTree::default(),
+            bookmarks: load_bookmarks(),
+            places: places(),
+            bookmark_dirs: Vec::new(),
+            renamed_bookmark: 0,
+            is_main_context_menu_open: false,
+            templates: Vec::new(),
+            interact_pointer_pos: pos2(0.0, 0.0),
+            popup_type: PopupType::None,
+            search: String::new(),
+            rename: String::new(),
+            inline_rename: None,
+            inline_rename_focus: false,
+            path_bar_editing: false,
+            path_bar_focus: false,
+            path_bar_input: String::new(),
+            path_bar_popup_rect: None,
+            path_bar_completions: None,
+            history_popup_opened_by_press: false,
+            file_types: HashMap::new(),
+            type_queue: Vec::new(),
+            type_job: None,
+            open_with: None,
+            open_with_job: None,
+            applications: None,
+            applications_job: None,
+            launcher: Launcher::default(),
+            toasts: Toasts::default(),
+            preview: None,
+            preview_job: None,
+            preview_checked: Instant::now(),
+            quick_look: false,
+            properties: None,
+            disk_usage: None,
+            duplicates: None,
+            compare: None,
+            diff_candidate: None,
+            diff: None,
+            vfs: VfsRegistry::default(),
+            archive_clipboard: None,
+            compress: None,
+            extract: None,
+            connect: None,
+            remotes: Vec::new(),
+            profiles: load_profiles(),
+            profile_dialog: None,
+            connecting: None,
+            transfers: Vec::new(),
+            downloads: Vec::new(),
+            listings: HashMap::new(),
+        }
+    }
+}
+
+impl FileExplorerApp {
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let mut app = Self::default();
+
+        app.launcher.set_repaint_context(cc.egui_ctx.clone());
+        app.drive_watcher.start(cc.egui_ctx.clone());
+        app.refresh_places();
+
+        if app.settings.dark_mode {
+            cc.egui_ctx.set_visuals(egui::style::Visuals::dark());
+        } else {
+            cc.egui_ctx.set_visuals(egui::style::Visuals::light());
+        }
+
+        app
+    }
+
+    fn new_tab(&self, path: PathBuf) -> Tab {
+        let mut tab = Tab::new(path);
+
+        tab.view_mode = self.settings.view_mode;
+
+        tab
+    }
+
+    fn save_settings(&mut self) {
+        self.settings.session.tabs = self.tabs.iter().map(|tab| tab.current_dir.clone()).collect();
+        self.settings.session.active_tab = self.active_tab;
+
+        if let Err(err) = save_settings(&self.settings) {
+            println!("{:#?}", err);
+        }
+    }
+
+    fn open_file(&mut self, entry: &VfsEntry) {
+        if ArchiveFormat::from_path(&entry.path).is_some() {
+            self.navigate_to(entry.path.clone());
+        } else {
+            self.open_path(&entry.path);
+        }
+    }
+
+    fn open_path(&mut self, path: &Path) {
+        let mut command = match OS {
+            "windows" => Command::new("cmd"),
+            "linux" => Command::new("xdg-open"),
+            "macos" => Command::new("open"),
+            _ => {
+                self.toasts.push("Не вдалося відкрити файл", "операційна система не підтримується");
+                return;
+            }
+        };
+
+        // порожні лапки — заголовок вікна для start, інакше шлях сприйметься як заголовок
+        if OS == "windows" {
+            command.args(["/C", "start", ""]);
+        }
+
+        command.arg(path);
+
+        self.launcher.spawn(command);
+    }
+
+    fn open_folder(&mut self, entry: &VfsEntry) {
+        self.navigate_to(entry.path.clone());
+    }
+
+    // вкладка активної панелі: в режимі командира права панель має власну вкладку
+    fn tab(&self) -> &Tab {
+        if self.commander_mode && self.active_pane == Pane::Right {
+            &self.second_pane
+        } else {
+            &self.tabs[self.active_tab]
+        }
+    }
+
+    fn tab_mut(&mut self) -> &mut Tab {
+        if self.commander_mode && self.active_pane == Pane::Right {
+            &mut self.second_pane
+        } else {
+            &mut self.tabs[self.active_tab]
+        }
+    }
+
+    fn other_tab(&self) -> &Tab {
+        if self.active_pane == Pane::Right {
+            &self.tabs[self.active_tab]
+        } else {
+            &self.second_pane
+        }
+    }
+
+    fn toggle_commander_mode(&mut self) {
+        self.commander_mode = !self.commander_mode;
+        self.active_pane = Pane::Left;
+        self.inline_rename = None;
+
+        if self.commander_mode {
+            self.second_pane = self.new_tab(self.tab().current_dir.clone());
+        }
+    }
+
+    fn swap_panes(&mut self) {
+        std::mem::swap(&mut self.tabs[self.active_tab], &mut self.second_pane);
+
+        self.tabs[self.active_tab].restore_scroll = true;
+        self.second_pane.restore_scroll = true;
+    }
+
+    // інша панель переходить у ту ж папку, що й активна
+    fn sync_panes(&mut self) {
+        let path = self.tab().current_dir.clone();
+
+        self.active_pane = self.active_pane.other();
+
+        self.navigate_to(path);
+
+        self.active_pane = self.active_pane.other();
+    }
+
+    // F5 копіює, F6 переносить виділений елемент з активної панелі в іншу
+    fn transfer_to_other_pane(&mut self, ctx: &egui::Context, is_move: bool) {
+        let source = self.tab().selected_item.clone();
+        let target_dir = self.other_tab().current_dir.clone();
+
+        // виділення могло лишитися з іншої папки
+        if source.parent() != Some(self.tab().current_dir.as_path()) || self.other_tab().is_disk_selection {
+            return;
+        }
+
+        if self.vfs.split_archive(&source).is_some() && is_move {
+            self.toasts.push("Не вдалося перемістити", "архів відкрито лише для читання");
+            return;
+        }
+
+        self.copy_across(ctx, vec![source], target_dir, is_move);
+    }
+
+    fn handle_commander_input(&mut self, ctx: &egui::Context) {
+        if !self.commander_mode || ctx.memory(|m| m.focused().is_some()) {
+            return;
+        }
+
+        let (copy, move_item, switch_pane) = ctx.input_mut(|i| (
+            i.consume_key(egui::Modifiers::NONE, egui::Key::F5),
+            i.consume_key(egui::Modifiers::NONE, egui::Key::F6),
+            i.consume_key(egui::Modifiers::NONE, egui::Key::Tab),
+        ));
+
+        if copy {
+            self.transfer_to_other_pane(ctx, false);
+        } else if move_item {
+            self.transfer_to_other_pane(ctx, true);
+        } else if switch_pane {
+            self.active_pane = self.active_pane.other();
+            self.inline_rename = None;
+        }
+    }
+
+    fn navigate_to(&mut self, path: PathBuf) {
+        let tab = self.tab_mut();
+
+        if path != tab.current_dir || tab.is_disk_selection {
+            tab.history.visit(tab.current_dir.clone());
+        }
+
+        self.set_current_dir(path);
+    }
+
+    // перехід без запису в історію, для кнопок "назад" і "вперед"
+    fn set_current_dir(&mut self, path: PathBuf) {
+        let tab = self.tab_mut();
+
+        tab.current_dir = path;
+        tab.marked.clear();
+        tab.is_disk_selection = false;
+        tab.scroll_offset = 0.0;
+        tab.restore_scroll = true;
+
+        self.inline_rename = None;
+    }
+
+    fn go_back(&mut self, steps: usize) {
+        let tab = self.tab_mut();
+
+        if let Some(path) = tab.history.go_back(tab.current_dir.clone(), steps) {
+            self.set_current_dir(path);
+        }
+    }
+
+    fn go_forward(&mut self, steps: usize) {
+        let tab = self.tab_mut();
+
+        if let Some(path) = tab.history.go_forward(tab.current_dir.clone(), steps) {
+            self.set_current_dir(path);
+        }
+    }
+
+    fn open_tab(&mut self, path: PathBuf, activate: bool) {
+        self.tabs.insert(self.active_tab + 1, self.new_tab(path));
+
+        if activate {
+            self.switch_tab(self.active_tab + 1);
+        }
+    }
+
+    fn close_tab(&mut self, index: usize) {
+        // остання вкладка не закривається
+        if self.tabs.len() < 2 {
+            return;
+        }
+
+        self.tabs.remove(index);
+
+        if self.active_tab > index || self.active_tab == self.tabs.len() {
+            self.switch_tab(self.active_tab.saturating_sub(1));
+        } else {
+            self.switch_tab(self.active_tab);
+        }
+    }
+
+    fn switch_tab(&mut self, index: usize) {
+        self.active_tab = index;
+        self.inline_rename = None;
+        self.path_bar_editing = false;
+        self.tabs[index].restore_scroll = true;
+    }
+
+    fn handle_tab_input(&mut self, ctx: &egui::Context) {
+        let (new_tab, close_tab, prev_tab, next_tab) = ctx.input_mut(|i| (
+            i.consume_key(egui::Modifiers::COMMAND, egui::Key::T),
+            i.consume_key(egui::Modifiers::COMMAND, egui::Key::W),
+            i.consume_key(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::Tab),
+            i.consume_key(egui::Modifiers::COMMAND, egui::Key::Tab),
+        ));
+
+        if new_tab {
+            self.open_tab(self.tab().current_dir.clone(), true);
+        } else if close_tab {
+            self.close_tab(self.active_tab);
+        } else if prev_tab {
+            self.switch_tab((self.active_tab + self.tabs.len() - 1) % self.tabs.len());
+        } else if next_tab {
+            self.switch_tab((self.active_tab + 1) % self.tabs.len());
+        }
+    }
+
+    fn show_tab_bar(&mut self, ui: &mut egui::Ui) {
+        let mut switch_to: Option<usize> = None;
+        let mut close: Option<usize> = None;
+        let mut swap: Option<(usize, usize)> = None;
+
+        for index in 0..self.tabs.len() {
+            let tab_button = ui.add(
+                egui::Button::new(self.tabs[index].title())
+                    .selected(index == self.active_tab)
+                    .sense(egui::Sense::click_and_drag())
+            ).on_hover_text(self.tabs[index].current_dir.to_string_lossy());
+
+            tab_button.clone().on_hover_cursor(PointingHand);
+
+            if tab_button.clicked() {
+                switch_to = Some(index);
+            }
+
+            if tab_button.middle_clicked() {
+                close = Some(index);
+            }
+
+            // перетягування: вкладка міняється місцями з сусідньою, щойно курсор заходить за її межу
+            if tab_button.dragged()
+                && let Some(pos) = ui.input(|i| i.pointer.interact_pos())
+            {
+                if pos.x < tab_button.rect.left() && index > 0 {
+                    swap = Some((index, index - 1));
+                } else if pos.x > tab_button.rect.right() && index + 1 < self.tabs.len() {
+                    swap = Some((index, index + 1));
+                }
+            }
+
+            if self.tabs.len() > 1 {
+                let close_button = ui.add(egui::Button::image(
+                    egui::Image::new(Icon::Xmark).max_size(vec2(10.0, 10.0)),
+                ).frame(false));
+
+                if close_button.on_hover_cursor(PointingHand).clicked() {
+                    close = Some(index);
+                }
+            }
+
+            ui.add_space(5.0);
+        }
+
+        let new_tab_button = ui.add(egui::Button::image(
+            egui::Image::new(Icon::Plus).max_size(vec2(14.0, 14.0)),
+        ));
+
+        if new_tab_button.on_hover_cursor(PointingHand).clicked() {
+            self.tabs.push(self.new_tab(self.tab().current_dir.clone()));
+            self.switch_tab(self.tabs.len() - 1);
+        }
+
+        if let Some((from, to)) = swap {
+            self.tabs.swap(from, to);
+
+            if self.active_tab == from {
+                self.active_tab = to;
+            } else if self.active_tab == to {
+                self.active_tab = from;
+            }
+        } else if let Some(index) = close {
+            self.close_tab(index);
+        } else if let Some(index) = switch_to {
+            self.switch_tab(index);
+        }
+    }
+
+    fn parent_folder(&mut self) {
+        match self.tab().current_dir.parent() {
+            Some(parent) => {
+                self.navigate_to(parent.to_path_buf());
+            },
+            None => {
+                self.tab_mut().is_disk_selection = true;
+
+                self.update_disk_list();
+            }
+        }
+    }
+
+    fn handle_navigation_input(&mut self, ctx: &egui::Context) {
+        let (back, forward, up) = ctx.input_mut(|i| (
+            i.consume_key(egui::Modifiers::ALT, egui::Key::ArrowLeft) || i.pointer.button_pressed(egui::PointerButton::Extra1),
+            i.consume_key(egui::Modifiers::ALT, egui::Key::ArrowRight) || i.pointer.button_pressed(egui::PointerButton::Extra2),
+            i.consume_key(egui::Modifiers::ALT, egui::Key::ArrowUp),
+        ));
+
+        if back {
+            self.go_back(1);
+        } else if forward {
+            self.go_forward(1);
+        } else if up {
+            self.parent_folder();
+        }
+    }
+
+    // кнопка "назад"/"вперед": клік переходить на один крок, довге натискання або ПКМ показує список історії
+    fn show_history_button(&mut self, ui: &mut egui::Ui, icon: Icon, is_back: bool) {
+        let enabled = if is_back { self.tab().history.can_go_back() } else { self.tab().history.can_go_forward() };
+
+        let button = ui.add_enabled(enabled, egui::Button::image(
+            egui::Image::new(icon).max_size(vec2(20.0, 20.0)),
+        ));
+
+        let button = button.on_hover_cursor(PointingHand);
+        let popup_id = button.id.with("history_popup");
+
+        let long_press = button.is_pointer_button_down_on() && ui.input(|i| {
+            i.pointer.press_start_time().is_some_and(|start| i.time - start > 0.5)
+        });
+
+        if button.is_pointer_button_down_on() {
+            ui.ctx().request_repaint();
+        }
+
+        if (long_press && !ui.memory(|m| m.is_popup_open(popup_id))) || button.secondary_clicked() {
+            self.history_popup_opened_by_press = long_press;
+
+            ui.memory_mut(|m| m.open_popup(popup_id));
+        }
+
+        if button.clicked() {
+            if self.history_popup_opened_by_press {
+                self.history_popup_opened_by_press = false;
+            } else if is_back {
+                self.go_back(1);
+            } else {
+                self.go_forward(1);
+            }
+        }
+
+        let entries: Vec<PathBuf> = if is_back {
+            self.tab().history.back_entries().take(15).cloned().collect()
+        } else {
+            self.tab().history.forward_entries().take(15).cloned().collect()
+        };
+
+        let mut jump_steps: Option<usize> = None;
+
+        egui::popup_below_widget(ui, popup_id, &button, egui::PopupCloseBehavior::CloseOnClick, |ui| {
+            ui.set_min_width(250.0);
+
+            for (index, entry) in entries.iter().enumerate() {
+                if ui.selectable_label(false, entry.to_string_lossy()).on_hover_cursor(PointingHand).clicked() {
+                    jump_steps = Some(index + 1);
+                }
+            }
+        });
+
+        if let Some(steps) = jump_steps {
+            if is_back {
+                self.go_back(steps);
+            } else {
+                self.go_forward(steps);
+            }
+        }
+    }
+
+    // знімні диски читає потік DriveWatcher, список прийде в handle_drive_events
+    fn update_disk_list(&mut self) {
+        self.disk_list = get_disk_list();
+        self.drive_watcher.refresh();
+    }
+
+    fn set_removable_drives(&mut self, drives: Vec<RemovableDrive>) {
+        let mounted_before: Vec<PathBuf> = self.removable_drives.iter().filter_map(|drive| drive.mount_point.clone()).collect();
+
+        self.removable_drives = drives;
+
+        // вкладки на відмонтованому або від'єднаному пристрої повертаються в домашню папку
+        for mount_point in mounted_before {
+            if self.removable_drives.iter().any(|drive| drive.mount_point.as_ref() == Some(&mount_point)) {
+                continue;
+            }
+
+            for tab in self.tabs.iter_mut().chain(std::iter::once(&mut self.second_pane)) {
+                if tab.current_dir.starts_with(&mount_point) {
+                    tab.current_dir = home_dir();
+                    tab.marked.clear();
+                }
+            }
+        }
+    }
+
+    fn run_drive_action(&mut self, ctx: &egui::Context, action: DriveAction, drive: &RemovableDrive) {
+        if self.drive_job.is_some() {
+            return;
+        }
+
+        let job_drive = drive.clone();
+
+        self.drive_job = Some((action, drive.clone(), Job::spawn(ctx, move |_| run_action(action, &job_drive))));
+    }
+
+    fn open_drive(&mut self, ctx: &egui::Context, drive: &RemovableDrive) {
+        match &drive.mount_point {
+            Some(mount_point) => self.navigate_to(mount_point.clone()),
+            None => self.run_drive_action(ctx, DriveAction::Mount, drive),
+        }
+    }
+
+    // сигнали про підключення пристроїв і завершення монтування оновлюють список дисків без кнопки Update
+    fn handle_drive_events(&mut self) {
+        let mut latest = None;
+
+        for received in self.drive_watcher.receive() {
+            match received {
+                Ok(drives) => latest = Some(drives),
+                Err(err) => self.toasts.push("Не вдалося отримати список знімних дисків", err),
+            }
+        }
+
+        if let Some(drives) = latest {
+            self.disk_list = get_disk_list();
+            self.set_removable_drives(drives);
+            self.folder_tree.refresh();
+        }
+
+        if let Some((_, _, job)) = &mut self.drive_job {
+            job.poll();
+        }
+
+        let Some((action, drive, job)) = self.drive_job.take_if(|(_, _, job)| job.is_finished()) else {
+            return;
+        };
+
+        match job.result {
+            Some(Ok(mount_point)) => {
+                self.update_disk_list();
+                self.folder_tree.refresh();
+
+                if let Some(mount_point) = mount_point {
+                    self.navigate_to(mount_point);
+                }
+
+                if action == DriveAction::Eject {
+                    self.toasts.push("Пристрій можна від'єднати", drive.label);
+                }
+            },
+            Some(Err(err)) => {
+                let title = match action {
+                    DriveAction::Mount => "Не вдалося змонтувати",
+                    DriveAction::Unmount => "Не вдалося відмонтувати",
+                    DriveAction::Eject => "Не вдалося безпечно вилучити",
+                };
+
+                self.toasts.push(format!("{} {}", title, drive.label), err.to_string());
+            },
+            None => {},
+        }
+    }
+
+    fn is_drive_busy(&self, drive: &RemovableDrive) -> bool {
+        self.drive_job.as_ref().is_some_and(|(_, busy_drive, _)| busy_drive.object == drive.object)
+    }
+
+    fn show_drive_actions(&mut self, ui: &mut egui::Ui, drive: &RemovableDrive) {
+        let idle = self.drive_job.is_none();
+        let mut action: Option<DriveAction> = None;
+
+        if drive.mount_point.is_none() {
+            if ui.add_enabled(idle, egui::Button::new("Змонтувати")).on_hover_cursor(PointingHand).clicked() {
+                action = Some(DriveAction::Mount);
+            }
+        } else if ui.add_enabled(idle, egui::Button::new("Відмонтувати")).on_hover_cursor(PointingHand).clicked() {
+            action = Some(DriveAction::Unmount);
+        }
+
+        if drive.can_eject && ui.add_enabled(idle, egui::Button::new("Безпечно вилучити")).on_hover_cursor(PointingHand).clicked() {
+            action = Some(DriveAction::Eject);
+        }
+
+        if let Some(action) = action {
+            self.run_drive_action(&ui.ctx().clone(), action, drive);
+            ui.close_menu();
+        }
+    }
+
+    // виділяє щойно створений елемент і одразу переводить його в режим перейменування
+    fn on_item_created(&mut self, created: io::Result<PathBuf>) {
+        match created {
+            Ok(path) => {
+                self.reload_listings();
+                self.rename = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                self.tab_mut().selected_item = path.clone();
+                self.inline_rename = Some(path);
+                self.inline_rename_focus = true;
+            },
+            Err(err) => {
+                self.toasts.push("Не вдалося створити", err.to_string());
+            }
+        }
+    }
+
+    fn show_inline_rename(&mut self, ui: &mut egui::Ui, icon: Icon) {
+        ui.horizontal(|ui| {
+            ui.add(egui::Image::new(icon).max_size(vec2(14.0, 14.0)));
+
+            let mut output = egui::TextEdit::singleline(&mut self.rename).show(ui);
+
+            if self.inline_rename_focus {
+                self.inline_rename_focus = false;
+
+                // виділяємо ім'я без розширення, як це роблять інші файлові менеджери
+                let stem_len = match self.rename.rfind('.') {
+                    Some(dot) if dot > 0 && !self.tab().selected_item.is_dir() => self.rename[..dot].chars().count(),
+                    _ => self.rename.chars().count(),
+                };
+
+                output.state.cursor.set_char_range(Some(CCursorRange::two(CCursor::new(0), CCursor::new(stem_len))));
+                output.state.store(ui.ctx(), output.response.id);
+                output.response.request_focus();
+            }
+
+            if output.response.lost_focus()
+                && let Some(path) = self.inline_rename.take()
+                && !ui.input(|i| i.key_pressed(egui::Key::Escape))
+            {
+                match self.rename_item(&path, &self.rename.clone()) {
+                    Ok(new_path) => {
+                        self.tab_mut().selected_item = new_path;
+                    },
+                    Err(err) => {
+                        self.toasts.push("Не вдалося перейменувати", err.to_string());
+                    }
+                }
+            }
+        });
+    }
+    fn show_path_bar(&mut self, ui: &mut egui::Ui) {
+        if self.path_bar_editing {
+            self.show_path_input(ui);
+
+            return;
+        }
+
+        let mut navigate_to: Option<PathBuf> = None;
+
+        ui.spacing_mut().item_spacing.x = 2.0;
+
+        for breadcrumb in breadcrumbs(&self.tab().current_dir) {
+            if ui.button(&breadcrumb.label).on_hover_cursor(PointingHand).clicked() {
+                navigate_to = Some(breadcrumb.path.clone());
+            }
+
+            // стрілка після сегмента показує його підпапки, тобто сусідів наступного сегмента
+            ui.menu_image_button(
+                egui::Image::new(Icon::AngleRight).max_size(vec2(10.0, 10.0)),
+                |ui| {
+                    egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                        for folder in subfolders(&breadcrumb.path, false) {
+                            let name = folder.file_name().unwrap_or_default().to_string_lossy().to_string();
+
+                            if ui.button(name).on_hover_cursor(PointingHand).clicked() {
+                                navigate_to = Some(folder);
+
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                },
+            );
+        }
+
+        let edit_button = ui.add(egui::Button::image(
+            egui::Image::new(Icon::Pen).max_size(vec2(14.0, 14.0)),
+        ).frame(false));
+
+        // клік по вільному місці панелі теж перемикає її в режим редагування
+        let free_space = ui.allocate_response(ui.available_size_before_wrap(), egui::Sense::click());
+
+        if edit_button.on_hover_cursor(PointingHand).clicked() || free_space.clicked() {
+            self.path_bar_editing = true;
+            self.path_bar_focus = true;
+            self.path_bar_input = self.tab().current_dir.to_string_lossy().to_string();
+            self.path_bar_completions = None;
+        }
+
+        if let Some(path) = navigate_to {
+            self.navigate_to(path);
+        }
+    }
+
+    fn show_path_input(&mut self, ui: &mut egui::Ui) {
+        let mut output = egui::TextEdit::singleline(&mut self.path_bar_input)
+            .desired_width(f32::INFINITY)
+            .lock_focus(true)
+            .show(ui);
+
+        if self.path_bar_completions.as_ref().is_none_or(|(input, _)| *input != self.path_bar_input) {
+            let completions = completions(&self.tab().current_dir, &self.path_bar_input);
+
+            self.path_bar_completions = Some((self.path_bar_input.clone(), completions));
+        }
+
+        let completions = self.path_bar_completions.as_ref().map(|(_, completions)| completions.clone()).unwrap_or_default();
+
+        if output.response.has_focus() && ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Tab)) {
+            if let Some(completed) = common_prefix(&completions)
+                && completed.len() > self.path_bar_input.len()
+            {
+                self.path_bar_input = completed;
+            }
+
+            self.path_bar_focus = true;
+        }
+
+        if self.path_bar_focus {
+            self.path_bar_focus = false;
+
+            let end = CCursor::new(self.path_bar_input.chars().count());
+
+            output.state.cursor.set_char_range(Some(CCursorRange::one(end)));
+            output.state.store(ui.ctx(), output.response.id);
+            output.response.request_focus();
+        }
+
+        let mut picked: Option<String> = None;
+
+        self.path_bar_popup_rect = if completions.is_empty() {
+            None
+        } else {
+            let area = egui::Area::new(ui.id().with("path_bar_completions"))
+                .order(egui::Order::Foreground)
+                .fixed_pos(output.response.rect.left_bottom())
+                .show(ui.ctx(), |ui| {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.set_min_width(output.response.rect.width());
+
+                        egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                            for completion in &completions {
+                                if ui.selectable_label(false, completion).on_hover_cursor(PointingHand).clicked() {
+                                    picked = Some(completion.clone());
+                                }
+                            }
+                        });
+                    });
+                });
+
+            Some(area.response.rect)
+        };
+
+        if let Some(completion) = picked {
+            self.path_bar_input = completion;
+            self.path_bar_focus = true;
+
+            return;
+        }
+
+        if output.response.lost_focus() {
+            if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                let path = self.tab().current_dir.join(expand_path(&self.path_bar_input));
+
+                // віртуальна папка перевіряється вже під час завантаження її вмісту
+                let is_dir = if self.vfs.is_local(&path) { path.is_dir() } else { true };
+
+                if is_dir {
+                    self.path_bar_editing = false;
+                    self.navigate_to(path);
+                } else if path.is_file() {
+                    self.path_bar_editing = false;
+                    self.navigate_to(path.parent().map(PathBuf::from).unwrap_or_default());
+                    self.tab_mut().selected_item = path;
+                } else {
+                    self.toasts.push("Шлях не знайдено", path.display().to_string());
+
+                    self.path_bar_focus = true;
+                }
+            } else {
+                let clicked_completion = ui.input(|i| i.pointer.interact_pos()).is_some_and(|pos| {
+                    self.path_bar_popup_rect.is_some_and(|rect| rect.contains(pos))
+                });
+
+                if !clicked_completion {
+                    self.path_bar_editing = false;
+                    self.path_bar_popup_rect = None;
+                }
+            }
+        }
+    }
+    fn add_bookmark(&mut self, path: &Path) {
+        let bookmark = Bookmark::from_path(path);
+
+        if !self.bookmarks.iter().any(|existing| existing.uri == bookmark.uri) {
+            self.bookmarks.push(bookmark);
+
+            self.save_bookmarks();
+        }
+    }
+
+    fn save_bookmarks(&mut self) {
+        if let Err(err) = save_bookmarks(&self.bookmarks) {
+            println!("{:#?}", err);
+        }
+
+        self.refresh_places();
+    }
+
+    fn refresh_places(&mut self) {
+        self.places = places();
+        self.bookmark_dirs = self.bookmarks.iter()
+            .map(|bookmark| bookmark.path().is_some_and(|path| path.is_dir()))
+            .collect();
+    }
+
+    fn show_places(&mut self, ui: &mut egui::Ui) {
+        let current_dir = self.tab().current_dir.clone();
+
+        let (_, dropped) = ui.dnd_drop_zone::<PathBuf, ()>(egui::Frame::NONE, |ui| {
+            ui.label(RichText::new("Місця").strong());
+            ui.add_space(5.0);
+
+            for (label, path) in self.places.clone() {
+                let icon = if path == home_dir() {
+                    Icon::House
+                } else if path == Path::new(TRASH_ROOT) {
+                    Icon::Trash
+                } else {
+                    Icon::Folder
+                };
+
+                let place_button = ui.add(egui::Button::image_and_text(
+                    egui::Image::new(icon).max_size(vec2(14.0, 14.0)),
+                    RichText::new(label).size(14.0),
+                ).frame(false).selected(current_dir == path));
+
+                let place_button = place_button.on_hover_cursor(PointingHand);
+
+                if place_button.clicked() {
+                    self.navigate_to(path.clone());
+                }
+
+                if place_button.middle_clicked() {
+                    self.open_tab(path.clone(), false);
+                }
+
+                if self.vfs.is_local(&path) {
+                    self.handle_folder_drop(ui, &place_button, &path);
+                }
+            }
+
+            let mut reorder: Option<(usize, usize)> = None;
+            let mut remove: Option<usize> = None;
+
+            for (index, bookmark) in self.bookmarks.clone().into_iter().enumerate() {
+                let path = bookmark.path();
+
+                let bookmark_button = ui.add_enabled(self.bookmark_dirs.get(index).copied().unwrap_or(false), egui::Button::image_and_text(
+                    egui::Image::new(Icon::Bookmark).max_size(vec2(14.0, 14.0)),
+                    RichText::new(bookmark.title()).size(14.0),
+                ).frame(false).selected(path.as_ref() == Some(&current_dir)));
+
+                let bookmark_button = bookmark_button
+                    .on_hover_text(path.as_ref().map(|path| path.to_string_lossy().to_string()).unwrap_or(bookmark.uri.clone()))
+                    .on_hover_cursor(PointingHand);
+
+                if let Some(path) = &path {
+                    if bookmark_button.clicked() {
+                        self.navigate_to(path.clone());
+                    }
+
+                    if bookmark_button.middle_clicked() {
+                        self.open_tab(path.clone(), false);
+                    }
+                }
+
+                bookmark_button.interact(egui::Sense::drag()).dnd_set_drag_payload(DraggedBookmark(index));
+
+                if let Some(dragged) = bookmark_button.dnd_release_payload::<DraggedBookmark>() {
+                    reorder = Some((dragged.0, index));
+                }
+
+                bookmark_button.context_menu(|ui| {
+                    if ui.button("Перейменувати").on_hover_cursor(PointingHand).clicked() {
+                        self.popup_type = PopupType::RenameBookmark;
+                        self.renamed_bookmark = index;
+                        self.rename = bookmark.title();
+
+                        ui.close_menu();
+                    }
+                    if index > 0 && ui.button("Перемістити вгору").on_hover_cursor(PointingHand).clicked() {
+                        reorder = Some((index, index - 1));
+
+                        ui.close_menu();
+                    }
+                    if index + 1 < self.bookmarks.len() && ui.button("Перемістити вниз").on_hover_cursor(PointingHand).clicked() {
+                        reorder = Some((index, index + 1));
+
+                        ui.close_menu();
+                    }
+                    if ui.button("Видалити закладку").on_hover_cursor(PointingHand).clicked() {
+                        remove = Some(index);
+
+                        ui.close_menu();
+                    }
+
+                    self.is_main_context_menu_open = false;
+                });
+            }
+
+            if let Some((from, to)) = reorder
+                && from != to
+            {
+                let bookmark = self.bookmarks.remove(from);
+
+                self.bookmarks.insert(to, bookmark);
+                self.save_bookmarks();
+            } else if let Some(index) = remove {
+                self.bookmarks.remove(index);
+                self.save_bookmarks();
+            }
+        });
+
+        // папка, кинута на розділ (а не на конкретне місце), додається в закладки
+        if let Some(path) = dropped
+            && path.is_dir()
+        {
+            self.add_bookmark(&path);
+        }
+    }
+
+    fn mount_remote(&mut self, root: PathBuf, home: PathBuf, vfs: Arc<dyn Vfs>) {
+        self.vfs.mount(root.clone(), vfs);
+        self.remotes.retain(|(remote_root, _)| *remote_root != root);
+        self.remotes.push((root, home));
+    }
+
+    fn connect_profile(&mut self, ctx: &egui::Context, profile: &RemoteProfile) {
+        let profile = profile.clone();
+
+        self.connecting = Some((profile.root(), Job::spawn(ctx, move |_| profile.connect())));
+    }
+
+    fn show_remotes(&mut self, ui: &mut egui::Ui) {
+        let current_dir = self.tab().current_dir.clone();
+
+        if let Some((_, job)) = &mut self.connecting {
+            job.poll();
+        }
+
+        if let Some((root, job)) = self.connecting.take_if(|(_, job)| job.is_finished()) {
+            match job.result {
+                Some(Ok(vfs)) => {
+                    self.mount_remote(root.clone(), root.clone(), vfs);
+                    self.navigate_to(root);
+                },
+                Some(Err(err)) => self.toasts.push("Не вдалося підключитися", err.to_string()),
+                None => {},
+            }
+        }
+
+        ui.label(RichText::new("Мережа").strong());
+        ui.add_space(5.0);
+
+        let mut disconnect: Option<PathBuf> = None;
+        let mut edit: Option<usize> = None;
+        let mut remove: Option<usize> = None;
+
+        for (index, profile) in self.profiles.clone().into_iter().enumerate() {
+            let root = profile.root();
+            let is_connected = self.remotes.iter().any(|(remote_root, _)| *remote_root == root);
+            let is_connecting = self.connecting.as_ref().is_some_and(|(connecting_root, _)| *connecting_root == root);
+
+            let profile_button = ui.horizontal(|ui| {
+                let button = ui.add(egui::Button::image_and_text(
+                    egui::Image::new(Icon::Cloud).max_size(vec2(14.0, 14.0)),
+                    RichText::new(&profile.name).size(14.0),
+                ).frame(false).selected(current_dir.starts_with(&root)));
+
+                if is_connecting {
+                    ui.spinner();
+                }
+
+                button
+            }).inner;
+
+            let profile_button = profile_button
+                .on_hover_text(format!("{}: {}", profile.kind.label(), profile.url))
+                .on_hover_cursor(PointingHand);
+
+            if profile_button.clicked() {
+                if is_connected {
+                    self.navigate_to(root.clone());
+                } else if self.connecting.is_none() {
+                    self.connect_profile(ui.ctx(), &profile);
+                }
+            }
+
+            if is_connected {
+                self.handle_folder_drop(ui, &profile_button, &root);
+            }
+
+            profile_button.context_menu(|ui| {
+                if ui.button("Змінити…").on_hover_cursor(PointingHand).clicked() {
+                    edit = Some(index);
+                    ui.close_menu();
+                }
+                if is_connected && ui.button("Відключитися").on_hover_cursor(PointingHand).clicked() {
+                    disconnect = Some(root.clone());
+                    ui.close_menu();
+                }
+                if ui.button("Видалити профіль").on_hover_cursor(PointingHand).clicked() {
+                    remove = Some(index);
+                    ui.close_menu();
+                }
+
+                self.is_main_context_menu_open = false;
+            });
+        }
+
+        // сервери sftp, підключені без профілю
+        for (root, home) in self.remotes.clone() {
+            if self.profiles.iter().any(|profile| profile.root() == root) {
+                continue;
+            }
+
+            let remote_button = ui.add(egui::Button::image_and_text(
+                egui::Image::new(Icon::Server).max_size(vec2(14.0, 14.0)),
+                RichText::new(root.to_string_lossy()).size(14.0),
+            ).frame(false).selected(current_dir.starts_with(&root)));
+
+            let remote_button = remote_button.on_hover_cursor(PointingHand);
+
+            if remote_button.clicked() {
+                self.navigate_to(home.clone());
+            }
+
+            if remote_button.middle_clicked() {
+                self.open_tab(home.clone(), false);
+            }
+
+            self.handle_folder_drop(ui, &remote_button, &home);
+
+            remote_button.context_menu(|ui| {
+                if ui.button("Відключитися").on_hover_cursor(PointingHand).clicked() {
+                    disconnect = Some(root.clone());
+                    ui.close_menu();
+                }
+
+                self.is_main_context_menu_open = false;
+            });
+        }
+
+        if let Some(root) = disconnect {
+            self.disconnect(&root);
+        }
+
+        if let Some(index) = edit {
+            self.profile_dialog = Some(ProfileDialog {
+                index: Some(index),
+                profile: self.profiles[index].clone(),
+                ..Default::default()
+            });
+        }
+
+        if let Some(index) = remove {
+            self.remove_profile(index);
+        }
+
+        let connect_button = ui.add(egui::Button::image_and_text(
+            egui::Image::new(Icon::Plus).max_size(vec2(14.0, 14.0)),
+            RichText::new("Підключитися до сервера…").size(14.0),
+        ).frame(false));
+
+        if connect_button.on_hover_cursor(PointingHand).clicked() && self.connect.is_none() {
+            self.connect = Some(ConnectDialog {
+                aliases: host_aliases(),
+                ..Default::default()
+            });
+        }
+
+        let profile_button = ui.add(egui::Button::image_and_text(
+            egui::Image::new(Icon::Plus).max_size(vec2(14.0, 14.0)),
+            RichText::new("Додати WebDAV або S3…").size(14.0),
+        ).frame(false));
+
+        if profile_button.on_hover_cursor(PointingHand).clicked() && self.profile_dialog.is_none() {
+            self.profile_dialog = Some(ProfileDialog::default());
+        }
+    }
+
+    fn remove_profile(&mut self, index: usize) {
+        let profile = self.profiles.remove(index);
+
+        self.disconnect(&profile.root());
+
+        if let Err(err) = delete_secret(&profile.name) {
+            self.toasts.push("Не вдалося видалити пароль профілю", err.to_string());
+        }
+
+        if let Err(err) = save_profiles(&self.profiles) {
+            self.toasts.push("Не вдалося зберегти профілі", err.to_string());
+        }
+    }
+
+    fn validate_profile(&self, dialog: &ProfileDialog) -> Result<(), String> {
+        let profile = &dialog.profile;
+
+        if profile.name.trim().is_empty() {
+            return Err("Вкажіть назву".to_string());
+        }
+
+        if profile.name.contains(['/', '\\']) {
+            return Err("Назва не може містити «/»".to_string());
+        }
+
+        let is_taken = self.profiles.iter()
+            .enumerate()
+            .any(|(index, other)| Some(index) != dialog.index && other.name == profile.name.trim());
+
+        if is_taken {
+            return Err("Профіль з такою назвою вже є".to_string());
+        }
+
+        if !profile.url.trim().starts_with("http://") && !profile.url.trim().starts_with("https://") {
+            return Err("Адреса має починатися з http:// або https://".to_string());
+        }
+
+        if profile.kind == RemoteKind::S3 && profile.bucket.trim().is_empty() {
+            return Err("Вкажіть кошик".to_string());
+        }
+
+        Ok(())
+    }
+
+    // пароль переноситься разом із профілем, якщо його перейменували
+    fn store_profile(&mut self, dialog: &ProfileDialog) -> Result<(), String> {
+        self.validate_profile(dialog)?;
+
+        let mut profile = dialog.profile.clone();
+        profile.name = profile.name.trim().to_string();
+        profile.url = profile.url.trim().to_string();
+
+        let old_profile = dialog.index.map(|index| self.profiles[index].clone());
+        let old_name = old_profile.as_ref().map(|old_profile| old_profile.name.clone());
+
+        let stored = if !dialog.secret.is_empty() {
+            save_secret(&profile.name, &dialog.secret)
+        } else if let Some(old_name) = &old_name
+            && *old_name != profile.name
+        {
+            load_secret(old_name).and_then(|secret| save_secret(&profile.name, &secret))
+        } else {
+            Ok(())
+        };
+
+        stored.map_err(|err| format!("Не вдалося зберегти пароль: {}", err))?;
+
+        if let Some(old_name) = &old_name
+            && *old_name != profile.name
+            && let Err(err) = delete_secret(old_name)
+        {
+            self.toasts.push("Не вдалося видалити старий пароль профілю", err.to_string());
+        }
+
+        // змінений профіль підключиться заново з новими параметрами
+        if let Some(old_profile) = old_profile {
+            self.disconnect(&old_profile.root());
+        }
+
+        match dialog.index {
+            Some(index) => self.profiles[index] = profile,
+            None => self.profiles.push(profile),
+        }
+
+        save_profiles(&self.profiles).map_err(|err| format!("Не вдалося зберегти профілі: {}", err))
+    }
+
+    fn show_profile_dialog(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = self.profile_dialog.as_mut() else {
+            return;
+        };
+
+        let mut open = true;
+        let mut save = false;
+
+        egui::Window::new("Профіль підключення")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let profile = &mut dialog.profile;
+                let is_s3 = profile.kind == RemoteKind::S3;
+
+                egui::Grid::new("profile_grid").num_columns(2).spacing(vec2(12.0, 6.0)).show(ui, |ui| {
+                    ui.label("Тип");
+                    ui.horizontal(|ui| {
+                        for kind in RemoteKind::ALL {
+                            ui.radio_value(&mut profile.kind, kind, kind.label());
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Назва");
+                    ui.text_edit_singleline(&mut profile.name);
+                    ui.end_row();
+
+                    ui.label("Адреса");
+                    ui.add(egui::TextEdit::singleline(&mut profile.url)
+                        .hint_text(if is_s3 { "http://localhost:9000" } else { "https://example.com/dav" })
+                        .desired_width(300.0));
+                    ui.end_row();
+
+                    if is_s3 {
+                        ui.label("Кошик");
+                        ui.text_edit_singleline(&mut profile.bucket);
+                        ui.end_row();
+
+                        ui.label("Регіон");
+                        ui.add(egui::TextEdit::singleline(&mut profile.region).hint_text("us-east-1"));
+                        ui.end_row();
+                    }
+
+                    ui.label(if is_s3 { "Ключ доступу" } else { "Користувач" });
+                    ui.text_edit_singleline(&mut profile.user);
+                    ui.end_row();
+
+                    ui.label(if is_s3 { "Секретний ключ" } else { "Пароль" });
+                    ui.add(egui::TextEdit::singleline(&mut dialog.secret)
+                        .password(true)
+                        .hint_text(if dialog.index.is_some() { "без змін" } else { "" }));
+                    ui.end_row();
+                });
+
+                if ui.button("Зберегти").on_hover_cursor(PointingHand).clicked() {
+                    save = true;
+                }
+
+                if let Some(error) = &dialog.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+            });
+
+        if !open {
+            self.profile_dialog = None;
+        } else if save
+            && let Some(mut dialog) = self.profile_dialog.take()
+            && let Err(err) = self.store_profile(&dialog)
+        {
+            dialog.error = Some(err);
+            self.profile_dialog = Some(dialog);
+        }
+    }
+
+    // вкладки, відкриті на сервері, повертаються в домашню папку
+    fn disconnect(&mut self, root: &Path) {
+        self.vfs.unmount(root);
+        self.remotes.retain(|(remote_root, _)| remote_root != root);
+
+        for tab in self.tabs.iter_mut().chain(std::iter::once(&mut self.second_pane)) {
+            if tab.current_dir.starts_with(root) {
+                tab.current_dir = home_dir();
+                tab.marked.clear();
+            }
+        }
+    }
+
+    fn show_connect(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = self.connect.as_mut() else {
+            return;
+        };
+
+        if let Some(job) = &mut dialog.job {
+            job.poll();
+        }
+
+        if let Some(job) = dialog.job.take_if(|job| job.is_finished()) {
+            match job.result {
+                Some(Ok(sftp)) => {
+                    let (root, home) = (sftp.root().to_path_buf(), sftp.home().to_path_buf());
+
+                    self.mount_remote(root, home.clone(), Arc::new(sftp));
+                    self.connect = None;
+
+                    self.navigate_to(home);
+                    return;
+                },
+                Some(Err(err)) => match unknown_host_key(&err) {
+                    Some(host_key) => dialog.host_key = Some(host_key.clone()),
+                    None => dialog.error = Some(err.to_string()),
+                },
+                None => {},
+            }
+        }
+
+        let mut open = true;
+
+        egui::Window::new("Підключення до сервера")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let is_busy = dialog.job.is_some() || dialog.host_key.is_some();
+
+                ui.add_enabled_ui(!is_busy, |ui| {
+                    egui::Grid::new("connect_grid").num_columns(2).spacing(vec2(12.0, 6.0)).show(ui, |ui| {
+                        ui.label("Сервер");
+                        ui.horizontal(|ui| {
+                            ui.add(egui::TextEdit::singleline(&mut dialog.host).hint_text("адреса або аліас з ~/.ssh/config"));
+
+                            if !dialog.aliases.is_empty() {
+                                ui.menu_button("▾", |ui| {
+                                    for alias in &dialog.aliases {
+                                        if ui.button(alias).on_hover_cursor(PointingHand).clicked() {
+                                            dialog.host = alias.clone();
+                                            ui.close_menu();
+                                        }
+                                    }
+                                });
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Користувач");
+                        ui.add(egui::TextEdit::singleline(&mut dialog.user).hint_text("з ~/.ssh/config"));
+                        ui.end_row();
+
+                        ui.label("Порт");
+                        ui.add(egui::TextEdit::singleline(&mut dialog.port).hint_text("22").desired_width(60.0));
+                        ui.end_row();
+
+                        ui.label("Вхід");
+                        ui.horizontal(|ui| {
+                            ui.radio_value(&mut dialog.use_password, false, "Ключ SSH");
+                            ui.radio_value(&mut dialog.use_password, true, "Пароль");
+                        });
+                        ui.end_row();
+
+                        if !dialog.use_password {
+                            ui.label("Файл ключа");
+                            ui.add(egui::TextEdit::singleline(&mut dialog.key_path).hint_text("ssh-agent або ~/.ssh/id_*"));
+                            ui.end_row();
+                        }
+
+                        ui.label(if dialog.use_password { "Пароль" } else { "Пароль ключа" });
+                        ui.add(egui::TextEdit::singleline(&mut dialog.secret).password(true));
+                        ui.end_row();
+                    });
+
+                    let port = dialog.port.trim().parse::<u16>().ok();
+                    let port_valid = dialog.port.trim().is_empty() || port.is_some();
+
+                    if ui.add_enabled(!dialog.host.trim().is_empty() && port_valid, egui::Button::new("Підключитися")).on_hover_cursor(PointingHand).clicked() {
+                        let auth = if dialog.use_password {
+                            SftpAuth::Password(dialog.secret.clone())
+                        } else {
+                            SftpAuth::Key {
+                                path: (!dialog.key_path.trim().is_empty()).then(|| expand_path(&dialog.key_path)),
+                                passphrase: dialog.secret.clone(),
+                            }
+                        };
+
+                        let params = SftpParams {
+                            host: dialog.host.trim().to_string(),
+                            user: dialog.user.clone(),
+                            port,
+                            auth,
+                            trusted_key: None,
+                        };
+
+                        dialog.error = None;
+                        dialog.params = Some(params.clone());
+                        dialog.job = Some(Job::spawn(ctx, move |_| connect(&params)));
+                    }
+                });
+
+                // ключ довіряється лише цьому відбитку: якщо сервер тим часом надішле інший, знову питаємо
+                if let Some(host_key) = dialog.host_key.clone() {
+                    ui.separator();
+                    ui.label(format!("Сервер {} ще не відомий. Перевірте відбиток його ключа:", host_key.host));
+                    ui.monospace(&host_key.fingerprint);
+
+                    ui.horizontal(|ui| {
+                        if ui.button("Довіряти і підключитися").on_hover_cursor(PointingHand).clicked()
+                            && let Some(mut params) = dialog.params.clone()
+                        {
+                            params.trusted_key = Some(host_key.fingerprint.clone());
+
+                            dialog.host_key = None;
+                            dialog.job = Some(Job::spawn(ctx, move |_| connect(&params)));
+                        }
+
+                        if ui.button("Скасувати").on_hover_cursor(PointingHand).clicked() {
+                            dialog.host_key = None;
+                        }
+                    });
+                }
+
+                if dialog.job.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Підключення…");
+                    });
+                }
+
+                if let Some(error) = &dialog.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+            });
+
+        // незавершене підключення не скасовується, його результат просто відкидається
+        if !open {
+            self.connect = None;
+        }
+    }
+
+    // томи для дерева папок: букви дисків на windows, корінь файлової системи на інших ос і змонтовані знімні пристрої
+    fn volume_roots(&self) -> Vec<(String, PathBuf)> {
+        let mut roots: Vec<(String, PathBuf)> = self.disk_list
+            .iter()
+            .map(|disk| (disk.to_string(), PathBuf::from(format!("{}:\\", disk))))
+            .collect();
+
+        if OS != "windows" {
+            roots.push(("/".to_string(), PathBuf::from("/")));
+        }
+
+        for drive in &self.removable_drives {
+            if let Some(mount_point) = &drive.mount_point {
+                roots.push((drive.label.clone(), mount_point.clone()));
+            }
+        }
+
+        roots
+    }
+
+    fn show_folder_tree_node(&mut self, ui: &mut egui::Ui, path: PathBuf, label: String, is_volume: bool, reveal: bool) {
+        let current_dir = self.tab().current_dir.clone();
+        let id = ui.make_persistent_id(("folder_tree", &path));
+        let mut state = egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, false);
+
+        if reveal && current_dir.starts_with(&path) && current_dir != path {
+            state.set_open(true);
+        }
+
+        let icon = if is_volume { Icon::HardDrive } else { Icon::Folder };
+
+        let drive = self.removable_drives.iter()
+            .find(|drive| is_volume && drive.mount_point.as_ref() == Some(&path))
+            .cloned();
+        let can_eject = drive.as_ref().is_some_and(|drive| drive.can_eject);
+        let idle = self.drive_job.is_none();
+
+        let (toggle_button, header, _) = state
+            .show_header(ui, |ui| {
+                let node_button = ui.add(egui::Button::image_and_text(
+                    egui::Image::new(icon).max_size(vec2(14.0, 14.0)),
+                    RichText::new(&label).size(14.0),
+                ).frame(false).selected(current_dir == path));
+
+                if reveal && current_dir == path {
+                    node_button.scroll_to_me(None);
+                }
+
+                let eject_clicked = can_eject && ui.add_enabled(idle, egui::Button::image(
+                    egui::Image::new(Icon::Eject).max_size(vec2(12.0, 12.0)),
+                ).frame(false)).on_hover_text("Безпечно вилучити").on_hover_cursor(PointingHand).clicked();
+
+                (node_button.on_hover_cursor(PointingHand), eject_clicked)
+            })
+            .body(|ui| {
+                for child in self.folder_tree.children(&path) {
+                    let name = child.file_name().unwrap_or_default().to_string_lossy().to_string();
+
+                    self.show_folder_tree_node(ui, child, name, false, reveal);
+                }
+            });
+
+        let (node_button, eject_clicked) = header.inner;
+
+        if toggle_button.clicked() {
+            self.folder_tree.forget(&path);
+        }
+
+        if eject_clicked && let Some(drive) = &drive {
+            self.run_drive_action(&ui.ctx().clone(), DriveAction::Eject, drive);
+        }
+
+        if node_button.clicked() {
+            self.navigate_to(path.clone());
+        }
+
+        if node_button.middle_clicked() {
+            self.open_tab(path.clone(), false);
+        }
+
+        self.handle_folder_drop(ui, &node_button, &path);
+
+        if is_volume {
+            node_button.context_menu(|ui| {
+                if ui.button("Інформація про диск").on_hover_cursor(PointingHand).clicked() {
+                    println!("Інформація про диск");
+
+                    self.popup_type = PopupType::DiskInfo;
+
+                    ui.close_menu();
+                }
+                if ui.button("Аналізувати використання диска").on_hover_cursor(PointingHand).clicked() {
+                    self.open_disk_usage(path.clone(), ui.ctx());
+                    ui.close_menu();
+                }
+
+                if let Some(drive) = &drive {
+                    ui.separator();
+
+                    self.show_drive_actions(ui, drive);
+                }
+
+                self.is_main_context_menu_open = false;
+            });
+        }
+    }
+
+    // папка як ціль перетягування: елементи зі списку переносяться (з Ctrl - копіюються), файли ззовні копіюються
+    fn handle_folder_drop(&mut self, ui: &egui::Ui, response: &egui::Response, target_dir: &Path) {
+        if response.dnd_hover_payload::<PathBuf>().is_some() {
+            ui.painter().rect_stroke(
+                response.rect,
+                2.0,
+                ui.visuals().selection.stroke,
+                egui::StrokeKind::Inside,
+            );
+        }
+
+        let mut transfers: Vec<(PathBuf, bool)> = Vec::new();
+
+        if let Some(source) = response.dnd_release_payload::<PathBuf>() {
+            let is_copy = ui.input(|i| i.modifiers.command);
+
+            transfers.push((source.as_ref().clone(), !is_copy));
+        }
+
+        let hovered = ui.input(|i| i.pointer.hover_pos()).is_some_and(|pos| response.rect.contains(pos));
+
+        if hovered {
+            // забираємо файли, щоб панель під папкою не скопіювала їх вдруге
+            let dropped_files = ui.ctx().input_mut(|i| std::mem::take(&mut i.raw.dropped_files));
+
+            for file in dropped_files {
+                if let Some(source) = file.path {
+                    transfers.push((source, false));
+                }
+            }
+        }
+
+        transfers.retain(|(source, _)| source.parent() != Some(target_dir) && source != target_dir);
+
+        for is_move in [false, true] {
+            let sources: Vec<PathBuf> = transfers.iter().filter(|(_, moved)| *moved == is_move).map(|(source, _)| source.clone()).collect();
+
+            if !sources.is_empty() {
+                self.copy_across(ui.ctx(), sources, target_dir.to_path_buf(), is_move);
+            }
+        }
+    }
+
+    // копіювання і переміщення у фоні з прогресом: з архіву в папку елементи лише витягуються,
+    // між локальними папками працює transfer_local, решта передається через vfs
+    fn copy_across(&mut self, ctx: &egui::Context, sources: Vec<PathBuf>, target_dir: PathBuf, is_move: bool) {
+        let Some(first) = sources.first().cloned() else {
+            return;
+        };
+
+        if self.vfs.split_archive(&first).is_some() && self.vfs.is_local(&target_dir) {
+            self.extract_archive_entries(ctx, sources, target_dir);
+            return;
+        }
+
+        let endpoints = self.vfs.resolve(&first).and_then(|src| Ok((src, self.vfs.resolve(&target_dir)?)));
+
+        let (src, dst) = match endpoints {
+            Ok(endpoints) => endpoints,
+            Err(err) => {
+                self.toasts.push("Не вдалося скопіювати", err.to_string());
+                return;
+            }
+        };
+
+        if dst.is_read_only() {
+            self.toasts.push("Не вдалося скопіювати", format!("{} доступний лише для читання", target_dir.display()));
+            return;
+        }
+
+        // з простору лише для читання елементи копіюються, а не переносяться
+        let is_move = is_move && !src.is_read_only();
+
+        let title = if sources.len() == 1 {
+            format!("{} → {}", first.display(), target_dir.display())
+        } else {
+            format!("{} елементів → {}", sources.len(), target_dir.display())
+        };
+
+        let job_target_dir = target_dir.clone();
+
+        // між локальними папками переміщення в межах диска - це просто перейменування
+        let job = if self.vfs.is_local(&first) && self.vfs.is_local(&target_dir) {
+            Job::spawn(ctx, move |job| transfer_local(&sources, &job_target_dir, is_move, job))
+        } else {
+            Job::spawn(ctx, move |job| transfer(&sources, src.as_ref(), dst.as_ref(), &job_target_dir, is_move, job))
+        };
+
+        self.transfers.push(TransferView { title, target_dir, is_move, job });
+    }
+
+    fn show_transfers(&mut self, ctx: &egui::Context) {
+        for download in &mut self.downloads {
+            download.poll();
+        }
+
+        for download in self.downloads.extract_if(.., |download| download.is_finished()).collect::<Vec<_>>() {
+            match download.result {
+                Some(Ok(local_path)) => self.open_path(&local_path),
+                Some(Err(err)) => self.toasts.push("Не вдалося відкрити файл", err.to_string()),
+                None => {},
+            }
+        }
+
+        for view in &mut self.transfers {
+            view.job.poll();
+        }
+
+        for view in self.transfers.extract_if(.., |view| view.job.is_finished()).collect::<Vec<_>>() {
+            let Some(result) = view.job.result else {
+                continue;
+            };
+
+            self.folder_tree.forget(&view.target_dir);
+            self.reload_listings();
+
+            if let Some((path, err)) = result.errors.first() {
+                self.toasts.push(
+                    format!("Передачу завершено з помилками: {}", result.errors.len()),
+                    format!("{}: {}", path.display(), err),
+                );
+            } else {
+                let done = if view.is_move { format!("Переміщено: {}", result.done) } else { format!("Скопійовано файлів: {}", result.done) };
+
+                if result.cancelled {
+                    self.toasts.push("Передачу скасовано", done);
+                } else {
+                    self.toasts.push("Передачу завершено", done);
+                }
+            }
+        }
+
+        if self.transfers.is_empty() {
+            return;
+        }
+
+        egui::Window::new("Передача файлів")
+            .collapsible(true)
+            .resizable(false)
+            .show(ctx, |ui| {
+                for view in &self.transfers {
+                    ui.label(&view.title);
+
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+
+                        let progress = view.job.progress.unwrap_or_default();
+                        ui.label(format!("Файлів: {}, передано: {}", progress.files, format_size(progress.bytes)));
+
+                        if ui.add_enabled(!view.job.is_cancelled(), egui::Button::new("Скасувати")).on_hover_cursor(PointingHand).clicked() {
+                            view.job.cancel();
+                        }
+                    });
+
+                    ui.add_space(5.0);
+                }
+            });
+    }
+
+    fn show_pane(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, pane: Pane) {
+        // панель малюється так, ніби вона активна, щоб уся навігація працювала з її вкладкою
+        let focused_pane = self.active_pane;
+
+        self.active_pane = pane;
+
+        if self.commander_mode {
+            let path = if self.tab().is_disk_selection {
+                RichText::new("Диски")
+            } else {
+                RichText::new(self.tab().current_dir.to_string_lossy())
+            };
+
+            ui.label(if focused_pane == pane { path.strong() } else { path.weak() });
+            ui.add_space(5.0);
+        }
+
+        let mut scroll_area = egui::ScrollArea::vertical()
+            .id_salt(("main_scroll_area", pane))
+            .auto_shrink(false)
+            .max_height(ui.available_height());
+
+        if self.tab().restore_scroll {
+            scroll_area = scroll_area.vertical_scroll_offset(self.tab().scroll_offset);
+
+            self.tab_mut().restore_scroll = false;
+        }
+
+        let scroll_output = scroll_area.show(ui, |ui| {
+            let response = ui.interact(
+                ui.available_rect_before_wrap(),
+                ui.id().with(("main_scroll_area", pane)),
+                egui::Sense::click(),
+            );
+
+            if response.clicked_by(egui::PointerButton::Secondary) {
+                if let Some(pointer_pos) = ctx.input(|i| i.pointer.interact_pos()) {
+                    self.is_main_context_menu_open = true;
+                    self.interact_pointer_pos = pointer_pos;
+                    self.templates = templates();
+                }
+            }
+
+            if self.tab().is_disk_selection {
+                for disk in self.disk_list.clone() {
+                    let disk_button = ui.add(egui::Button::image_and_text(
+                        egui::Image::new(Icon::HardDrive),
+                        RichText::new(disk.to_string()).size(14.0),
+                    ));
+
+                    disk_button.clone().on_hover_cursor(PointingHand);
+
+                    if disk_button.double_clicked() {
+                        self.navigate_to(PathBuf::from(format!("{}:\\", disk)));
+                    }
+
+                    disk_button.context_menu(|ui| {
+                        if ui.button("Інформація про диск").on_hover_cursor(PointingHand).clicked() {
+                            println!("Інформація про диск");
+
+                            self.popup_type = PopupType::DiskInfo;
+                            
+                            ui.close_menu();
+                        }
+                        if ui.button("Аналізувати використання диска").on_hover_cursor(PointingHand).clicked() {
+                            self.open_disk_usage(PathBuf::from(format!("{}:\\", disk)), ctx);
+                            ui.close_menu();
+                        }
+
+                        self.is_main_context_menu_open = false;
+                    });
+                }
+
+                for drive in self.removable_drives.clone() {
+                    let drive_button = ui.horizontal(|ui| {
+                        let button = ui.add(egui::Button::image_and_text(
+                            egui::Image::new(Icon::HardDrive),
+                            RichText::new(format!("{} ({})", drive.label, format_size(drive.size))).size(14.0),
+                        ));
+
+                        if self.is_drive_busy(&drive) {
+                            ui.spinner();
+                        }
+
+                        button
+                    }).inner;
+
+                    let drive_button = match &drive.mount_point {
+                        Some(mount_point) => drive_button.on_hover_text(mount_point.display().to_string()),
+                        None => drive_button.on_hover_text(format!("{} не змонтовано", drive.device)),
+                    };
+
+                    drive_button.clone().on_hover_cursor(PointingHand);
+
+                    if drive_button.double_clicked() {
+                        self.open_drive(ctx, &drive);
+                    }
+
+                    drive_button.context_menu(|ui| {
+                        if ui.button("Інформація про диск").on_hover_cursor(PointingHand).clicked() {
+                            self.popup_type = PopupType::DiskInfo;
+
+                            ui.close_menu();
+                        }
+                        if let Some(mount_point) = &drive.mount_point
+                            && ui.button("Аналізувати використання диска").on_hover_cursor(PointingHand).clicked()
+                        {
+                            self.open_disk_usage(mount_point.clone(), ctx);
+                            ui.close_menu();
+                        }
+
+                        ui.separator();
+
+                        self.show_drive_actions(ui, &drive);
+
+                        self.is_main_context_menu_open = false;
+                    });
+                }
+            } else {
+                match self.tab().view_mode {
+                    ViewMode::Grid => {
+                        ui.horizontal_wrapped(|ui| {
+                            self.show_dir_entries(ui, ctx);
+                        });
+                    },
+                    ViewMode::Details => {
+                        egui::Grid::new(("details_view", pane))
+                            .striped(true)
+                            .spacing(vec2(16.0, 4.0))
+                            .show(ui, |ui| {
+                                Self::show_details_header(ui);
+                                self.show_dir_entries(ui, ctx);
+                            });
+                    },
+                    ViewMode::List => {
+                        self.show_dir_entries(ui, ctx);
+                    }
+                }
+
+                // кинуте на вільне місце панелі потрапляє в її поточну папку
+                let current_dir = self.tab().current_dir.clone();
+
+                if self.vfs.cached(&current_dir).is_some_and(|vfs| !vfs.is_read_only()) {
+                    self.handle_folder_drop(ui, &response, &current_dir);
+                }
+            }
+        });
+
+        self.tab_mut().scroll_offset = scroll_output.state.offset.y;
+
+        let pane_rect = ui.min_rect();
+        let pane_pressed = ui.input(|i| {
+            i.pointer.any_pressed() && i.pointer.interact_pos().is_some_and(|pos| pane_rect.contains(pos))
+        });
+
+        self.active_pane = if pane_pressed { pane } else { focused_pane };
+    }
+
+    fn show_popups(&mut self, ctx: &egui::Context, window_size: egui::Vec2) {
+        // закрите вікно властивостей не повинно далі рахувати розмір папки
+        if self.popup_type != PopupType::Properties
+            && let Some(dialog) = self.properties.take()
+        {
+            dialog.cancel_jobs();
+        }
+
+        if self.is_main_context_menu_open {
+            egui::Window::new("")
+                .current_pos(self.interact_pointer_pos)
+                .movable(false)
+                .resizable(false)
+                .collapsible(false)
+                .title_bar(false)
+                .show(ctx, |ui| {
+                    let is_virtual = !self.vfs.is_local(&self.tab().current_dir);
+
+                    if ui.button("Оновити").on_hover_cursor(PointingHand).clicked() {
+                        let current_dir = self.tab().current_dir.clone();
+
+                        if let Some(vfs) = self.vfs.cached(&current_dir) {
+                            vfs.invalidate(&current_dir);
+                        }
+
+                        self.reload_listings();
+                    }
+
+                    if let Some(source) = self.archive_clipboard.clone()
+                        && !is_virtual
+                        && ui.button("Вставити").on_hover_cursor(PointingHand).clicked()
+                    {
+                        let target_dir = self.tab().current_dir.clone();
+
+                        self.extract_archive_entries(ctx, vec![source], target_dir);
+                        self.is_main_context_menu_open = false;
+                    }
+
+                    if is_virtual {
+                        let current_dir = self.tab().current_dir.clone();
+
+                        if let Some(vfs) = self.vfs.cached(&current_dir)
+                            && !vfs.is_read_only()
+                            && ui.button("Створити нову папку").on_hover_cursor(PointingHand).clicked()
+                        {
+                            self.on_item_created(create_folder_in(vfs.as_ref(), &current_dir));
+                        }
+
+                        return;
+                    }
+
+                    if ui.button("Створити нову папку").on_hover_cursor(PointingHand).clicked() {
+                        self.on_item_created(create_folder(&self.tab().current_dir));
+                    } else if ui.button("Створити новий файл").on_hover_cursor(PointingHand).clicked() {
+                        self.on_item_created(create_file(&self.tab().current_dir));
+                    }
+
+                    if !self.templates.is_empty() {
+                        ui.menu_button("Створити з шаблону", |ui| {
+                            for template in self.templates.clone() {
+                                let name = template.file_stem().unwrap_or_default().to_string_lossy();
+
+                                if ui.button(name).on_hover_cursor(PointingHand).clicked() {
+                                    self.on_item_created(create_from_template(&self.tab().current_dir, &template));
+                                    self.is_main_context_menu_open = false;
+
+                                    ui.close_menu();
+                                }
+                            }
+                        });
+                    }
+
+                    //ui.close_menu();
+                });
+        }
+
+        if self.popup_type != PopupType::None {
+            egui::Window::new(
+                if self.popup_type == PopupType::DiskInfo {
+                    "Інформація про диск"
+                } else if self.popup_type == PopupType::Rename {
+                    "Перейменувати"
+                } else if self.popup_type == PopupType::RenameBookmark {
+                    "Перейменувати закладку"
+                } else if self.popup_type == PopupType::Settings {
+                    "Налаштування"
+                } else {
+                    "empty popup"
+                }
+            )
+                .default_size(vec2(
+                    window_size.x / 2.0,
+                    window_size.y / 2.0,
+                ))
+                .max_size(vec2(
+                    window_size.x - 200.0,
+                    window_size.y - 200.0,
+                ))
+                .default_pos(pos2(
+                    window_size.x / 4.0,
+                    window_size.y / 4.0,
+                ))
+                //.movable(false)
+                .resizable(false)
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    if self.popup_type == PopupType::DiskInfo {
+                        ui.label("Інформація");
+                    } else if self.popup_type == PopupType::Rename {
+                        ui.horizontal(|ui| {
+                            let new_name_label = ui.label("Перейменувати: ");
+
+                            ui.text_edit_singleline(&mut self.rename).labelled_by(new_name_label.id);
+
+                            if ui.button("Перейменувати").on_hover_cursor(PointingHand).clicked() {
+                                let mut new_name = self.tab().selected_item.clone();
+
+                                new_name.pop();
+                                new_name.push(&self.rename);
+
+                                fs::rename(&self.tab().selected_item, new_name).expect("Виникла помилка, під час спроби перейменувати файл");
+                            
+                                self.popup_type = PopupType::None;
+                            }
+                        });
+                    } else if self.popup_type == PopupType::RenameBookmark {
+                        ui.horizontal(|ui| {
+                            let new_name_label = ui.label("Назва: ");
+
+                            ui.text_edit_singleline(&mut self.rename).labelled_by(new_name_label.id);
+
+                            if ui.button("Перейменувати").on_hover_cursor(PointingHand).clicked() {
+                                let label = self.rename.trim().to_string();
+
+                                if let Some(bookmark) = self.bookmarks.get_mut(self.renamed_bookmark) {
+                                    bookmark.label = if label.is_empty() { None } else { Some(label) };
+                                }
+
+                                self.save_bookmarks();
+
+                                self.popup_type = PopupType::None;
+                            }
+                        });
+                    } else if self.popup_type == PopupType::Settings {
+                        self.show_settings(ui, ctx);
+                    } else if self.popup_type == PopupType::Properties {
+                        self.show_properties(ui, ctx);
+                    } else {}
+
+                    if ui.button("Закрити").on_hover_cursor(PointingHand).clicked() {
+                        self.popup_type = PopupType::None;
+                    }
+                });
+        }
+
+        if ctx.input(|i| i.pointer.primary_clicked()) {
+            if self.is_main_context_menu_open {
+                self.is_main_context_menu_open = false;
+            }
+        }
+    }
+
+    fn open_properties(&mut self, path: &Path, ctx: &egui::Context) {
+        let properties = match read_properties(path) {
+            Ok(properties) => properties,
+            Err(err) => {
+                self.toasts.push("Не вдалося прочитати властивості", err.to_string());
+                return;
+            }
+        };
+
+        if let Some(dialog) = self.properties.take() {
+            dialog.cancel_jobs();
+        }
+
+        let folder_size = properties.is_dir.then(|| {
+            let path = path.to_path_buf();
+            Job::spawn(ctx, move |job| folder_size(&path, job))
+        });
+
+        self.properties = Some(PropertiesDialog {
+            tab: PropertiesTab::General,
+            mode: properties.mode.unwrap_or(0),
+            uid: properties.uid.unwrap_or(0),
+            gid: properties.gid.unwrap_or(0),
+            users: users(),
+            groups: groups(),
+            recursive: false,
+            recursive_target: RecursiveTarget::All,
+            apply_job: None,
+            properties,
+            folder_size,
+            error: None,
+        });
+
+        self.popup_type = PopupType::Properties;
+    }
+
+    fn show_properties(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let Some(dialog) = self.properties.as_mut() else {
+            return;
+        };
+
+        if let Some(job) = &mut dialog.folder_size {
+            job.poll();
+        }
+
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut dialog.tab, PropertiesTab::General, "Загальне");
+
+            if dialog.properties.mode.is_some() {
+                ui.selectable_value(&mut dialog.tab, PropertiesTab::Permissions, "Права доступу");
+            }
+        });
+
+        ui.separator();
+
+        match dialog.tab {
+            PropertiesTab::General => Self::show_general_properties(ui, dialog),
+            PropertiesTab::Permissions => Self::show_permissions(ui, ctx, dialog),
+        }
+    }
+
+    fn show_general_properties(ui: &mut egui::Ui, dialog: &PropertiesDialog) {
+        let properties = &dialog.properties;
+
+        egui::Grid::new("properties_grid").num_columns(2).spacing(vec2(20.0, 6.0)).show(ui, |ui| {
+            ui.label("Назва");
+            ui.label(properties.path.file_name().unwrap_or_default().to_string_lossy());
+            ui.end_row();
+
+            ui.label("Розташування");
+            ui.label(properties.path.to_string_lossy());
+            ui.end_row();
+
+            if let Some(target) = &properties.symlink_target {
+                ui.label("Посилання на");
+                ui.label(target.to_string_lossy());
+                ui.end_row();
+
+                ui.label("");
+                ui.weak("Права і власник стосуються цілі посилання");
+                ui.end_row();
+            }
+
+            ui.label("Розмір");
+            match &dialog.folder_size {
+                Some(job) => {
+                    let size = job.result.or(job.progress).unwrap_or_default();
+
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{} ({} байт)", format_size(size.bytes), size.bytes));
+
+                        if !job.is_finished() {
+                            ui.spinner();
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Вміст");
+                    ui.label(format!("файлів: {}, папок: {}", size.files, size.folders));
+                },
+                None => {
+                    ui.label(format!("{} ({} байт)", format_size(properties.size), properties.size));
+                }
+            }
+            ui.end_row();
+
+            for (label, time) in [("Створено", properties.created), ("Змінено", properties.modified), ("Відкрито", properties.accessed)] {
+                if let Some(time) = time {
+                    ui.label(label);
+                    ui.label(format_time(time));
+                    ui.end_row();
+                }
+            }
+
+            if let Some(mode) = properties.mode {
+                ui.label("Права");
+                ui.monospace(format!("{:04o}  {}", mode, mode_string(mode)));
+                ui.end_row();
+            }
+
+            if let (Some(owner), Some(group)) = (&properties.owner, &properties.group) {
+                ui.label("Власник");
+                ui.label(format!("{}:{}", owner, group));
+                ui.end_row();
+            }
+
+            if let Some(inode) = properties.inode {
+                ui.label("Inode");
+                ui.label(inode.to_string());
+                ui.end_row();
+            }
+
+            if let Some(links) = properties.links {
+                ui.label("Жорстких посилань");
+                ui.label(links.to_string());
+                ui.end_row();
+            }
+        });
+    }
+
+    fn show_permissions(ui: &mut egui::Ui, ctx: &egui::Context, dialog: &mut PropertiesDialog) {
+        // змінювати права може власник або root, а власника — лише root
+        let is_root = current_uid() == 0;
+        let is_owner = is_root || dialog.properties.uid == Some(current_uid());
+
+        if let Some(job) = &mut dialog.apply_job {
+            let was_running = !job.is_finished();
+
+            job.poll();
+
+            // після завершення показуємо актуальні значення з диска
+            if was_running
+                && job.is_finished()
+                && let Ok(properties) = read_properties(&dialog.properties.path)
+            {
+                dialog.properties = properties;
+            }
+        }
+
+        let is_running = dialog.apply_job.as_ref().is_some_and(|job| !job.is_finished());
+
+        if let Some(target) = &dialog.properties.symlink_target {
+            ui.weak(format!("Зміни застосовуються до цілі посилання: {}", target.to_string_lossy()));
+            ui.add_space(6.0);
+        }
+
+        ui.add_enabled_ui(is_owner && !is_running, |ui| {
+            egui::Grid::new("permissions_grid").num_columns(4).spacing(vec2(16.0, 6.0)).show(ui, |ui| {
+                ui.label("");
+                ui.label("Читання");
+                ui.label("Запис");
+                ui.label("Виконання");
+                ui.end_row();
+
+                for (label, shift) in [("Власник", 6), ("Група", 3), ("Інші", 0)] {
+                    ui.label(label);
+
+                    for bit in [0o4, 0o2, 0o1] {
+                        mode_checkbox(ui, &mut dialog.mode, bit << shift, "");
+                    }
+
+                    ui.end_row();
+                }
+
+                ui.label("Особливі");
+                mode_checkbox(ui, &mut dialog.mode, 0o4000, "setuid");
+                mode_checkbox(ui, &mut dialog.mode, 0o2000, "setgid");
+                mode_checkbox(ui, &mut dialog.mode, 0o1000, "sticky");
+                ui.end_row();
+            });
+
+            ui.monospace(format!("{:04o}  {}", dialog.mode, mode_string(dialog.mode)));
+
+            ui.add_space(6.0);
+
+            egui::Grid::new("ownership_grid").num_columns(2).spacing(vec2(16.0, 6.0)).show(ui, |ui| {
+                ui.label("Власник");
+                ui.add_enabled_ui(is_root, |ui| {
+                    account_combo_box(ui, "owner_combo", &dialog.users, &mut dialog.uid);
+                });
+                ui.end_row();
+
+                ui.label("Група");
+                account_combo_box(ui, "group_combo", &dialog.groups, &mut dialog.gid);
+                ui.end_row();
+            });
+
+            if dialog.properties.is_dir {
+                ui.add_space(6.0);
+                ui.checkbox(&mut dialog.recursive, "Застосувати до вкладених елементів");
+
+                ui.add_enabled_ui(dialog.recursive, |ui| {
+                    ui.horizontal(|ui| {
+                        for target in RecursiveTarget::ALL {
+                            ui.radio_value(&mut dialog.recursive_target, target, target.label());
+                        }
+                    });
+                });
+            }
+
+            if ui.button("Застосувати").on_hover_cursor(PointingHand).clicked() {
+                let properties = &dialog.properties;
+
+                let change = PermissionChange {
+                    mode: Some(dialog.mode).filter(|mode| Some(*mode) != properties.mode),
+                    uid: Some(dialog.uid).filter(|uid| Some(*uid) != properties.uid),
+                    gid: Some(dialog.gid).filter(|gid| Some(*gid) != properties.gid),
+                };
+
+                let path = properties.path.clone();
+
+                if dialog.recursive && properties.is_dir {
+                    // вкладеним елементам передаються лише змінені поля, щоб chown не переписував їм права
+                    let target = dialog.recursive_target;
+
+                    dialog.error = None;
+                    dialog.apply_job = Some(Job::spawn(ctx, move |job| apply_recursive(&path, change, target, job)));
+                } else {
+                    dialog.error = change.apply(&path).err().map(|err| err.to_string());
+
+                    match read_properties(&path) {
+                        Ok(properties) => dialog.properties = properties,
+                        Err(err) => dialog.error = Some(err.to_string()),
+                    }
+                }
+            }
+        });
+
+        if let Some(job) = &dialog.apply_job {
+            match &job.result {
+                None => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(format!("Змінено елементів: {}", job.progress.unwrap_or(0)));
+
+                        if ui.button("Скасувати").on_hover_cursor(PointingHand).clicked() {
+                            job.cancel();
+                        }
+                    });
+                },
+                Some(result) => {
+                    let status = if result.cancelled { "Скасовано" } else { "Готово" };
+                    ui.label(format!("{}. Змінено елементів: {}", status, result.done));
+
+                    for (path, err) in result.errors.iter().take(5) {
+                        ui.colored_label(ui.visuals().error_fg_color, format!("{}: {}", path.display(), err));
+                    }
+
+                    if result.errors.len() > 5 {
+                        ui.colored_label(ui.visuals().error_fg_color, format!("і ще помилок: {}", result.errors.len() - 5));
+                    }
+                }
+            }
+        }
+
+        if !is_owner {
+            ui.label("Змінювати права може лише власник або root");
+        }
+
+        if let Some(error) = &dialog.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+    }
+
+    fn open_disk_usage(&mut self, path: PathBuf, ctx: &egui::Context) {
+        if let Some(view) = self.disk_usage.take() {
+            view.job.cancel();
+        }
+
+        let scan_path = path.clone();
+
+        self.disk_usage = Some(DiskUsageView {
+            root: path,
+            job: Job::spawn(ctx, move |job| scan_usage(&scan_path, job)),
+            tree: None,
+            focus: Vec::new(),
+            selected: None,
+            sort: UsageSort::Size,
+            error: None,
+        });
+    }
+
+    fn show_disk_usage(&mut self, ctx: &egui::Context, window_size: egui::Vec2) {
+        let Some(view) = self.disk_usage.as_mut() else {
+            return;
+        };
+
+        view.job.poll();
+
+        if view.tree.is_none() {
+            view.tree = view.job.result.take();
+        }
+
+        let mut open = true;
+        let mut action: Option<UsageAction> = None;
+
+        egui::Window::new("Аналіз використання диска")
+            .open(&mut open)
+            .collapsible(false)
+            .default_size(window_size * 0.7)
+            .show(ctx, |ui| {
+                let Some(tree) = &view.tree else {
+                    let progress = view.job.progress.unwrap_or_default();
+
+                    ui.label(view.root.to_string_lossy());
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(format!("Проскановано: {}, файлів: {}", format_size(progress.bytes), progress.files));
+
+                        if ui.button("Скасувати").on_hover_cursor(PointingHand).clicked() {
+                            view.job.cancel();
+                        }
+                    });
+                    return;
+                };
+
+                let Some(focused) = tree.node(&view.focus) else {
+                    view.focus.clear();
+                    return;
+                };
+
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(!view.focus.is_empty(), egui::Button::image(
+                        egui::Image::new(Icon::ArrowUp).max_size(vec2(16.0, 16.0)),
+                    )).on_hover_text("Вгору").clicked() {
+                        let mut focus = view.focus.clone();
+                        focus.pop();
+                        action = Some(UsageAction::Focus(focus));
+                    }
+
+                    // шлях від кореня сканування до поточного вузла
+                    let mut node = tree;
+
+                    if ui.link(&tree.name).clicked() {
+                        action = Some(UsageAction::Focus(Vec::new()));
+                    }
+
+                    for (depth, &index) in view.focus.iter().enumerate() {
+                        node = &node.children[index];
+
+                        ui.add(egui::Image::new(Icon::AngleRight).max_size(vec2(10.0, 10.0)));
+
+                        if ui.link(&node.name).clicked() {
+                            action = Some(UsageAction::Focus(view.focus[..=depth].to_vec()));
+                        }
+                    }
+
+                    ui.separator();
+                    ui.label(format!("{}, файлів: {}", format_size(focused.size), focused.files));
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Сортування:");
+
+                    for (sort, label) in [(UsageSort::Size, "Розмір"), (UsageSort::Name, "Назва"), (UsageSort::Files, "Кількість файлів")] {
+                        ui.selectable_value(&mut view.sort, sort, label);
+                    }
+
+                    if let Some(selected) = &view.selected
+                        && let Some(node) = tree.node(selected)
+                    {
+                        ui.separator();
+
+                        if ui.button(format!("Перемістити «{}» в кошик", node.name)).on_hover_cursor(PointingHand).clicked() {
+                            action = Some(UsageAction::Trash(selected.clone()));
+                        }
+                    }
+                });
+
+                if let Some(error) = &view.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                ui.separator();
+
+                ui.columns(2, |columns| {
+                    egui::ScrollArea::vertical().id_salt("usage_tree").auto_shrink(false).show(&mut columns[0], |ui| {
+                        Self::show_usage_rows(ui, focused, &view.focus, view.sort, &view.selected, &mut action);
+                    });
+
+                    Self::show_treemap(&mut columns[1], focused, &view.focus, &view.selected, &mut action);
+                });
+            });
+
+        match action {
+            Some(UsageAction::Focus(focus)) => {
+                view.focus = focus;
+                view.selected = None;
+            },
+            Some(UsageAction::Select(selected)) => view.selected = Some(selected),
+            Some(UsageAction::Open(path)) => self.navigate_to(path),
+            Some(UsageAction::Trash(index_path)) => {
+                if let Some(tree) = &mut view.tree
+                    && let Some(node) = tree.node(&index_path)
+                {
+                    match trash::delete(&node.path) {
+                        Ok(()) => {
+                            tree.remove(&index_path);
+
+                            // якщо видалено папку, в якій ми знаходились, повертаємось до її батька
+                            if view.focus.starts_with(&index_path) {
+                                view.focus.truncate(index_path.len() - 1);
+                            }
+
+                            view.selected = None;
+                            view.error = None;
+                        },
+                        Err(err) => view.error = Some(err.to_string()),
+                    }
+                }
+            },
+            None => {}
+        }
+
+        if !open && let Some(view) = self.disk_usage.take() {
+            view.job.cancel();
+        }
+    }
+
+    fn show_usage_rows(ui: &mut egui::Ui, node: &UsageNode, index_path: &[usize], sort: UsageSort, selected: &Option<Vec<usize>>, action: &mut Option<UsageAction>) {
+        let mut indices: Vec<usize> = (0..node.children.len()).collect();
+
+        match sort {
+            UsageSort::Size => indices.sort_by(|&a, &b| node.children[b].size.cmp(&node.children[a].size)),
+            UsageSort::Name => indices.sort_by_key(|&index| node.children[index].name.to_lowercase()),
+            UsageSort::Files => indices.sort_by(|&a, &b| node.children[b].files.cmp(&node.children[a].files)),
+        }
+
+        for index in indices {
+            let child = &node.children[index];
+
+            let mut child_path = index_path.to_vec();
+            child_path.push(index);
+
+            let show_row = |ui: &mut egui::Ui, action: &mut Option<UsageAction>| {
+                let percent = if node.size > 0 { child.size as f64 * 100.0 / node.size as f64 } else { 0.0 };
+                let icon = if child.is_dir { Icon::Folder } else { Icon::File };
+
+                let row = ui.add(egui::Button::image_and_text(
+                    egui::Image::new(icon).max_size(vec2(14.0, 14.0)),
+                    format!("{}   {}   {:.1}%", child.name, format_size(child.size), percent),
+                ).frame(false).selected(selected.as_ref() == Some(&child_path)));
+
+                if row.clicked() {
+                    *action = Some(UsageAction::Select(child_path.clone()));
+                }
+
+                if row.double_clicked() && child.is_dir {
+                    *action = Some(UsageAction::Focus(child_path.clone()));
+                }
+
+                row.context_menu(|ui| {
+                    if child.is_dir && ui.button("Відкрити тут").on_hover_cursor(PointingHand).clicked() {
+                        *action = Some(UsageAction::Focus(child_path.clone()));
+                        ui.close_menu();
+                    }
+                    if ui.button("Показати в провіднику").on_hover_cursor(PointingHand).clicked() {
+                        let folder = if child.is_dir { child.path.clone() } else { child.path.parent().unwrap_or(&child.path).to_path_buf() };
+                        *action = Some(UsageAction::Open(folder));
+                        ui.close_menu();
+                    }
+                    if ui.button("Перемістити в кошик").on_hover_cursor(PointingHand).clicked() {
+                        *action = Some(UsageAction::Trash(child_path.clone()));
+                        ui.close_menu();
+                    }
+                });
+            };
+
+            if child.is_dir && !child.children.is_empty() {
+                let id = ui.make_persistent_id(("usage_row", &child.path));
+
+                egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, false)
+                    .show_header(ui, |ui| show_row(ui, action))
+                    .body(|ui| Self::show_usage_rows(ui, child, &child_path, sort, selected, action));
+            } else {
+                ui.horizontal(|ui| {
+                    ui.add_space(ui.spacing().indent);
+                    show_row(ui, action);
+                });
+            }
+        }
+    }
+
+    fn show_treemap(ui: &mut egui::Ui, node: &UsageNode, index_path: &[usize], selected: &Option<Vec<usize>>, action: &mut Option<UsageAction>) {
+        let (rect, response) = ui.allocate_exact_size(ui.available_size(), egui::Sense::click());
+        let painter = ui.painter_at(rect);
+
+        let sizes: Vec<u64> = node.children.iter().map(|child| child.size).collect();
+        let rects = squarify(&sizes, rect.shrink(2.0));
+
+        let hover_pos = response.hover_pos();
+
+        for (index, (child, child_rect)) in node.children.iter().zip(rects).enumerate() {
+            if child_rect.width() < 1.0 || child_rect.height() < 1.0 {
+                continue;
+            }
+
+            // папки — синюваті, файли — зеленуваті, відтінок змінюється від елемента до елемента
+            let shade = 40 + (index * 37 % 80) as u8;
+            let mut fill = if child.is_dir {
+                egui::Color32::from_rgb(shade, shade + 60, 170)
+            } else {
+                egui::Color32::from_rgb(shade, 150, shade + 40)
+            };
+
+            let mut child_path = index_path.to_vec();
+            child_path.push(index);
+
+            let is_hovered = hover_pos.is_some_and(|pos| child_rect.contains(pos));
+
+            if is_hovered || selected.as_ref() == Some(&child_path) {
+                fill = fill.gamma_multiply(1.4);
+            }
+
+            painter.rect_filled(child_rect, 2.0, fill);
+            painter.rect_stroke(child_rect, 2.0, egui::Stroke::new(1.0, ui.visuals().extreme_bg_color), egui::StrokeKind::Inside);
+
+            if child_rect.width() > 60.0 && child_rect.height() > 18.0 {
+                painter.text(
+                    child_rect.left_top() + vec2(4.0, 2.0),
+                    egui::Align2::LEFT_TOP,
+                    format!("{}\n{}", child.name, format_size(child.size)),
+                    egui::FontId::proportional(11.0),
+                    egui::Color32::WHITE,
+                );
+            }
+
+            if is_hovered {
+                response.clone().on_hover_text(format!("{}\n{}", child.path.display(), format_size(child.size)));
+
+                if response.clicked() {
+                    *action = Some(UsageAction::Select(child_path.clone()));
+                }
+
+                if response.double_clicked() && child.is_dir {
+                    *action = Some(UsageAction::Focus(child_path));
+                }
+            }
+        }
+    }
+
+    fn open_duplicates(&mut self, path: PathBuf, ctx: &egui::Context) {
+        if let Some(view) = self.duplicates.take() {
+            view.job.cancel();
+        }
+
+        let scan_path = path.clone();
+
+        self.duplicates = Some(DuplicatesView {
+            root: path,
+            job: Job::spawn(ctx, move |job| find_duplicates(&scan_path, job)),
+            choices: None,
+            action: DuplicateAction::Trash,
+            resolve_job: None,
+        });
+    }
+
+    fn show_duplicates(&mut self, ctx: &egui::Context, window_size: egui::Vec2) {
+        let Some(view) = self.duplicates.as_mut() else {
+            return;
+        };
+
+        view.job.poll();
+
+        if view.choices.is_none()
+            && let Some(groups) = view.job.result.take()
+        {
+            view.choices = Some(groups.into_iter().map(|group| DuplicateChoice { group, keep: 0, selected: true }).collect());
+        }
+
+        if let Some(job) = &mut view.resolve_job {
+            let was_running = !job.is_finished();
+
+            job.poll();
+
+            // прибираємо лише повністю оброблені групи, решта лишається зі звітом про помилки
+            if was_running
+                && let Some(result) = &job.result
+                && let Some(choices) = &mut view.choices
+            {
+                choices.retain(|choice| !result.resolved.contains(&choice.group.files[choice.keep].path));
+            }
+        }
+
+        let mut open = true;
+
+        egui::Window::new("Пошук дублікатів")
+            .open(&mut open)
+            .collapsible(false)
+            .default_size(window_size * 0.6)
+            .show(ctx, |ui| {
+                ui.label(view.root.to_string_lossy());
+
+                let Some(choices) = &mut view.choices else {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+
+                        if let Some(progress) = view.job.progress {
+                            if progress.total > 0 {
+                                ui.label(format!("{}: {} з {}", progress.stage.label(), progress.done, progress.total));
+                            } else {
+                                ui.label(format!("{}: {}", progress.stage.label(), progress.done));
+                            }
+                        }
+
+                        if ui.button("Скасувати").on_hover_cursor(PointingHand).clicked() {
+                            view.job.cancel();
+                        }
+                    });
+                    return;
+                };
+
+                let reclaimable: u64 = choices.iter().map(|choice| choice.group.reclaimable()).sum();
+                let selected_reclaimable: u64 = choices.iter().filter(|choice| choice.selected).map(|choice| choice.group.reclaimable()).sum();
+
+                ui.label(format!(
+                    "Груп: {}, можна звільнити: {} (вибрано: {})",
+                    choices.len(),
+                    format_size(reclaimable),
+                    format_size(selected_reclaimable),
+                ));
+
+                let is_resolving = view.resolve_job.as_ref().is_some_and(|job| !job.is_finished());
+
+                ui.add_enabled_ui(!is_resolving, |ui| {
+                    ui.horizontal(|ui| {
+                        for action in DuplicateAction::ALL {
+                            ui.radio_value(&mut view.action, action, action.label());
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        if ui.button("Вибрати всі").on_hover_cursor(PointingHand).clicked() {
+                            choices.iter_mut().for_each(|choice| choice.selected = true);
+                        }
+
+                        if ui.button("Зняти вибір").on_hover_cursor(PointingHand).clicked() {
+                            choices.iter_mut().for_each(|choice| choice.selected = false);
+                        }
+
+                        if ui.button("Застосувати до вибраних").on_hover_cursor(PointingHand).clicked() {
+                            // у кожній групі лишається файл, позначений як "залишити"
+                            let groups: Vec<_> = choices
+                                .iter()
+                                .filter(|choice| choice.selected)
+                                .map(|choice| {
+                                    let keep = choice.group.files[choice.keep].clone();
+                                    let duplicates = choice.group.files.iter().filter(|file| **file != keep).cloned().collect();
+
+                                    (choice.group.size, keep, duplicates)
+                                })
+                                .collect();
+
+                            let action = view.action;
+                            view.resolve_job = Some(Job::spawn(ctx, move |job| resolve_duplicates(groups, action, job)));
+                        }
+                    });
+                });
+
+                if let Some(job) = &view.resolve_job {
+                    match &job.result {
+                        None => {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label(format!("Оброблено файлів: {}", job.progress.unwrap_or(0)));
+
+                                if ui.button("Скасувати").on_hover_cursor(PointingHand).clicked() {
+                                    job.cancel();
+                                }
+                            });
+                        },
+                        Some(result) => {
+                            ui.label(format!("Оброблено файлів: {}", result.batch.done));
+
+                            for (path, err) in result.batch.errors.iter().take(5) {
+                                ui.colored_label(ui.visuals().error_fg_color, format!("{}: {}", path.display(), err));
+                            }
+                        }
+                    }
+                }
+
+                ui.separator();
+
+                // поки задача працює, вибір не змінюється, інакше звіт не відповідав би списку
+                egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| ui.add_enabled_ui(!is_resolving, |ui| {
+                    for (index, choice) in choices.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut choice.selected, "");
+
+                            egui::CollapsingHeader::new(format!(
+                                "{} × {} — можна звільнити {}",
+                                format_size(choice.group.size),
+                                choice.group.files.len(),
+                                format_size(choice.group.reclaimable()),
+                            ))
+                            .id_salt(("duplicate_group", index))
+                            .show(ui, |ui| {
+                                for (file_index, file) in choice.group.files.iter().enumerate() {
+                                    ui.radio_value(&mut choice.keep, file_index, file.path.to_string_lossy())
+                                        .on_hover_text("Залишити цей файл");
+                                }
+                            });
+                        });
+                    }
+
+                    if choices.is_empty() {
+                        ui.label("Дублікатів не знайдено");
+                    }
+                }));
+            });
+
+        if !open && let Some(view) = self.duplicates.take() {
+            view.job.cancel();
+
+            if let Some(job) = view.resolve_job {
+                job.cancel();
+            }
+        }
+    }
+
+    fn open_compare(&mut self, left: PathBuf, right: Option<PathBuf>, ctx: &egui::Context) {
+        let mut view = CompareView {
+            left_input: left.to_string_lossy().to_string(),
+            right_input: right.map(|right| right.to_string_lossy().to_string()).unwrap_or_default(),
+            left,
+            right: PathBuf::new(),
+            compare_content: false,
+            job: None,
+            result: None,
+            error: None,
+            selected: Vec::new(),
+            sync_mode: SyncMode::MirrorToRight,
+            pending_sync: None,
+            sync_job: None,
+        };
+
+        if !view.right_input.is_empty() {
+            Self::start_compare(&mut view, ctx);
+        }
+
+        if let Some(old_view) = self.compare.replace(view) {
+            old_view.cancel_jobs();
+        }
+    }
+
+    fn start_compare(view: &mut CompareView, ctx: &egui::Context) {
+        view.left = expand_path(&view.left_input);
+        view.right = expand_path(&view.right_input);
+        view.result = None;
+        view.error = None;
+        view.pending_sync = None;
+        view.selected.clear();
+
+        let (left, right, compare_content) = (view.left.clone(), view.right.clone(), view.compare_content);
+
+        view.job = Some(Job::spawn(ctx, move |job| compare_folders(&left, &right, compare_content, job)));
+    }
+
+    fn show_compare(&mut self, ctx: &egui::Context, window_size: egui::Vec2) {
+        let Some(view) = self.compare.as_mut() else {
+            return;
+        };
+
+        if let Some(job) = &mut view.job {
+            job.poll();
+
+            match job.result.take() {
+                Some(Ok(result)) => {
+                    view.selected = vec![true; result.entries.len()];
+                    view.result = Some(result);
+                    view.job = None;
+                },
+                Some(Err(err)) => {
+                    view.error = Some(err.to_string());
+                    view.job = None;
+                },
+                None => {},
+            }
+        }
+
+        if let Some(job) = &mut view.sync_job {
+            let was_running = !job.is_finished();
+
+            job.poll();
+
+            // після синхронізації порівнюємо заново, щоб побачити, що лишилось
+            if was_running && job.is_finished() {
+                Self::start_compare(view, ctx);
+            }
+        }
+
+        let mut open = true;
+        let mut diff_request: Option<(PathBuf, PathBuf)> = None;
+
+        egui::Window::new("Порівняння папок")
+            .open(&mut open)
+            .collapsible(false)
+            .default_size(window_size * 0.7)
+            .show(ctx, |ui| {
+                let is_busy = view.job.is_some() || view.sync_job.as_ref().is_some_and(|job| !job.is_finished());
+
+                egui::Grid::new("compare_inputs").num_columns(2).show(ui, |ui| {
+                    ui.label("Ліва папка");
+                    ui.add(egui::TextEdit::singleline(&mut view.left_input).desired_width(f32::INFINITY));
+                    ui.end_row();
+
+                    ui.label("Права папка");
+                    ui.add(egui::TextEdit::singleline(&mut view.right_input).desired_width(f32::INFINITY));
+                    ui.end_row();
+                });
+
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut view.compare_content, "Порівнювати вміст (хеш)");
+
+                    if ui.add_enabled(!is_busy, egui::Button::new("Порівняти")).on_hover_cursor(PointingHand).clicked() {
+                        Self::start_compare(view, ctx);
+                    }
+                });
+
+                if let Some(job) = &view.job {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(format!("Порівняно елементів: {}", job.progress.unwrap_or(0)));
+
+                        if ui.button("Скасувати").on_hover_cursor(PointingHand).clicked() {
+                            job.cancel();
+                        }
+                    });
+                }
+
+                if let Some(err) = &view.error {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+
+                let Some(result) = &view.result else {
+                    return;
+                };
+
+                ui.separator();
+
+                let count = |kind: DiffKind| result.entries.iter().filter(|entry| entry.kind == kind).count();
+
+                ui.label(format!(
+                    "Лише ліворуч: {}, лише праворуч: {}, відрізняються: {}, однакових файлів: {}{}",
+                    count(DiffKind::LeftOnly),
+                    count(DiffKind::RightOnly),
+                    count(DiffKind::Different),
+                    result.identical,
+                    if result.cancelled { " (порівняння скасовано)" } else { "" },
+                ));
+
+                for (path, err) in result.errors.iter().take(5) {
+                    ui.colored_label(ui.visuals().error_fg_color, format!("{}: {}", path.display(), err));
+                }
+
+                ui.add_enabled_ui(!is_busy && view.pending_sync.is_none(), |ui| {
+                    ui.horizontal(|ui| {
+                        for mode in SyncMode::ALL {
+                            ui.radio_value(&mut view.sync_mode, mode, mode.label());
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        if ui.button("Вибрати всі").on_hover_cursor(PointingHand).clicked() {
+                            view.selected.iter_mut().for_each(|selected| *selected = true);
+                        }
+
+                        if ui.button("Зняти вибір").on_hover_cursor(PointingHand).clicked() {
+                            view.selected.iter_mut().for_each(|selected| *selected = false);
+                        }
+
+                        if ui.button("Синхронізувати вибране").on_hover_cursor(PointingHand).clicked() {
+                            let entries: Vec<CompareEntry> = result.entries
+                                .iter()
+                                .zip(&view.selected)
+                                .filter(|(_, selected)| **selected)
+                                .map(|(entry, _)| entry.clone())
+                                .collect();
+
+                            view.pending_sync = Some(entries);
+                        }
+                    });
+                });
+
+                let mut start_sync = false;
+
+                if let Some(entries) = &view.pending_sync {
+                    let removed = entries.iter().filter(|entry| view.sync_mode.removes(entry.kind)).count();
+
+                    if removed == 0 {
+                        start_sync = true;
+                    } else {
+                        ui.horizontal(|ui| {
+                            ui.colored_label(ui.visuals().warn_fg_color, format!("Буде остаточно видалено елементів: {}", removed));
+
+                            if ui.button("Видалити і синхронізувати").on_hover_cursor(PointingHand).clicked() {
+                                start_sync = true;
+                            }
+
+                            if ui.button("Скасувати").on_hover_cursor(PointingHand).clicked() {
+                                view.pending_sync = None;
+                            }
+                        });
+                    }
+                }
+
+                if start_sync && let Some(entries) = view.pending_sync.take() {
+                    let (left, right, mode) = (view.left.clone(), view.right.clone(), view.sync_mode);
+
+                    view.sync_job = Some(Job::spawn(ctx, move |job| sync_folders(&left, &right, entries, mode, job)));
+                }
+
+                if let Some(job) = &view.sync_job {
+                    match &job.result {
+                        None => {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label(format!("Синхронізовано: {}", job.progress.unwrap_or(0)));
+
+                                if ui.button("Скасувати").on_hover_cursor(PointingHand).clicked() {
+                                    job.cancel();
+                                }
+                            });
+                        },
+                        Some(sync_result) => {
+                            ui.label(format!("Синхронізовано: {}", sync_result.done));
+
+                            for (path, err) in sync_result.errors.iter().take(5) {
+                                ui.colored_label(ui.visuals().error_fg_color, format!("{}: {}", path.display(), err));
+                            }
+                        }
+                    }
+                }
+
+                ui.separator();
+
+                let entry_text = |info: Option<EntryInfo>| match info {
+                    Some(info) if info.is_dir => "папка".to_string(),
+                    Some(info) => format!("{}  {}", format_size(info.size), info.modified.map(format_time).unwrap_or_default()),
+                    None => String::new(),
+                };
+
+                egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+                    egui::Grid::new("compare_grid").striped(true).num_columns(4).spacing(vec2(12.0, 4.0)).show(ui, |ui| {
+                        ui.label("");
+                        ui.strong("Ліворуч");
+                        ui.strong("Шлях");
+                        ui.strong("Праворуч");
+                        ui.end_row();
+
+                        for (entry, selected) in result.entries.iter().zip(view.selected.iter_mut()) {
+                            let color = match entry.kind {
+                                DiffKind::LeftOnly => egui::Color32::from_rgb(90, 170, 90),
+                                DiffKind::RightOnly => egui::Color32::from_rgb(90, 140, 210),
+                                DiffKind::Different => egui::Color32::from_rgb(220, 150, 60),
+                            };
+
+                            ui.checkbox(selected, "");
+                            ui.label(entry_text(entry.left));
+                            ui.horizontal(|ui| {
+                                ui.colored_label(color, entry.relative.to_string_lossy());
+
+                                let both_files = entry.left.is_some_and(|info| !info.is_dir) && entry.right.is_some_and(|info| !info.is_dir);
+
+                                if entry.kind == DiffKind::Different
+                                    && both_files
+                                    && ui.small_button("Різниця").on_hover_cursor(PointingHand).clicked()
+                                {
+                                    diff_request = Some((view.left.join(&entry.relative), view.right.join(&entry.relative)));
+                                }
+                            });
+                            ui.label(entry_text(entry.right));
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+
+        if !open && let Some(view) = self.compare.take() {
+            view.cancel_jobs();
+        }
+
+        if let Some((left, right)) = diff_request {
+            self.open_diff(left, right, ctx);
+        }
+    }
+
+    fn open_compress(&mut self, sources: Vec<PathBuf>) {
+        let Some(first) = sources.first() else {
+            return;
+        };
+
+        let target_dir = first.parent().map(PathBuf::from).unwrap_or_default();
+
+        // кілька елементів пакуються в архів з назвою папки, де вони лежать
+        let name = if sources.len() == 1 {
+            first.file_stem().unwrap_or_default().to_string_lossy().to_string()
+        } else {
+            target_dir.file_name().map_or("Архів".to_string(), |name| name.to_string_lossy().to_string())
+        };
+
+        if let Some(old_dialog) = self.compress.take()
+            && let Some(job) = old_dialog.job
+        {
+            job.cancel();
+        }
+
+        self.compress = Some(CompressDialog {
+            sources,
+            target_dir,
+            name,
+            format: ArchiveFormat::Zip,
+            level: ArchiveFormat::Zip.default_level(),
+            exclude: String::new(),
+            job: None,
+        });
+    }
+
+    fn show_compress(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = self.compress.as_mut() else {
+            return;
+        };
+
+        let mut finished = false;
+
+        if let Some(job) = &mut dialog.job {
+            let was_running = !job.is_finished();
+
+            job.poll();
+
+            finished = was_running && job.is_finished();
+        }
+
+        let mut open = true;
+
+        egui::Window::new("Стиснути")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let is_busy = dialog.job.as_ref().is_some_and(|job| !job.is_finished());
+
+                if dialog.sources.len() == 1 {
+                    ui.label(dialog.sources[0].to_string_lossy());
+                } else {
+                    ui.label(format!("Вибрано елементів: {}", dialog.sources.len()));
+                }
+
+                ui.add_enabled_ui(!is_busy, |ui| {
+                    egui::Grid::new("compress_grid").num_columns(2).spacing(vec2(12.0, 6.0)).show(ui, |ui| {
+                        ui.label("Назва архіву");
+                        ui.horizontal(|ui| {
+                            ui.text_edit_singleline(&mut dialog.name);
+                            ui.label(dialog.format.extensions()[0]);
+                        });
+                        ui.end_row();
+
+                        ui.label("Формат");
+                        egui::ComboBox::from_id_salt("compress_format")
+                            .selected_text(dialog.format.label())
+                            .show_ui(ui, |ui| {
+                                for format in ArchiveFormat::ALL {
+                                    if ui.selectable_value(&mut dialog.format, format, format.label()).changed() {
+                                        dialog.level = format.default_level();
+                                    }
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label("Рівень стиснення");
+                        match dialog.format.level_range() {
+                            Some(range) => ui.add(egui::Slider::new(&mut dialog.level, range)),
+                            None => ui.label("без стиснення"),
+                        };
+                        ui.end_row();
+
+                        ui.label("Виключити");
+                        ui.add(egui::TextEdit::singleline(&mut dialog.exclude).hint_text("*.log, target, .git"));
+                        ui.end_row();
+                    });
+
+                    let output = dialog.target_dir.join(format!("{}{}", dialog.name.trim(), dialog.format.extensions()[0]));
+
+                    if ui.add_enabled(!dialog.name.trim().is_empty(), egui::Button::new("Стиснути")).on_hover_cursor(PointingHand).clicked() {
+                        let sources = dialog.sources.clone();
+                        let options = CompressOptions {
+                            format: dialog.format,
+                            level: dialog.level,
+                            exclude: parse_patterns(&dialog.exclude),
+                        };
+
+                        dialog.job = Some(Job::spawn(ctx, move |job| compress(&sources, &output, &options, job)));
+                    }
+                });
+
+                if let Some(job) = &dialog.job {
+                    match &job.result {
+                        None => {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+
+                                let progress = job.progress.unwrap_or_default();
+                                ui.label(format!("Додано: {} з {}", progress.done, progress.total));
+
+                                if ui.button("Скасувати").on_hover_cursor(PointingHand).clicked() {
+                                    job.cancel();
+                                }
+                            });
+                        },
+                        Some(result) => show_batch_result(ui, "Додано до архіву", result),
+                    }
+                }
+            });
+
+        if finished {
+            let target_dir = dialog.target_dir.clone();
+
+            self.folder_tree.forget(&target_dir);
+        }
+
+        if !open && let Some(dialog) = self.compress.take()
+            && let Some(job) = dialog.job
+        {
+            job.cancel();
+        }
+    }
+
+    // "Витягти сюди" запускається одразу, "Витягти в папку…" спершу показує діалог
+    fn open_extract(&mut self, archive: PathBuf, target_dir: PathBuf, start: bool, ctx: &egui::Context) {
+        if let Some(old_dialog) = self.extract.take()
+            && let Some(job) = old_dialog.job
+        {
+            job.cancel();
+        }
+
+        let mut dialog = ExtractDialog {
+            archive,
+            target_input: target_dir.to_string_lossy().to_string(),
+            conflict: ConflictPolicy::KeepBoth,
+            job: None,
+        };
+
+        if start {
+            Self::start_extract(&mut dialog, ctx);
+        }
+
+        self.extract = Some(dialog);
+    }
+
+    fn start_extract(dialog: &mut ExtractDialog, ctx: &egui::Context) {
+        let (archive, target_dir, conflict) = (dialog.archive.clone(), expand_path(&dialog.target_input), dialog.conflict);
+
+        dialog.job = Some(Job::spawn(ctx, move |job| extract_archive(&archive, &target_dir, conflict, job)));
+    }
+
+    fn show_extract(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = self.extract.as_mut() else {
+            return;
+        };
+
+        let mut finished = false;
+
+        if let Some(job) = &mut dialog.job {
+            let was_running = !job.is_finished();
+
+            job.poll();
+
+            finished = was_running && job.is_finished();
+        }
+
+        let mut open = true;
+
+        egui::Window::new("Розпакування")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let is_busy = dialog.job.as_ref().is_some_and(|job| !job.is_finished());
+
+                ui.label(dialog.archive.to_string_lossy());
+
+                ui.add_enabled_ui(!is_busy, |ui| {
+                    egui::Grid::new("extract_grid").num_columns(2).spacing(vec2(12.0, 6.0)).show(ui, |ui| {
+                        ui.label("Папка призначення");
+                        ui.add(egui::TextEdit::singleline(&mut dialog.target_input).desired_width(300.0));
+                        ui.end_row();
+
+                        ui.label("Якщо файл існує");
+                        ui.vertical(|ui| {
+                            for conflict in ConflictPolicy::ALL {
+                                ui.radio_value(&mut dialog.conflict, conflict, conflict.label());
+                            }
+                        });
+                        ui.end_row();
+                    });
+
+                    if ui.button("Витягти").on_hover_cursor(PointingHand).clicked() {
+                        Self::start_extract(dialog, ctx);
+                    }
+                });
+
+                if let Some(job) = &dialog.job {
+                    match &job.result {
+                        None => {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label(format!("Оброблено записів: {}", job.progress.unwrap_or(0)));
+
+                                if ui.button("Скасувати").on_hover_cursor(PointingHand).clicked() {
+                                    job.cancel();
+                                }
+                            });
+                        },
+                        Some(result) => show_batch_result(ui, "Витягнуто файлів", result),
+                    }
+                }
+            });
+
+        if finished {
+            let target_dir = expand_path(&dialog.target_input);
+
+            self.folder_tree.forget(&target_dir);
+        }
+
+        if !open && let Some(dialog) = self.extract.take()
+            && let Some(job) = dialog.job
+        {
+            job.cancel();
+        }
+    }
+
+    fn open_diff(&mut self, left: PathBuf, right: PathBuf, ctx: &egui::Context) {
+        let (left_path, right_path) = (left.clone(), right.clone());
+
+        let job = Job::spawn(ctx, move |_| diff_files(&left_path, &right_path).map_err(|err| err.to_string()));
+
+        if let Some(old_view) = self.diff.replace(DiffView { left, right, side_by_side: true, job, result: None }) {
+            old_view.job.cancel();
+        }
+    }
+
+    fn show_diff(&mut self, ctx: &egui::Context, window_size: egui::Vec2) {
+        let Some(view) = self.diff.as_mut() else {
+            return;
+        };
+
+        view.job.poll();
+
+        if let Some(result) = view.job.result.take() {
+            view.result = Some(result);
+        }
+
+        let mut open = true;
+
+        egui::Window::new("Порівняння файлів")
+            .open(&mut open)
+            .collapsible(false)
+            .default_size(window_size * 0.8)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(view.left.to_string_lossy()).color(egui::Color32::from_rgb(220, 90, 90)));
+                    ui.label("→");
+                    ui.label(RichText::new(view.right.to_string_lossy()).color(egui::Color32::from_rgb(90, 170, 90)));
+                });
+
+                let result = match &view.result {
+                    None => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Порівняння…");
+                        });
+
+                        return;
+                    },
+                    Some(Err(err)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err);
+
+                        return;
+                    },
+                    Some(Ok(result)) => result,
+                };
+
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut view.side_by_side, true, "Поруч");
+                    ui.radio_value(&mut view.side_by_side, false, "Об'єднаний");
+
+                    ui.separator();
+
+                    if result.inserted == 0 && result.deleted == 0 {
+                        ui.label("Файли однакові");
+                    } else {
+                        ui.colored_label(egui::Color32::from_rgb(90, 170, 90), format!("+{}", result.inserted));
+                        ui.colored_label(egui::Color32::from_rgb(220, 90, 90), format!("−{}", result.deleted));
+                    }
+                });
+
+                ui.separator();
+
+                let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+
+                if view.side_by_side {
+                    let rows = side_by_side(&result.lines);
+                    let half_width = (ui.available_width() - ui.spacing().item_spacing.x) / 2.0;
+
+                    egui::ScrollArea::vertical().auto_shrink(false).show_rows(ui, row_height, rows.len(), |ui, range| {
+                        for (old, new) in &rows[range] {
+                            ui.horizontal(|ui| {
+                                let old_job = diff_line_job(ui, *old, old.and_then(|line| line.old_number).map(|number| format!("{:>5} ", number)));
+                                let new_job = diff_line_job(ui, *new, new.and_then(|line| line.new_number).map(|number| format!("{:>5} ", number)));
+
+                                ui.add_sized(vec2(half_width, row_height), egui::Label::new(old_job).truncate());
+                                ui.add_sized(vec2(half_width, row_height), egui::Label::new(new_job).truncate());
+                            });
+                        }
+                    });
+                } else {
+                    egui::ScrollArea::both().auto_shrink(false).show_rows(ui, row_height, result.lines.len(), |ui, range| {
+                        for line in &result.lines[range] {
+                            let number = |number: Option<usize>| number.map_or("     ".to_string(), |number| format!("{:>5}", number));
+                            let sign = match line.kind {
+                                DiffLineKind::Equal => ' ',
+                                DiffLineKind::Delete => '-',
+                                DiffLineKind::Insert => '+',
+                            };
+
+                            let gutter = format!("{} {} {} ", number(line.old_number), number(line.new_number), sign);
+
+                            ui.add(egui::Label::new(diff_line_job(ui, Some(line), Some(gutter))).extend());
+                        }
+                    });
+                }
+            });
+
+        if !open && let Some(view) = self.diff.take() {
+            view.job.cancel();
+        }
+    }
+
+    fn show_settings(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        egui::Grid::new("settings_grid").num_columns(2).spacing(vec2(20.0, 8.0)).show(ui, |ui| {
+            ui.label("Тема");
+            ui.horizontal(|ui| {
+                if ui.radio_value(&mut self.settings.dark_mode, true, "Темна").changed()
+                    || ui.radio_value(&mut self.settings.dark_mode, false, "Світла").changed()
+                {
+                    if self.settings.dark_mode {
+                        ctx.set_visuals(egui::style::Visuals::dark());
+                    } else {
+                        ctx.set_visuals(egui::style::Visuals::light());
+                    }
+                }
+            });
+            ui.end_row();
+
+            ui.label("Вигляд нових вкладок");
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.settings.view_mode, ViewMode::List, "Список");
+                ui.radio_value(&mut self.settings.view_mode, ViewMode::Grid, "Сітка");
+                ui.radio_value(&mut self.settings.view_mode, ViewMode::Details, "Таблиця");
+            });
+            ui.end_row();
+
+            ui.label("Сортування");
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt("settings_sort_key")
+                    .selected_text(self.settings.sort_order.key.label())
+                    .show_ui(ui, |ui| {
+                        for key in SortKey::ALL {
+                            ui.selectable_value(&mut self.settings.sort_order.key, key, key.label());
+                        }
+                    });
+
+                ui.checkbox(&mut self.settings.sort_order.descending, "За спаданням");
+            });
+            ui.end_row();
+
+            ui.label("Після запуску");
+            ui.checkbox(&mut self.settings.restore_session, "Відновлювати вкладки");
+            ui.end_row();
+
+            ui.label("Стартова папка");
+            ui.add_enabled(
+                !self.settings.restore_session,
+                egui::TextEdit::singleline(&mut self.start_dir_input).hint_text(env::current_dir().unwrap_or_default().to_string_lossy()),
+            );
+            ui.end_row();
+
+            ui.label("Ширина бічної панелі");
+            if let Some(sidebar_width) = &mut self.settings.sidebar_width
+                && ui.add(egui::DragValue::new(sidebar_width).range(SIDEBAR_WIDTH_RANGE).suffix(" px")).changed()
+            {
+                self.resize_sidebar = true;
+            }
+            ui.end_row();
+
+            ui.label("Розмір вікна");
+            ui.horizontal(|ui| {
+                if let Some([width, height]) = &mut self.settings.window_size {
+                    let width_changed = ui.add(egui::DragValue::new(width).range(400.0..=10000.0).suffix(" px")).changed();
+                    let height_changed = ui.add(egui::DragValue::new(height).range(300.0..=10000.0).suffix(" px")).changed();
+
+                    if width_changed || height_changed {
+                        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(vec2(*width, *height)));
+                    }
+                }
+            });
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+        ui.label(RichText::new("Закладки").strong());
+
+        let mut remove: Option<usize> = None;
+
+        for (index, bookmark) in self.bookmarks.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.small_button("x").on_hover_cursor(PointingHand).clicked() {
+                    remove = Some(index);
+                }
+
+                ui.label(bookmark.title());
+                ui.weak(bookmark.uri.as_str());
+            });
+        }
+
+        if let Some(index) = remove {
+            self.bookmarks.remove(index);
+            self.save_bookmarks();
+        }
+
+        ui.add_space(10.0);
+
+        ui.horizontal(|ui| {
+            if ui.button("Зберегти").on_hover_cursor(PointingHand).clicked() {
+                let start_dir = expand_path(&self.start_dir_input);
+
+                self.settings.start_dir = if self.start_dir_input.trim().is_empty() { None } else { Some(start_dir) };
+
+                self.save_settings();
+            }
+
+            ui.weak(config_path().to_string_lossy());
+        });
+    }
+
+    // кеш за часом зміни з лістингу; поки вміст файлу аналізується у фоні, показується загальний тип
+    fn file_type(&mut self, entry: &VfsEntry) -> FileType {
+        if let Some((cached_modified, cached)) = self.file_types.get(&entry.path)
+            && *cached_modified == entry.modified
+        {
+            return cached.clone();
+        }
+
+        if self.file_types.len() > 10_000 {
+            self.file_types.clear();
+        }
+
+        let detected = if entry.is_dir {
+            from_mime("inode/directory".to_string())
+        } else {
+            match detect_by_name(&entry.path) {
+                Some(mime) => from_mime(mime),
+                None => {
+                    if self.vfs.is_local(&entry.path) {
+                        self.type_queue.push((entry.path.clone(), entry.modified));
+                    }
+
+                    from_mime("application/octet-stream".to_string())
+                }
+            }
+        };
+
+        self.file_types.insert(entry.path.clone(), (entry.modified, detected.clone()));
+
+        detected
+    }
+
+    fn poll_file_types(&mut self, ctx: &egui::Context) {
+        if let Some(job) = &mut self.type_job {
+            job.poll();
+
+            if let Some(detected) = job.result.take() {
+                for (path, modified, file_type) in detected {
+                    self.file_types.insert(path, (modified, file_type));
+                }
+
+                self.type_job = None;
+            }
+        }
+
+        if self.type_job.is_some() || self.type_queue.is_empty() {
+            return;
+        }
+
+        let queue = std::mem::take(&mut self.type_queue);
+
+        self.type_job = Some(Job::spawn(ctx, move |job| {
+            queue
+                .into_iter()
+                .take_while(|_| !job.is_cancelled())
+                .map(|(path, modified)| {
+                    let detected = file_type(&path);
+
+                    (path, modified, detected)
+                })
+                .collect()
+        }));
+    }
+
+    fn show_details_header(ui: &mut egui::Ui) {
+        ui.strong("Назва");
+        ui.strong("Розмір");
+        ui.strong("Тип");
+        ui.strong("Змінено");
+        ui.end_row();
+    }
+
+    fn show_details_columns(&mut self, ui: &mut egui::Ui, entry: &VfsEntry) {
+        if self.tab().view_mode != ViewMode::Details {
+            return;
+        }
+
+        if entry.is_dir {
+            ui.label("");
+        } else {
+            ui.label(format_size(entry.size));
+        }
+
+        ui.label(self.file_type(entry).name);
+
+        match entry.modified {
+            Some(modified) => ui.label(format_time(modified)),
+            None => ui.label(""),
+        };
+
+        ui.end_row();
+    }
+
+    fn show_open_with_menu(&mut self, ui: &mut egui::Ui, path: &Path) {
+        if self.applications.is_none() {
+            let job = self.applications_job.get_or_insert_with(|| Job::spawn(ui.ctx(), |_| applications()));
+
+            job.poll();
+
+            if let Some(all_apps) = job.result.take() {
+                self.applications = Some(Arc::new(all_apps));
+                self.applications_job = None;
+            }
+        }
+
+        if let Some(job) = &mut self.open_with_job {
+            job.poll();
+
+            if let Some(menu) = job.result.take() {
+                self.open_with = Some(menu);
+                self.open_with_job = None;
+            }
+        }
+
+        let is_loading = self.open_with_job.is_some();
+
+        if self.open_with.as_ref().is_none_or(|menu| menu.path != path)
+            && !is_loading
+            && let Some(all_apps) = self.applications.clone()
+        {
+            let path = path.to_path_buf();
+
+            self.open_with_job = Some(Job::spawn(ui.ctx(), move |_| OpenWithMenu::load(path, &all_apps)));
+        }
+
+        let Some(menu) = self.open_with.as_mut().filter(|menu| menu.path == path) else {
+            ui.spinner();
+            return;
+        };
+
+        let mut chosen = None;
+
+        for entry in &menu.associated {
+            let label = if menu.default_id.as_ref() == Some(&entry.id) {
+                format!("{} (типова)", entry.name)
+            } else {
+                entry.name.clone()
+            };
+
+            if ui.button(label).on_hover_cursor(PointingHand).clicked() {
+                chosen = Some(entry.clone());
+            }
+        }
+
+        if menu.associated.is_empty() {
+            ui.label("Немає програм для цього типу файлів");
+        }
+
+        ui.separator();
+
+        ui.menu_button("Інші програми", |ui| {
+            egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                for entry in &menu.others {
+                    if ui.button(&entry.name).on_hover_cursor(PointingHand).clicked() {
+                        chosen = Some(entry.clone());
+                    }
+                }
+            });
+        });
+
+        ui.checkbox(&mut menu.set_default, "Зробити типовою");
+
+        if let Some(entry) = chosen {
+            if menu.set_default && let Err(err) = set_default_app(&menu.mime, &entry.id) {
+                self.toasts.push("Не вдалося зробити програму типовою", err.to_string());
+            }
+
+            match entry.command(&[path]) {
+                Some(command) => self.launcher.spawn(command),
+                None => self.toasts.push(format!("Не вдалося запустити {}", entry.name), "порожній рядок Exec"),
+            }
+
+            // меню збереться заново, щоб показати нову типову програму
+            self.open_with = None;
+
+            ui.close_menu();
+        }
+    }
+
+    fn show_preview(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let path = self.tab().selected_item.clone();
+
+        if path.as_os_str().is_empty() || !self.vfs.is_local(&path) {
+            ui.label("Виберіть файл для перегляду");
+            return;
+        }
+
+        if let Some((_, job)) = &mut self.preview_job {
+            job.poll();
+        }
+
+        if let Some((_, job)) = self.preview_job.take_if(|(_, job)| job.is_finished()) {
+            if let Some(Some(preview)) = job.result {
+                // інакше після зміни файлу показувалась би стара картинка з кешу
+                if let PreviewContent::Image { uri, .. } = &preview.content {
+                    ctx.forget_image(uri);
+                }
+
+                self.preview = Some(preview);
+            }
+
+            self.preview_checked = Instant::now();
+        }
+
+        let shown = self.preview.as_ref().filter(|preview| preview.path == path).map(|preview| preview.modified);
+        let loading = self.preview_job.as_ref().is_some_and(|(job_path, _)| *job_path == path);
+
+        // зміну файлу перевіряємо у фоні раз на PREVIEW_REFRESH, а не stat у кожному кадрі
+        if !loading && (shown.is_none() || self.preview_checked.elapsed() >= PREVIEW_REFRESH) {
+            let job_path = path.clone();
+
+            let job = Job::spawn(ctx, move |_| match shown {
+                Some(modified) => Preview::reload_if_changed(&job_path, modified),
+                None => Some(Preview::load(&job_path)),
+            });
+
+            if let Some((_, old_job)) = self.preview_job.replace((path.clone(), job)) {
+                old_job.cancel();
+            }
+        }
+
+        let Some(preview) = self.preview.as_mut().filter(|preview| preview.path == path) else {
+            ui.spinner();
+            return;
+        };
+
+        ui.heading(path.file_name().unwrap_or_default().to_string_lossy());
+
+        egui::Grid::new("preview_metadata").num_columns(2).show(ui, |ui| {
+            ui.label("Тип");
+            ui.label(&preview.file_type.name);
+            ui.end_row();
+
+            if !matches!(preview.content, PreviewContent::Folder { .. }) {
+                ui.label("Розмір");
+                ui.label(format_size(preview.size));
+                ui.end_row();
+            }
+
+            if let Some(modified) = preview.modified {
+                ui.label("Змінено");
+                ui.label(format_time(modified));
+                ui.end_row();
+            }
+
+            if let PreviewContent::Image { dimensions: Some((width, height)), .. } = &preview.content {
+                ui.label("Розміри");
+                ui.label(format!("{} × {}", width, height));
+                ui.end_row();
+            }
+        });
+
+        ui.separator();
+
+        match &preview.content {
+            PreviewContent::Text { text, language, truncated } => {
+                if *truncated {
+                    ui.label(format!("Показано перші {}", format_size(PREVIEW_LIMIT as u64)));
+                }
+
+                egui::ScrollArea::both().auto_shrink(false).show(ui, |ui| {
+                    let theme = egui_extras::syntax_highlighting::CodeTheme::from_style(ui.style());
+                    egui_extras::syntax_highlighting::code_view_ui(ui, &theme, text, language);
+                });
+            },
+            PreviewContent::Image { uri, .. } => {
+                ui.horizontal(|ui| {
+                    let zoom = preview.zoom.unwrap_or(1.0);
+
+                    if ui.add(egui::Button::image(egui::Image::new(Icon::MagnifyingGlassMinus).max_size(vec2(16.0, 16.0)))).clicked() {
+                        preview.zoom = Some((zoom / 1.25).max(0.05));
+                    }
+
+                    if ui.selectable_label(preview.zoom.is_none(), "Вмістити").clicked() {
+                        preview.zoom = None;
+                    }
+
+                    if ui.add(egui::Button::image(egui::Image::new(Icon::MagnifyingGlassPlus).max_size(vec2(16.0, 16.0)))).clicked() {
+                        preview.zoom = Some((zoom * 1.25).min(20.0));
+                    }
+
+                    if let Some(zoom) = preview.zoom {
+                        ui.label(format!("{:.0}%", zoom * 100.0));
+                    }
+                });
+
+                egui::ScrollArea::both().auto_shrink(false).show(ui, |ui| {
+                    let image = egui::Image::new(uri.as_str());
+
+                    match preview.zoom {
+                        Some(zoom) => ui.add(image.fit_to_original_size(zoom)),
+                        None => ui.add(image.max_size(ui.available_size()).maintain_aspect_ratio(true)),
+                    };
+                });
+            },
+            PreviewContent::Pdf(Ok(page)) => {
+                egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+                    ui.add(egui::Image::new(page.as_str()).max_width(ui.available_width()));
+                });
+            },
+            PreviewContent::Pdf(Err(error)) => {
+                ui.label(format!("Попередній перегляд PDF недоступний: {}", error));
+            },
+            PreviewContent::Binary { dump, truncated } => {
+                if *truncated {
+                    ui.label("Показано перші 4 КБ");
+                }
+
+                egui::ScrollArea::both().auto_shrink(false).show(ui, |ui| {
+                    ui.add(egui::Label::new(RichText::new(dump).monospace()).extend());
+                });
+            },
+            PreviewContent::Folder { items } => {
+                ui.label(format!("Елементів: {}", items));
+            },
+            PreviewContent::Error(err) => {
+                ui.label(format!("Не вдалося прочитати файл: {}", err));
+            }
+        }
+    }
+
+    // пробіл відкриває великий перегляд без запуску сторонньої програми
+    fn show_quick_look(&mut self, ctx: &egui::Context, window_size: egui::Vec2) {
+        if !ctx.wants_keyboard_input() && ctx.input(|i| i.key_pressed(egui::Key::Space)) {
+            self.quick_look = !self.quick_look && !self.tab().selected_item.as_os_str().is_empty();
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            self.quick_look = false;
+        }
+
+        if !self.quick_look {
+            return;
+        }
+
+        let mut open = true;
+
+        egui::Window::new("Перегляд")
+            .open(&mut open)
+            .collapsible(false)
+            .default_size(window_size * 0.8)
+            .default_pos(pos2(window_size.x * 0.1, window_size.y * 0.1))
+            .show(ctx, |ui| {
+                self.show_preview(ui, ctx);
+            });
+
+        self.quick_look &= open;
+    }
+
+    // Ctrl+клацання додає елемент до виділення або прибирає з нього
+    fn click_item(&mut self, ui: &egui::Ui, path: PathBuf) {
+        if ui.input(|i| i.modifiers.command) {
+            self.tab_mut().toggle_marked(path);
+        } else {
+            self.tab_mut().select(path);
+        }
+    }
+
+    fn rename_item(&mut self, path: &Path, new_name: &str) -> io::Result<PathBuf> {
+        if self.vfs.is_local(path) {
+            return rename_in_place(path, new_name);
+        }
+
+        let new_path = renamed_path(path, new_name)?;
+
+        if new_path != path {
+            self.vfs.resolve(path)?.rename(path, &new_path)?;
+            self.reload_listings();
+        }
+
+        Ok(new_path)
+    }
+
+    // розпакування може тривати довго, тож воно йде разом з іншими передачами і закінчується тостом
+    fn extract_archive_entries(&mut self, ctx: &egui::Context, sources: Vec<PathBuf>, target_dir: PathBuf) {
+        let title = match sources.as_slice() {
+            [source] => format!("{} → {}", source.display(), target_dir.display()),
+            _ => format!("{} елементів → {}", sources.len(), target_dir.display()),
+        };
+
+        let job_target_dir = target_dir.clone();
+        let job = Job::spawn(ctx, move |job| extract_into(&sources, &job_target_dir, job));
+
+        self.transfers.push(TransferView { title, target_dir, is_move: false, job });
+    }
+
+    // файл копіюється в кеш у фоні, відкривається в show_transfers, коли завантажиться
+    fn open_virtual_file(&mut self, ctx: &egui::Context, vfs: &Arc<dyn Vfs>, path: &Path) {
+        let (vfs, path) = (vfs.clone(), path.to_path_buf());
+
+        self.downloads.push(Job::spawn(ctx, move |_| fetch_to_cache(vfs.as_ref(), &path)));
+    }
+
+    // архіви, кошик та інші простори, до яких немає прямого доступу через std::fs
+    fn show_virtual_entries(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, vfs: &Arc<dyn Vfs>, entries: Vec<VfsEntry>) {
+        let in_archive = self.vfs.split_archive(&self.tab().current_dir).is_some();
+
+        for entry in sort_entries(entries, self.settings.sort_order) {
+            let path = entry.path.clone();
+            let icon = if entry.is_dir { Icon::Folder } else { self.file_type(&entry).icon };
+
+            if self.inline_rename.as_ref() == Some(&path) {
+                self.show_inline_rename(ui, icon);
+                self.show_details_columns(ui, &entry);
+                continue;
+            }
+
+            let entry_button = ui.add(egui::Button::image_and_text(
+                egui::Image::new(icon),
+                RichText::new(&entry.name).size(14.0),
+            ).selected(self.tab().is_selected(&path)));
+
+            entry_button.clone().on_hover_cursor(PointingHand);
+
+            if entry_button.clicked() {
+                self.click_item(ui, path.clone());
+            }
+
+            entry_button.interact(egui::Sense::drag()).dnd_set_drag_payload(path.clone());
+
+            if entry.is_dir && !vfs.is_read_only() {
+                self.handle_folder_drop(ui, &entry_button, &path);
+            }
+
+            if entry_button.double_clicked() {
+                if entry.is_dir {
+                    self.navigate_to(path.clone());
+                } else {
+                    self.open_virtual_file(ctx, vfs, &path);
+                }
+            }
+
+            entry_button.context_menu(|ui| {
+                if in_archive && ui.button("Копіювати").on_hover_cursor(PointingHand).clicked() {
+                    self.archive_clipboard = Some(path.clone());
+                    ui.close_menu();
+                }
+                if in_archive
+                    && self.commander_mode
+                    && !self.other_tab().is_disk_selection
+                    && self.vfs.split_archive(&self.other_tab().current_dir).is_none()
+                    && ui.button("Витягти в іншу панель").on_hover_cursor(PointingHand).clicked()
+                {
+                    let target_dir = self.other_tab().current_dir.clone();
+
+                    self.extract_archive_entries(ctx, vec![path.clone()], target_dir);
+                    ui.close_menu();
+                }
+                if !vfs.is_read_only() && ui.button("Перейменувати").on_hover_cursor(PointingHand).clicked() {
+                    self.tab_mut().select(path.clone());
+                    self.rename = entry.name.clone();
+                    self.inline_rename = Some(path.clone());
+                    self.inline_rename_focus = true;
+
+                    ui.close_menu();
+                }
+                if !in_archive && ui.button("Видалити").on_hover_cursor(PointingHand).clicked() {
+                    if let Err(err) = vfs.remove(&path) {
+                        self.toasts.push("Не вдалося видалити", err.to_string());
+                    }
+
+                    self.reload_listings();
+
+                    ui.close_menu();
+                }
+                if ui.button("Копіювати шлях").on_hover_cursor(PointingHand).clicked() {
+                    ctx.copy_text(path.to_string_lossy().to_string());
+                    ui.close_menu();
+                }
+
+                self.is_main_context_menu_open = false;
+            });
+
+            self.show_details_columns(ui, &entry);
+        }
+    }
+
+    // після змін у віртуальних папках їхній вміст перечитується при наступному кадрі
+    fn reload_listings(&mut self) {
+        for listing in self.listings.values_mut() {
+            listing.loaded_at = None;
+        }
+    }
+
+    fn poll_listing(&mut self, ctx: &egui::Context, dir: &Path) -> Option<Result<Listing, String>> {
+        // папки, які вже не відкриті в жодній вкладці, забуваємо
+        let tabs = &self.tabs;
+        self.listings.retain(|listed_dir, _| listed_dir == dir || tabs.iter().any(|tab| tab.current_dir == *listed_dir));
+
+        let listing = self.listings.entry(dir.to_path_buf()).or_default();
+
+        if let Some(job) = &mut listing.job {
+            job.poll();
+
+            if let Some(result) = job.result.take() {
+                listing.result = Some(result.map_err(|err| err.to_string()));
+                listing.loaded_at = Some(Instant::now());
+                listing.job = None;
+            }
+        }
+
+        let is_stale = listing.loaded_at.is_none_or(|loaded_at| loaded_at.elapsed() >= LISTING_REFRESH);
+
+        if listing.job.is_none() && is_stale {
+            let (registry, dir) = (self.vfs.clone(), dir.to_path_buf());
+
+            listing.job = Some(Job::spawn(ctx, move |_| {
+                let vfs = registry.resolve(&dir)?;
+                let entries = vfs.list(&dir)?;
+
+                Ok((vfs, entries))
+            }));
+        }
+
+        if let Some(loaded_at) = listing.loaded_at {
+            ctx.request_repaint_after(LISTING_REFRESH.saturating_sub(loaded_at.elapsed()));
+        }
+
+        listing.result.clone()
+    }
+
+    fn show_dir_entries(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let current_dir = self.tab().current_dir.clone();
+
+        if !self.vfs.is_local(&current_dir) {
+            match self.poll_listing(ctx, &current_dir) {
+                Some(Ok((vfs, entries))) => self.show_virtual_entries(ui, ctx, &vfs, entries),
+                Some(Err(err)) => {
+                    ui.colored_label(ui.visuals().error_fg_color, format!("Не вдалося відкрити папку: {}", err));
+                },
+                None => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Завантаження...");
+                    });
+                }
+            }
+
+            return;
+        }
+
+        let listing = self.vfs.resolve(&current_dir).and_then(|vfs| {
+            let entries = vfs.list(&current_dir)?;
+
+            Ok((vfs, entries))
+        });
+
+        let (vfs, entries) = match listing {
+            Ok(listing) => listing,
+            Err(err) => {
+                ui.colored_label(ui.visuals().error_fg_color, format!("Не вдалося відкрити папку: {}", err));
+                return;
+            }
+        };
+
+        for dir_element in sort_entries(entries, self.settings.sort_order) {
+            if self.inline_rename.as_ref() == Some(&dir_element.path) {
+                if dir_element.is_dir {
+                    self.show_inline_rename(ui, Icon::Folder);
+                } else {
+                    let icon = self.file_type(&dir_element).icon;
+                    self.show_inline_rename(ui, icon);
+                }
+
+                self.show_details_columns(ui, &dir_element);
+            } else if dir_element.is_dir {
+                let dir_button = ui.add(egui::Button::image_and_text(
+                    egui::Image::new(Icon::Folder),
+                    RichText::new(&dir_element.name).size(14.0),
+                ).selected(self.tab().is_selected(&dir_element.path)));
+
+                dir_button.clone().on_hover_cursor(PointingHand);
+
+                if dir_button.clicked() {
+                    self.click_item(ui, dir_element.path.clone());
+                }
+
+                dir_button.interact(egui::Sense::drag()).dnd_set_drag_payload(dir_element.path.clone());
+                self.handle_folder_drop(ui, &dir_button, &dir_element.path);
+
+                if dir_button.double_clicked() {
+                    self.open_folder(&dir_element);
+                }
+
+                if dir_button.middle_clicked() {
+                    self.open_tab(dir_element.path.clone(), false);
+                }
+
+                dir_button.context_menu(|ui| {
+                    if ui.button("Перейменувати").on_hover_cursor(PointingHand).clicked() {
+                        println!("Перейменувати");
+
+                        self.popup_type = PopupType::Rename;
+                        self.tab_mut().selected_item = dir_element.path.clone();
+                        self.rename = dir_element.name.clone();
+                        
+                        ui.close_menu();
+                    }
+                    if ui.button("Копіювати").on_hover_cursor(PointingHand).clicked() {
+                        println!("Копіювати");
+                        ui.close_menu();
+                    }
+                    if ui.button("Вирізати").on_hover_cursor(PointingHand).clicked() {
+                        println!("Вирізати");
+                        ui.close_menu();
+                    }
+                    if ui.button("Видалити").on_hover_cursor(PointingHand).clicked() {
+                        match vfs.remove(&dir_element.path) {
+                            Ok(()) => {
+                                println!("папка була видалена");
+                            },
+                            Err(err) => {
+                                println!("{:#?}", err);
+                            }
+                        }
+                        ui.close_menu();
+                    }
+                    if ui.button("Копіювати шлях до папки").on_hover_cursor(PointingHand).clicked() {
+                        println!("Копіювати шлях");
+                        
+                        ctx.copy_text(self.tab().current_dir.to_string_lossy().to_string());
+
+                        ui.close_menu();
+                    }
+                    if ui.button("Додати в закладки").on_hover_cursor(PointingHand).clicked() {
+                        self.add_bookmark(&dir_element.path);
+
+                        ui.close_menu();
+                    }
+                    if ui.button("Аналізувати використання диска").on_hover_cursor(PointingHand).clicked() {
+                        self.open_disk_usage(dir_element.path.clone(), ctx);
+                        ui.close_menu();
+                    }
+                    if ui.button("Знайти дублікати").on_hover_cursor(PointingHand).clicked() {
+                        self.open_duplicates(dir_element.path.clone(), ctx);
+                        ui.close_menu();
+                    }
+                    let can_compare = self.vfs.is_local(&dir_element.path);
+
+                    if ui.add_enabled(can_compare, egui::Button::new("Порівняти з…")).on_hover_cursor(PointingHand).clicked() {
+                        let other_dir = Some(self.other_tab().current_dir.clone())
+                            .filter(|other_dir| self.commander_mode && self.vfs.is_local(other_dir));
+
+                        self.open_compare(dir_element.path.clone(), other_dir, ctx);
+                        ui.close_menu();
+                    }
+                    if ui.button("Стиснути…").on_hover_cursor(PointingHand).clicked() {
+                        self.open_compress(self.tab().selection_for(&dir_element.path));
+                        ui.close_menu();
+                    }
+                    if ui.button("Інформація про папку").on_hover_cursor(PointingHand).clicked() {
+                        self.open_properties(&dir_element.path, ctx);
+                        ui.close_menu();
+                    }
+
+                    self.is_main_context_menu_open = false;
+                });
+
+                self.show_details_columns(ui, &dir_element);
+            } else {
+                let icon = self.file_type(&dir_element).icon;
+
+                let file_button = ui.add(egui::Button::image_and_text(
+                    egui::Image::new(icon),
+                    RichText::new(&dir_element.name).size(14.0),
+                ).selected(self.tab().is_selected(&dir_element.path)));
+
+                file_button.clone().on_hover_cursor(PointingHand);
 
-use eframe::{
-    egui, 
-    egui::vec2, 
-    egui::pos2, 
-    egui::Pos2, 
-    egui::RichText, 
-    egui::CursorIcon::PointingHand,
-    egui::TopBottomPanel,
-    egui::SidePanel,
-    egui::Margin
-};
+                if file_button.clicked() {
+                    self.click_item(ui, dir_element.path.clone());
+                }
 
-use std::{
-    env,
-    fs,
-    path::PathBuf,
-    process::Command,
-    env::consts::OS,
-    fs::DirEntry,
-    fs::remove_dir_all,
-    fs::remove_file
-};
+                file_button.interact(egui::Sense::drag()).dnd_set_drag_payload(dir_element.path.clone());
 
-#[derive(PartialEq, Debug)]
-enum PopupType {
-    Rename,
-    DiskInfo,
-    None
-}
+                if file_button.double_clicked() {
+                    self.open_file(&dir_element);
+                }
 
-#[derive(Debug)]
-pub struct FileExplorerApp {
-    current_dir: PathBuf,
-    program_root: PathBuf,
-    selected_item: PathBuf,
-    is_disk_selection: bool,
-    disk_list: Vec<char>,
-    is_main_context_menu_open: bool,
-    interact_pointer_pos: Pos2,
-    popup_type: PopupType,
-    search: String,
-    rename: String,
-}
+                file_button.context_menu(|ui| {
+                    ui.menu_button("Відкрити за допомогою", |ui| {
+                        self.show_open_with_menu(ui, &dir_element.path);
+                    });
+                    if ui.button("Перейменувати").on_hover_cursor(PointingHand).clicked() {
+                        println!("Перейменувати");
 
-impl Default for FileExplorerApp {
-    fn default() -> Self {
-        let path = env::current_dir().unwrap_or_default();
+                        self.popup_type = PopupType::Rename;
+                        self.tab_mut().selected_item = dir_element.path.clone();
+                        self.rename = dir_element.name.clone();
+                        
+                        ui.close_menu();
+                    }
+                    if ui.button("Копіювати").on_hover_cursor(PointingHand).clicked() {
+                        println!("Копіювати");
+                        ui.close_menu();
+                    }
+                    if ui.button("Вирізати").on_hover_cursor(PointingHand).clicked() {
+                        println!("Вирізати");
+                        ui.close_menu();
+                    }
+                    if ui.button("Видалити").on_hover_cursor(PointingHand).clicked() {
+                        match vfs.remove(&dir_element.path) {
+                            Ok(()) => {
+                                println!("файл був видалений");
+                            },
+                            Err(err) => {
+                                println!("{:#?}", err);
+                            }
+                        }
 
-        Self {
-            current_dir: path.clone(),
-            program_root: path.clone(),
-            selected_item: path,
-            is_disk_selection: false,
-            disk_list: get_disk_list(),
-            is_main_context_menu_open: false,
-            interact_pointer_pos: pos2(0.0, 0.0),
-            popup_type: PopupType::None,
-            search: String::new(),
-            rename: String::new(),
+                        ui.close_menu();
+                    }
+                    if ui.button("Копіювати шлях до файлу").on_hover_cursor(PointingHand).clicked() {
+                        println!("Копіювати шлях");
+
+                        ctx.copy_text(dir_element.path.clone().to_string_lossy().to_string());
+
+                        ui.close_menu();
+                    }
+                    if ui.button("Стиснути…").on_hover_cursor(PointingHand).clicked() {
+                        self.open_compress(self.tab().selection_for(&dir_element.path));
+                        ui.close_menu();
+                    }
+                    if ArchiveFormat::from_path(&dir_element.path).is_some() {
+                        if ui.button("Витягти сюди").on_hover_cursor(PointingHand).clicked() {
+                            let target_dir = self.tab().current_dir.clone();
+
+                            self.open_extract(dir_element.path.clone(), target_dir, true, ctx);
+                            ui.close_menu();
+                        }
+                        if ui.button("Витягти в папку…").on_hover_cursor(PointingHand).clicked() {
+                            let target_dir = self.tab().current_dir.join(archive_stem(&dir_element.path));
+
+                            self.open_extract(dir_element.path.clone(), target_dir, false, ctx);
+                            ui.close_menu();
+                        }
+                    }
+                    if ui.button("Вибрати для порівняння").on_hover_cursor(PointingHand).clicked() {
+                        self.diff_candidate = Some(dir_element.path.clone());
+                        ui.close_menu();
+                    }
+                    if let Some(candidate) = self.diff_candidate.clone()
+                        && candidate != dir_element.path.clone()
+                        && ui.button(format!("Порівняти з «{}»", candidate.file_name().unwrap_or_default().to_string_lossy())).on_hover_cursor(PointingHand).clicked()
+                    {
+                        self.open_diff(candidate, dir_element.path.clone(), ctx);
+                        ui.close_menu();
+                    }
+                    if ui.button("Інформація про файл").on_hover_cursor(PointingHand).clicked() {
+                        self.open_properties(&dir_element.path, ctx);
+                        ui.close_menu();
+                    }
+
+                    self.is_main_context_menu_open = false;
+                });
+
+                self.show_details_columns(ui, &dir_element);
+            }
         }
     }
 }
 
-impl FileExplorerApp {
-    fn open_file(&self, dir_entry: &DirEntry) {
-        match OS {
-            "windows" => {
-                Command::new("cmd")
-                    .args(&[
-                        "/C", 
-                        "explorer", 
-                        dir_entry.path().to_str().unwrap()
-                    ])
-                    .status()
-                    .expect(&format!("could not be opened {}", dir_entry.path().to_str().unwrap()));
-            },
-            "linux" => {
-                Command::new("xdg-open")
-                    .arg(dir_entry.path().to_str().unwrap())
-                    .status()
-                    .expect(&format!("could not be opened {}", dir_entry.path().to_str().unwrap()));
-            },
-            _ => {
-                println!("операційна система не підтримується");
-            }
-        }
+fn show_batch_result(ui: &mut egui::Ui, done_label: &str, result: &BatchResult) {
+    ui.label(format!(
+        "{}: {}{}",
+        done_label,
+        result.done,
+        if result.cancelled { " (скасовано)" } else { "" },
+    ));
+
+    if !result.errors.is_empty() {
+        ui.colored_label(ui.visuals().error_fg_color, format!("Помилок: {}", result.errors.len()));
     }
 
-    fn open_folder(&mut self, dir_entry: &DirEntry) {
-        self.current_dir = dir_entry.path();
+    for (path, err) in result.errors.iter().take(5) {
+        ui.colored_label(ui.visuals().error_fg_color, format!("{}: {}", path.display(), err));
     }
+}
 
-    fn prev_folder(&mut self) {
-        let to_prev_folder = self.current_dir.pop();
+// рядок різниці моноширинним шрифтом; змінені всередині рядка частини підсвічені яскравіше
+fn diff_line_job(ui: &egui::Ui, line: Option<&DiffLine>, gutter: Option<String>) -> LayoutJob {
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let mut job = LayoutJob::default();
 
-        if !to_prev_folder {
-            self.is_disk_selection = true;
+    job.append(&gutter.unwrap_or_default(), 0.0, egui::TextFormat::simple(font_id.clone(), ui.visuals().weak_text_color()));
 
-            self.update_disk_list();
-        } else {
-            if self.is_disk_selection {
-                self.is_disk_selection = false;
-            }
-        }
+    let Some(line) = line else {
+        return job;
+    };
+
+    let (color, alpha) = match line.kind {
+        DiffLineKind::Equal => (egui::Color32::TRANSPARENT, 0),
+        DiffLineKind::Delete => (egui::Color32::from_rgb(220, 60, 60), 45),
+        DiffLineKind::Insert => (egui::Color32::from_rgb(60, 180, 60), 45),
+    };
+
+    for segment in &line.segments {
+        let alpha = if segment.emphasized { alpha * 3 } else { alpha };
+        let format = egui::TextFormat {
+            background: egui::Color32::from_rgba_unmultiplied(color.r(), color.g(), color.b(), alpha),
+            ..egui::TextFormat::simple(font_id.clone(), ui.visuals().text_color())
+        };
+
+        job.append(&segment.text, 0.0, format);
     }
 
-    fn update_disk_list(&mut self) {
-        self.disk_list = get_disk_list();
+    job
+}
+
+fn mode_checkbox(ui: &mut egui::Ui, mode: &mut u32, bit: u32, label: &str) {
+    let mut checked = *mode & bit != 0;
+
+    if ui.checkbox(&mut checked, label).changed() {
+        *mode ^= bit;
+    }
+}
+
+fn account_combo_box(ui: &mut egui::Ui, id: &str, accounts: &[Account], selected: &mut u32) {
+    let selected_name = accounts
+        .iter()
+        .find(|account| account.id == *selected)
+        .map_or(selected.to_string(), |account| account.name.clone());
+
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(selected_name)
+        .height(300.0)
+        .show_ui(ui, |ui| {
+            for account in accounts {
+                ui.selectable_value(selected, account.id, format!("{} ({})", account.name, account.id));
+            }
+        });
+}
+
+fn common_prefix(values: &[String]) -> Option<String> {
+    let first = values.first()?;
+    let mut prefix_len = first.len();
+
+    for value in &values[1..] {
+        prefix_len = first
+            .char_indices()
+            .zip(value.chars())
+            .take_while(|((_, a), b)| a == b)
+            .map(|((index, a), _)| index + a.len_utf8())
+            .last()
+            .unwrap_or(0)
+            .min(prefix_len);
     }
+
+    Some(first[..prefix_len].to_string())
 }
 
 /*struct DiskInfo {
@@ -119,6 +4790,16 @@ impl eframe::App for FileExplorerApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         let window_rect = ctx.screen_rect();
         let window_size = window_rect.size();
+
+        if let Some(inner_rect) = ctx.input(|i| i.viewport().inner_rect) {
+            self.settings.window_size = Some([inner_rect.width(), inner_rect.height()]);
+        }
+
+        self.handle_navigation_input(ctx);
+        self.handle_tab_input(ctx);
+        self.handle_commander_input(ctx);
+        self.handle_drive_events();
+        self.poll_file_types(ctx);
         
         TopBottomPanel::top("top_panel").show(ctx, |ui| {
             ui.add_space(5.0);
@@ -129,6 +4810,12 @@ impl eframe::App for FileExplorerApp {
             ui.add(egui::Separator::default());
             ui.add_space(5.0);
 
+            ui.horizontal(|ui| {
+                self.show_tab_bar(ui);
+            });
+
+            ui.add_space(5.0);
+
             ui.horizontal(|ui| {
                 // ui.style_mut().spacing.button_padding = vec2(3.0, 3.0);
                 
@@ -137,9 +4824,7 @@ impl eframe::App for FileExplorerApp {
                 ui.text_edit_singleline(&mut self.search).labelled_by(name_label.id);
 
                 let search_button = ui.add(egui::Button::image(
-                    egui::Image::new(
-                        format!("file://{}/{}", self.program_root.to_str().unwrap(), "assets/Font_Awesome_Icons/solid/magnifying-glass.svg")
-                    ).max_size(vec2(20.0, 20.0)),
+                    egui::Image::new(Icon::MagnifyingGlass).max_size(vec2(20.0, 20.0)),
                 ));
 
                 search_button.clone().on_hover_cursor(PointingHand);
@@ -152,30 +4837,23 @@ impl eframe::App for FileExplorerApp {
             ui.add_space(10.0);
 
             ui.horizontal(|ui| {
-                let prev_folder_button = ui.add(egui::Button::image(
-                    egui::Image::new(
-                        format!("file://{}/{}", self.program_root.to_str().unwrap(), "assets/Font_Awesome_Icons/solid/arrow-left.svg")
-                    ).max_size(vec2(20.0, 20.0)),
+                self.show_history_button(ui, Icon::ArrowLeft, true);
+                self.show_history_button(ui, Icon::ArrowRight, false);
+
+                let parent_folder_button = ui.add(egui::Button::image(
+                    egui::Image::new(Icon::ArrowUp).max_size(vec2(20.0, 20.0)),
                 ));
 
-                prev_folder_button.clone().on_hover_cursor(PointingHand);
+                parent_folder_button.clone().on_hover_cursor(PointingHand);
 
-                if prev_folder_button.clicked() {
-                    self.prev_folder();
+                if parent_folder_button.clicked() {
+                    self.parent_folder();
                 }
 
-                let mut theme_icon: String = String::new();
-
-                if ctx.style().visuals.dark_mode {
-                    theme_icon = "sun.svg".to_string();
-                } else {
-                    theme_icon = "moon.svg".to_string();
-                }
+                let theme_icon = if ctx.style().visuals.dark_mode { Icon::Sun } else { Icon::Moon };
 
                 let color_theme_button = ui.add(egui::Button::image(
-                    egui::Image::new(
-                        format!("file://{}/{}{}", self.program_root.to_str().unwrap(), "assets/Font_Awesome_Icons/solid/", theme_icon)
-                    ).max_size(vec2(20.0, 20.0)),
+                    egui::Image::new(theme_icon).max_size(vec2(20.0, 20.0)),
                 ));
 
                 color_theme_button.clone().on_hover_cursor(PointingHand);
@@ -186,15 +4864,138 @@ impl eframe::App for FileExplorerApp {
                     } else {
                         ctx.set_visuals(egui::style::Visuals::dark());
                     }
+
+                    self.settings.dark_mode = !self.settings.dark_mode;
+                }
+
+                let view_mode_icon = match self.tab().view_mode {
+                    ViewMode::List => Icon::Grip,
+                    ViewMode::Grid => Icon::TableList,
+                    ViewMode::Details => Icon::List,
+                };
+
+                let view_mode_button = ui.add(egui::Button::image(
+                    egui::Image::new(view_mode_icon).max_size(vec2(20.0, 20.0)),
+                ));
+
+                view_mode_button.clone().on_hover_cursor(PointingHand);
+
+                if view_mode_button.clicked() {
+                    self.tab_mut().view_mode = match self.tab().view_mode {
+                        ViewMode::List => ViewMode::Grid,
+                        ViewMode::Grid => ViewMode::Details,
+                        ViewMode::Details => ViewMode::List,
+                    };
+
+                    self.settings.view_mode = self.tab().view_mode;
+                }
+
+                let sort_icon = if self.settings.sort_order.descending { Icon::ArrowUpAZ } else { Icon::ArrowDownAZ };
+
+                ui.menu_image_button(
+                    egui::Image::new(sort_icon).max_size(vec2(20.0, 20.0)),
+                    |ui| {
+                        for key in SortKey::ALL {
+                            ui.radio_value(&mut self.settings.sort_order.key, key, key.label());
+                        }
+
+                        ui.separator();
+                        ui.checkbox(&mut self.settings.sort_order.descending, "За спаданням");
+                    },
+                );
+
+                let commander_button = ui.add(egui::Button::image(
+                    egui::Image::new(Icon::TableColumns).max_size(vec2(20.0, 20.0)),
+                ).selected(self.commander_mode));
+
+                commander_button.clone().on_hover_cursor(PointingHand);
+
+                if commander_button.clicked() {
+                    self.toggle_commander_mode();
+                }
+
+                let preview_button = ui.add(egui::Button::image(
+                    egui::Image::new(Icon::Image).max_size(vec2(20.0, 20.0)),
+                ).selected(self.settings.show_preview)).on_hover_text("Панель попереднього перегляду");
+
+                preview_button.clone().on_hover_cursor(PointingHand);
+
+                if preview_button.clicked() {
+                    self.settings.show_preview = !self.settings.show_preview;
+                }
+
+                if self.commander_mode {
+                    let swap_button = ui.add(egui::Button::image(
+                        egui::Image::new(Icon::ArrowRightArrowLeft).max_size(vec2(20.0, 20.0)),
+                    )).on_hover_text("Поміняти панелі місцями");
+
+                    swap_button.clone().on_hover_cursor(PointingHand);
+
+                    if swap_button.clicked() {
+                        self.swap_panes();
+                    }
+
+                    let sync_button = ui.add(egui::Button::image(
+                        egui::Image::new(Icon::Equals).max_size(vec2(20.0, 20.0)),
+                    )).on_hover_text("Відкрити ту ж папку в іншій панелі");
+
+                    sync_button.clone().on_hover_cursor(PointingHand);
+
+                    if sync_button.clicked() {
+                        self.sync_panes();
+                    }
+
+                    // порівнювати і синхронізувати можна лише локальні папки
+                    let panes_local = self.vfs.is_local(&self.tabs[self.active_tab].current_dir) && self.vfs.is_local(&self.second_pane.current_dir);
+
+                    let compare_button = ui.add_enabled(panes_local, egui::Button::image(
+                        egui::Image::new(Icon::CodeCompare).max_size(vec2(20.0, 20.0)),
+                    )).on_hover_text("Порівняти папки панелей");
+
+                    compare_button.clone().on_hover_cursor(PointingHand);
+
+                    if compare_button.clicked() {
+                        let (left, right) = (self.tabs[self.active_tab].current_dir.clone(), self.second_pane.current_dir.clone());
+
+                        self.open_compare(left, Some(right), ctx);
+                    }
                 }
+
+                let settings_button = ui.add(egui::Button::image(
+                    egui::Image::new(Icon::Gear).max_size(vec2(20.0, 20.0)),
+                )).on_hover_text("Налаштування");
+
+                settings_button.clone().on_hover_cursor(PointingHand);
+
+                if settings_button.clicked() {
+                    self.popup_type = PopupType::Settings;
+                }
+            });
+
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                self.show_path_bar(ui);
             });
 
             ui.add_space(5.0);
         });
 
-        SidePanel::left("left_panel")
-            .min_width(window_size.x / 100.0 * 15.0)
-            .max_width(window_size.x / 100.0 * 20.0)
+        let mut left_panel = SidePanel::left("left_panel")
+            .min_width(*SIDEBAR_WIDTH_RANGE.start())
+            .max_width(*SIDEBAR_WIDTH_RANGE.end())
+            .default_width(self.settings.sidebar_width.unwrap_or(window_size.x / 100.0 * 15.0));
+
+        // ширину, змінену в налаштуваннях, застосовуємо один раз
+        if self.resize_sidebar {
+            self.resize_sidebar = false;
+
+            if let Some(sidebar_width) = self.settings.sidebar_width {
+                left_panel = left_panel.exact_width(sidebar_width);
+            }
+        }
+
+        let left_panel = left_panel
             .frame(egui::Frame {
                 inner_margin: Margin {
                     left: 15,
@@ -212,7 +5013,7 @@ impl eframe::App for FileExplorerApp {
             .show(ctx, |ui| {
                 ui.vertical_centered_justified(|ui| {
                     let button_update = ui.add(egui::Button::image_and_text(
-                        egui::Image::new(format!("file://{}/{}", self.program_root.to_str().unwrap(), "assets/Font_Awesome_Icons/solid/arrow-rotate-right.svg")),
+                        egui::Image::new(Icon::ArrowRotateRight),
                         RichText::new("Update").size(14.0),
                     ));
 
@@ -220,42 +5021,92 @@ impl eframe::App for FileExplorerApp {
 
                     if button_update.clicked() {
                         self.update_disk_list();
+                        self.folder_tree.refresh();
+                        self.refresh_places();
                     }
 
                     ui.add_space(5.0);
                     ui.add(egui::Separator::default());
                     ui.add_space(5.0);
+                });
 
-                    for disk in &self.disk_list {
-                        let disk_button = ui.add(egui::Button::image_and_text(
-                            egui::Image::new(format!("file://{}/{}", self.program_root.to_str().unwrap(), "assets/Font_Awesome_Icons/solid/hard-drive.svg")),
-                            RichText::new(disk.to_string()).size(14.0),
-                        ));
+                self.show_places(ui);
 
-                        disk_button.clone().on_hover_cursor(PointingHand);
+                ui.add_space(5.0);
+                ui.add(egui::Separator::default());
+                ui.add_space(5.0);
 
-                        if disk_button.clicked() {
-                            let mut path = PathBuf::from(format!("{}:\\", disk));
-                            
-                            self.current_dir = path;
-                            self.is_disk_selection = false;
-                        }
+                self.show_remotes(ui);
 
-                        disk_button.context_menu(|ui| {
-                            if ui.button("Інформація про диск").on_hover_cursor(PointingHand).clicked() {
-                                println!("Інформація про диск");
+                ui.add_space(5.0);
+                ui.add(egui::Separator::default());
+                ui.add_space(5.0);
 
-                                self.popup_type = PopupType::DiskInfo;
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Папки").strong());
 
-                                ui.close_menu();
-                            }
+                    let hidden_icon = if self.folder_tree.show_hidden() { Icon::Eye } else { Icon::EyeSlash };
 
-                            self.is_main_context_menu_open = false;
-                        });
+                    let hidden_button = ui.add(egui::Button::image(
+                        egui::Image::new(hidden_icon).max_size(vec2(14.0, 14.0)),
+                    ).frame(false)).on_hover_text("Показувати приховані папки");
+
+                    if hidden_button.on_hover_cursor(PointingHand).clicked() {
+                        self.folder_tree.set_show_hidden(!self.folder_tree.show_hidden());
                     }
                 });
+
+                ui.add_space(5.0);
+
+                let current_dir = self.tab().current_dir.clone();
+                let reveal = self.folder_tree.needs_reveal(&current_dir);
+
+                egui::ScrollArea::vertical()
+                    .id_salt("folder_tree_scroll_area")
+                    .auto_shrink(false)
+                    .show(ui, |ui| {
+                        for (label, root) in self.volume_roots() {
+                            self.show_folder_tree_node(ui, root, label, true, reveal);
+                        }
+
+                        // незмонтовані пристрої монтуються першим клацанням
+                        for drive in self.removable_drives.clone().into_iter().filter(|drive| drive.mount_point.is_none()) {
+                            let drive_button = ui.horizontal(|ui| {
+                                let button = ui.add(egui::Button::image_and_text(
+                                    egui::Image::new(Icon::HardDrive).max_size(vec2(14.0, 14.0)),
+                                    RichText::new(&drive.label).size(14.0).weak(),
+                                ).frame(false));
+
+                                if self.is_drive_busy(&drive) {
+                                    ui.spinner();
+                                }
+
+                                button
+                            }).inner.on_hover_text(format!("{} не змонтовано", drive.device));
+
+                            if drive_button.clone().on_hover_cursor(PointingHand).clicked() {
+                                self.open_drive(ctx, &drive);
+                            }
+
+                            drive_button.context_menu(|ui| {
+                                self.show_drive_actions(ui, &drive);
+
+                                self.is_main_context_menu_open = false;
+                            });
+                        }
+                    });
             });
 
+        self.settings.sidebar_width = Some(left_panel.response.rect.width());
+
+        if self.settings.show_preview {
+            SidePanel::right("preview_panel")
+                .default_width(300.0)
+                .show(ctx, |ui| {
+                    self.show_preview(ui, ctx);
+                });
+        }
+
         egui::CentralPanel::default()
             .frame(egui::Frame {
                 inner_margin: Margin {
@@ -272,259 +5123,37 @@ impl eframe::App for FileExplorerApp {
                 ..Default::default()
             })
             .show(ctx, |ui| {
-
-            egui::ScrollArea::vertical()
-            .id_salt("main_scroll_area")
-            .auto_shrink(false)
-            .max_height(ui.available_height())
-            .show(ui, |ui| {
-                let response = ui.interact(
-                    ui.available_rect_before_wrap(),
-                    ui.id().with("main_scroll_area"),
-                    egui::Sense::click(),
-                );
-
-                if response.clicked_by(egui::PointerButton::Secondary) {
-                    if let Some(pointer_pos) = ctx.input(|i| i.pointer.interact_pos()) {
-                        self.is_main_context_menu_open = true;
-                        self.interact_pointer_pos = pointer_pos;
-                    }
-                }
-
-                if self.is_main_context_menu_open {
-                    egui::Window::new("")
-                        .current_pos(self.interact_pointer_pos)
-                        .movable(false)
-                        .resizable(false)
-                        .collapsible(false)
-                        .title_bar(false)
-                        .show(ctx, |ui| {
-                            if ui.button("Оновити").on_hover_cursor(PointingHand).clicked() {
-                                println!("Оновити");
-                            } else if ui.button("Створити нову папку").on_hover_cursor(PointingHand).clicked() {
-                                let mut dir = self.current_dir.clone();
-                                dir.push("Нова папка");
-
-                                fs::create_dir(dir).expect("Не вдалося створити нову папку");
-                            } else if ui.button("Створити новий файл").on_hover_cursor(PointingHand).clicked() {
-                                let mut file = self.current_dir.clone();
-                                file.push("Новий файл");
-
-                                fs::File::create(file).expect("Не вдалося створити новий файл");
-                            }
-
-                            //ui.close_menu();
-                        });
-                }
-
-                if self.popup_type != PopupType::None {
-                    egui::Window::new(
-                        if self.popup_type == PopupType::DiskInfo {
-                            "Інформація про диск"
-                        } else if self.popup_type == PopupType::Rename {
-                            "Перейменувати"
-                        } else {
-                            "empty popup"
-                        }
-                    )
-                        .default_size(vec2(
-                            window_size.x / 2.0,
-                            window_size.y / 2.0,
-                        ))
-                        .max_size(vec2(
-                            window_size.x - 200.0,
-                            window_size.y - 200.0,
-                        ))
-                        .default_pos(pos2(
-                            window_size.x / 4.0,
-                            window_size.y / 4.0,
-                        ))
-                        //.movable(false)
-                        .resizable(false)
-                        .collapsible(false)
-                        .show(ctx, |ui| {
-                            if self.popup_type == PopupType::DiskInfo {
-                                ui.label("Інформація");
-                            } else if self.popup_type == PopupType::Rename {
-                                ui.horizontal(|ui| {
-                                    let new_name_label = ui.label("Перейменувати: ");
-
-                                    ui.text_edit_singleline(&mut self.rename).labelled_by(new_name_label.id);
-
-                                    if ui.button("Перейменувати").on_hover_cursor(PointingHand).clicked() {
-                                        let mut new_name = self.selected_item.clone();
-
-                                        new_name.pop();
-                                        new_name.push(&self.rename);
-
-                                        fs::rename(&self.selected_item, new_name).expect("Виникла помилка, під час спроби перейменувати файл");
-                                    
-                                        self.popup_type = PopupType::None;
-                                    }
-                                });
-                            } else {}
-
-                            if ui.button("Закрити").on_hover_cursor(PointingHand).clicked() {
-                                self.popup_type = PopupType::None;
-                            }
-                        });
-                }
-
-                if ctx.input(|i| i.pointer.primary_clicked()) {
-                    if self.is_main_context_menu_open {
-                        self.is_main_context_menu_open = false;
-                    }
-                }
-
-                if self.is_disk_selection {
-                    for disk in &self.disk_list {
-                        let disk_button = ui.add(egui::Button::image_and_text(
-                            egui::Image::new(format!("file://{}/{}", self.program_root.to_str().unwrap(), "assets/Font_Awesome_Icons/solid/hard-drive.svg")),
-                            RichText::new(disk.to_string()).size(14.0),
-                        ));
-
-                        disk_button.clone().on_hover_cursor(PointingHand);
-
-                        if disk_button.double_clicked() {
-                            let mut path = PathBuf::from(format!("{}:\\", disk));
-                            
-                            self.current_dir = path;
-                            self.is_disk_selection = false;
-                        }
-
-                        disk_button.context_menu(|ui| {
-                            if ui.button("Інформація про диск").on_hover_cursor(PointingHand).clicked() {
-                                println!("Інформація про диск");
-
-                                self.popup_type = PopupType::DiskInfo;
-                                
-                                ui.close_menu();
-                            }
-
-                            self.is_main_context_menu_open = false;
-                        });
-                    }
+                if self.commander_mode {
+                    ui.columns(2, |columns| {
+                        self.show_pane(&mut columns[0], ctx, Pane::Left);
+                        self.show_pane(&mut columns[1], ctx, Pane::Right);
+                    });
                 } else {
-                    for dir_element in self.current_dir.read_dir().unwrap() {
-                        if let Ok(dir_element) = dir_element {
-                            if dir_element.metadata().unwrap().is_dir() {
-                                let dir_button = ui.add(egui::Button::image_and_text(
-                                    egui::Image::new(format!("file://{}/{}", self.program_root.to_str().unwrap(), "assets/Font_Awesome_Icons/solid/folder.svg")),
-                                    RichText::new(dir_element.file_name().to_str().unwrap()).size(14.0),
-                                ));
-
-                                dir_button.clone().on_hover_cursor(PointingHand);
-
-                                if dir_button.double_clicked() {
-                                    self.open_folder(&dir_element);
-                                }
-
-                                dir_button.context_menu(|ui| {
-                                    if ui.button("Перейменувати").on_hover_cursor(PointingHand).clicked() {
-                                        println!("Перейменувати");
-
-                                        self.popup_type = PopupType::Rename;
-                                        self.selected_item = dir_element.path();
-                                        self.rename = dir_element.file_name().into_string().unwrap();
-                                        
-                                        ui.close_menu();
-                                    }
-                                    if ui.button("Копіювати").on_hover_cursor(PointingHand).clicked() {
-                                        println!("Копіювати");
-                                        ui.close_menu();
-                                    }
-                                    if ui.button("Вирізати").on_hover_cursor(PointingHand).clicked() {
-                                        println!("Вирізати");
-                                        ui.close_menu();
-                                    }
-                                    if ui.button("Видалити").on_hover_cursor(PointingHand).clicked() {
-                                        match remove_dir_all(dir_element.path().to_str().unwrap()) {
-                                            Ok(()) => {
-                                                println!("папка була видалена");
-                                            },
-                                            Err(err) => {
-                                                println!("{:#?}", err);
-                                            }
-                                        }
-                                        ui.close_menu();
-                                    }
-                                    if ui.button("Копіювати шлях до папки").on_hover_cursor(PointingHand).clicked() {
-                                        println!("Копіювати шлях");
-                                        
-                                        ctx.copy_text(self.current_dir.to_string_lossy().to_string());
-
-                                        ui.close_menu();
-                                    }
-                                    if ui.button("Інформація про папку").on_hover_cursor(PointingHand).clicked() {
-                                        println!("Інформація про папку");
-                                        ui.close_menu();
-                                    }
-
-                                    self.is_main_context_menu_open = false;
-                                });
-                            } else if dir_element.metadata().unwrap().is_file() {
-                                let file_button = ui.add(egui::Button::image_and_text(
-                                    egui::Image::new(format!("file://{}/{}", self.program_root.to_str().unwrap(), "assets/Font_Awesome_Icons/solid/file.svg")),
-                                    RichText::new(dir_element.file_name().to_str().unwrap()).size(14.0),
-                                ));
-
-                                file_button.clone().on_hover_cursor(PointingHand);
-
-                                if file_button.double_clicked() {
-                                    self.open_file(&dir_element);
-                                }
-
-                                file_button.context_menu(|ui| {
-                                    if ui.button("Перейменувати").on_hover_cursor(PointingHand).clicked() {
-                                        println!("Перейменувати");
-
-                                        self.popup_type = PopupType::Rename;
-                                        self.selected_item = dir_element.path();
-                                        self.rename = dir_element.file_name().into_string().unwrap();
-                                        
-                                        ui.close_menu();
-                                    }
-                                    if ui.button("Копіювати").on_hover_cursor(PointingHand).clicked() {
-                                        println!("Копіювати");
-                                        ui.close_menu();
-                                    }
-                                    if ui.button("Вирізати").on_hover_cursor(PointingHand).clicked() {
-                                        println!("Вирізати");
-                                        ui.close_menu();
-                                    }
-                                    if ui.button("Видалити").on_hover_cursor(PointingHand).clicked() {
-                                        match remove_file(dir_element.path().to_str().unwrap()) {
-                                            Ok(()) => {
-                                                println!("файл був видалений");
-                                            },
-                                            Err(err) => {
-                                                println!("{:#?}", err);
-                                            }
-                                        }
+                    self.show_pane(ui, ctx, Pane::Left);
+                }
+            });
 
-                                        ui.close_menu();
-                                    }
-                                    if ui.button("Копіювати шлях до файлу").on_hover_cursor(PointingHand).clicked() {
-                                        println!("Копіювати шлях");
+        self.show_popups(ctx, window_size);
 
-                                        ctx.copy_text(dir_element.path().to_string_lossy().to_string());
+        self.show_quick_look(ctx, window_size);
+        self.show_disk_usage(ctx, window_size);
+        self.show_duplicates(ctx, window_size);
+        self.show_compare(ctx, window_size);
+        self.show_diff(ctx, window_size);
+        self.show_compress(ctx);
+        self.show_extract(ctx);
+        self.show_connect(ctx);
+        self.show_profile_dialog(ctx);
+        self.show_transfers(ctx);
 
-                                        ui.close_menu();
-                                    }
-                                    if ui.button("Інформація про файл").on_hover_cursor(PointingHand).clicked() {
-                                        println!("Інформація про файл");
-                                        ui.close_menu();
-                                    }
+        for failure in self.launcher.failures() {
+            self.toasts.push(format!("Не вдалося запустити {}", failure.program), failure.message);
+        }
 
-                                    self.is_main_context_menu_open = false;
-                                });
-                            }
-                        } else {
+        self.toasts.show(ctx);
+    }
 
-                        }
-                    }
-                }
-            });
-        });
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.save_settings();
     }
 }
\ No newline at end of file
@@ -0,0 +1,183 @@
+use crate::apps::key_file::{parse_key_file, split_list};
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::Command
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DesktopEntry {
+    pub id: String,
+    pub name: String,
+    pub exec: String,
+    pub mime_types: Vec<String>,
+    pub terminal: bool,
+    pub no_display: bool,
+    pub path: PathBuf,
+}
+
+impl DesktopEntry {
+    pub fn parse(id: &str, path: &Path, content: &str) -> Option<DesktopEntry> {
+        let (_, entries) = parse_key_file(content)
+            .into_iter()
+            .find(|(group, _)| group == "Desktop Entry")?;
+
+        let value = |key: &str| entries.iter().find(|(name, _)| name == key).map(|(_, value)| value.as_str());
+
+        if value("Type") != Some("Application") || value("Hidden") == Some("true") {
+            return None;
+        }
+
+        Some(DesktopEntry {
+            id: id.to_string(),
+            name: value("Name[uk]").or(value("Name")).unwrap_or(id).to_string(),
+            exec: value("Exec")?.to_string(),
+            mime_types: value("MimeType").map(split_list).unwrap_or_default(),
+            terminal: value("Terminal") == Some("true"),
+            no_display: value("NoDisplay") == Some("true"),
+            path: path.to_path_buf(),
+        })
+    }
+
+    // розгортає коди полів Exec (%f, %F, %u, %U, ...) для переданих файлів
+    pub fn command_line(&self, files: &[&Path]) -> Vec<String> {
+        let mut args = Vec::new();
+        let mut files_used = false;
+
+        for token in split_exec(&self.exec) {
+            match token.as_str() {
+                "%f" | "%u" => {
+                    args.extend(files.first().map(|file| file.to_string_lossy().to_string()));
+                    files_used = true;
+                },
+                "%F" | "%U" => {
+                    args.extend(files.iter().map(|file| file.to_string_lossy().to_string()));
+                    files_used = true;
+                },
+                "%i" | "%d" | "%D" | "%n" | "%N" | "%v" | "%m" => {},
+                _ => {
+                    let mut arg = String::new();
+                    let mut chars = token.chars();
+
+                    while let Some(c) = chars.next() {
+                        if c != '%' {
+                            arg.push(c);
+                            continue;
+                        }
+
+                        match chars.next() {
+                            Some('%') => arg.push('%'),
+                            Some('c') => arg.push_str(&self.name),
+                            Some('k') => arg.push_str(&self.path.to_string_lossy()),
+                            _ => {},
+                        }
+                    }
+
+                    args.push(arg);
+                }
+            }
+        }
+
+        // програма без кодів полів все одно має отримати файл
+        if !files_used {
+            args.extend(files.iter().map(|file| file.to_string_lossy().to_string()));
+        }
+
+        if self.terminal {
+            let terminal = env::var("TERMINAL").unwrap_or_else(|_| "x-terminal-emulator".to_string());
+            args.splice(0..0, [terminal, "-e".to_string()]);
+        }
+
+        args
+    }
+
+    pub fn command(&self, files: &[&Path]) -> Option<Command> {
+        let args = self.command_line(files);
+        let (program, args) = args.split_first()?;
+
+        let mut command = Command::new(program);
+        command.args(args);
+
+        Some(command)
+    }
+}
+
+// лапки в Exec працюють як у shell: "a b" — один аргумент, \" — екранування
+fn split_exec(exec: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut in_quotes = false;
+    let mut has_token = false;
+    let mut chars = exec.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_token = true;
+            },
+            '\\' if in_quotes => token.extend(chars.next()),
+            c if c.is_whitespace() && !in_quotes => {
+                if has_token {
+                    tokens.push(std::mem::take(&mut token));
+                    has_token = false;
+                }
+            },
+            c => {
+                token.push(c);
+                has_token = true;
+            }
+        }
+    }
+
+    if has_token {
+        tokens.push(token);
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(exec: &str) -> DesktopEntry {
+        DesktopEntry {
+            id: "editor.desktop".to_string(),
+            name: "Редактор".to_string(),
+            exec: exec.to_string(),
+            mime_types: Vec::new(),
+            terminal: false,
+            no_display: false,
+            path: PathBuf::from("/usr/share/applications/editor.desktop"),
+        }
+    }
+
+    #[test]
+    fn split_exec_handles_quotes_and_escapes() {
+        assert_eq!(split_exec("editor  --new-window %F"), ["editor", "--new-window", "%F"]);
+        assert_eq!(split_exec(r#""/opt/My App/run" "a b" """#), ["/opt/My App/run", "a b", ""]);
+        assert_eq!(split_exec(r#"sh -c "echo \"hi\" \\$HOME""#), ["sh", "-c", r#"echo "hi" \$HOME"#]);
+    }
+
+    #[test]
+    fn field_codes_expand_to_files() {
+        let files = [Path::new("/tmp/a b.txt"), Path::new("/tmp/c.txt")];
+
+        assert_eq!(entry("editor %f").command_line(&files), ["editor", "/tmp/a b.txt"]);
+        assert_eq!(entry("editor %U --x").command_line(&files), ["editor", "/tmp/a b.txt", "/tmp/c.txt", "--x"]);
+        assert_eq!(entry("editor %i --name=%c 100%%").command_line(&files[..1]), ["editor", "--name=Редактор", "100%", "/tmp/a b.txt"]);
+    }
+
+    #[test]
+    fn parse_reads_application_entries_only() {
+        let content = "[Desktop Entry]\nType=Application\nName=Editor\nName[uk]=Редактор\nExec=editor %F\nMimeType=text/plain;text/x-c;\n";
+        let parsed = DesktopEntry::parse("editor.desktop", Path::new("/a/editor.desktop"), content).unwrap();
+
+        assert_eq!(parsed.name, "Редактор");
+        assert_eq!(parsed.mime_types, ["text/plain", "text/x-c"]);
+        assert!(DesktopEntry::parse("link.desktop", Path::new("/a/link.desktop"), "[Desktop Entry]\nType=Link\nExec=x\n").is_none());
+        assert!(DesktopEntry::parse("hidden.desktop", Path::new("/a/h.desktop"), "[Desktop Entry]\nType=Application\nHidden=true\nExec=x\n").is_none());
+    }
+}
@@ -0,0 +1,45 @@
+// формат .desktop і mimeapps.list: [група], далі рядки ключ=значення
+pub fn parse_key_file(content: &str) -> Vec<(String, Vec<(String, String)>)> {
+    let mut groups: Vec<(String, Vec<(String, String)>)> = Vec::new();
+
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(group) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+            groups.push((group.to_string(), Vec::new()));
+        } else if let Some((key, value)) = line.split_once('=')
+            && let Some((_, entries)) = groups.last_mut()
+        {
+            entries.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    groups
+}
+
+// списки в значеннях розділені ';', останній елемент зазвичай порожній
+pub fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(';')
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_keys_and_comments() {
+        let groups = parse_key_file("# comment\nkey=ignored\n[Default Applications]\ntext/plain = editor.desktop;\n\n[Added Associations]\nimage/png=viewer.desktop;\n");
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, "Default Applications");
+        assert_eq!(groups[0].1, [("text/plain".to_string(), "editor.desktop;".to_string())]);
+        assert_eq!(split_list(&groups[1].1[0].1), ["viewer.desktop"]);
+        assert_eq!(split_list("a;;b"), ["a", "b"]);
+    }
+}
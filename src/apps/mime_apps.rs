@@ -0,0 +1,197 @@
+use crate::apps::{
+    desktop_entry::DesktopEntry,
+    key_file::{parse_key_file, split_list}
+};
+use crate::mime::database;
+use crate::xdg::{config_dirs, config_home, data_dirs};
+
+use std::{
+    collections::HashMap,
+    fs,
+    io,
+    path::{Path, PathBuf}
+};
+
+#[derive(Debug, Default)]
+pub struct MimeApps {
+    defaults: HashMap<String, Vec<String>>,
+    added: HashMap<String, Vec<String>>,
+    removed: HashMap<String, Vec<String>>,
+}
+
+// порядок пріоритету зі специфікації mime-apps: спершу конфігурація користувача
+fn mimeapps_files() -> Vec<PathBuf> {
+    config_dirs()
+        .into_iter()
+        .map(|dir| dir.join("mimeapps.list"))
+        .chain(data_dirs().into_iter().map(|dir| dir.join("applications/mimeapps.list")))
+        .collect()
+}
+
+pub fn load_mime_apps() -> MimeApps {
+    let mut mime_apps = MimeApps::default();
+
+    for file in mimeapps_files() {
+        let Ok(content) = fs::read_to_string(&file) else {
+            continue;
+        };
+
+        for (group, entries) in parse_key_file(&content) {
+            let map = match group.as_str() {
+                "Default Applications" => &mut mime_apps.defaults,
+                "Added Associations" => &mut mime_apps.added,
+                "Removed Associations" => &mut mime_apps.removed,
+                _ => continue,
+            };
+
+            for (mime, ids) in entries {
+                map.entry(mime).or_default().extend(split_list(&ids));
+            }
+        }
+    }
+
+    mime_apps
+}
+
+// id програми — шлях відносно папки applications, де '/' замінено на '-'
+pub fn applications() -> Vec<DesktopEntry> {
+    let mut entries: Vec<DesktopEntry> = Vec::new();
+
+    for dir in data_dirs() {
+        let applications_dir = dir.join("applications");
+        collect_entries(&applications_dir, &applications_dir, &mut entries);
+    }
+
+    entries.sort_by_key(|entry| entry.name.to_lowercase());
+    entries
+}
+
+fn collect_entries(root: &Path, dir: &Path, entries: &mut Vec<DesktopEntry>) {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for dir_entry in read_dir.filter_map(|dir_entry| dir_entry.ok()) {
+        let path = dir_entry.path();
+
+        if path.is_dir() {
+            collect_entries(root, &path, entries);
+            continue;
+        }
+
+        if path.extension().is_none_or(|extension| extension != "desktop") {
+            continue;
+        }
+
+        let Ok(relative) = path.strip_prefix(root) else {
+            continue;
+        };
+
+        let id = relative.to_string_lossy().replace('/', "-");
+
+        // однаковий id у папці з вищим пріоритетом перекриває інші
+        if entries.iter().any(|entry| entry.id == id) {
+            continue;
+        }
+
+        if let Ok(content) = fs::read_to_string(&path)
+            && let Some(entry) = DesktopEntry::parse(&id, &path, &content)
+        {
+            entries.push(entry);
+        }
+    }
+}
+
+// програми для типу та його батьківських типів, типова програма йде першою
+pub fn apps_for_mime(mime: &str, applications: &[DesktopEntry]) -> Vec<DesktopEntry> {
+    let mime_apps = load_mime_apps();
+    let mut ids: Vec<String> = Vec::new();
+
+    let mut mimes = vec![mime.to_string()];
+    let mut index = 0;
+
+    while index < mimes.len() && index < 16 {
+        for parent in database().parents(&mimes[index]) {
+            if parent != "application/octet-stream" && !mimes.contains(&parent) {
+                mimes.push(parent);
+            }
+        }
+
+        index += 1;
+    }
+
+    for mime in &mimes {
+        let removed = mime_apps.removed.get(mime).cloned().unwrap_or_default();
+
+        let associated = applications
+            .iter()
+            .filter(|entry| entry.mime_types.contains(mime))
+            .map(|entry| entry.id.clone());
+
+        let candidates = mime_apps.defaults.get(mime).into_iter().flatten().cloned()
+            .chain(mime_apps.added.get(mime).into_iter().flatten().cloned())
+            .chain(associated);
+
+        for id in candidates {
+            if !removed.contains(&id) && !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+    }
+
+    ids.iter()
+        .filter_map(|id| applications.iter().find(|entry| &entry.id == id).cloned())
+        .collect()
+}
+
+pub fn default_app(mime: &str, applications: &[DesktopEntry]) -> Option<DesktopEntry> {
+    let mime_apps = load_mime_apps();
+
+    mime_apps.defaults
+        .get(mime)?
+        .iter()
+        .find_map(|id| applications.iter().find(|entry| &entry.id == id).cloned())
+}
+
+// записує програму в [Default Applications] файлу ~/.config/mimeapps.list
+pub fn set_default_app(mime: &str, id: &str) -> io::Result<()> {
+    let path = config_home().join("mimeapps.list");
+
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err),
+    };
+
+    let line = format!("{}={};", mime, id);
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+
+    match lines.iter().position(|line| line.trim() == "[Default Applications]") {
+        Some(group_start) => {
+            let group_end = lines[group_start + 1..]
+                .iter()
+                .position(|line| line.trim_start().starts_with('['))
+                .map_or(lines.len(), |offset| group_start + 1 + offset);
+
+            let existing = lines[group_start + 1..group_end]
+                .iter()
+                .position(|line| line.split_once('=').is_some_and(|(key, _)| key.trim() == mime));
+
+            match existing {
+                Some(offset) => lines[group_start + 1 + offset] = line,
+                None => lines.insert(group_start + 1, line),
+            }
+        },
+        None => {
+            if lines.last().is_some_and(|last| !last.is_empty()) {
+                lines.push(String::new());
+            }
+
+            lines.push("[Default Applications]".to_string());
+            lines.push(line);
+        }
+    }
+
+    fs::create_dir_all(config_home())?;
+    fs::write(path, lines.join("\n") + "\n")
+}
@@ -0,0 +1,3 @@
+pub mod desktop_entry;
+pub mod key_file;
+pub mod mime_apps;
@@ -0,0 +1,215 @@
+use super::reader::{for_each_entry, write_entry};
+use crate::file_ops::create::free_path;
+use crate::jobs::job::{BatchResult, JobContext};
+
+use std::{
+    fs,
+    io,
+    io::ErrorKind,
+    path::Path
+};
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum ConflictPolicy {
+    Skip,
+    Overwrite,
+    KeepBoth
+}
+
+impl ConflictPolicy {
+    pub const ALL: [ConflictPolicy; 3] = [ConflictPolicy::Skip, ConflictPolicy::Overwrite, ConflictPolicy::KeepBoth];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ConflictPolicy::Skip => "Пропустити наявні",
+            ConflictPolicy::Overwrite => "Замінити наявні",
+            ConflictPolicy::KeepBoth => "Зберегти обидва",
+        }
+    }
+}
+
+// витягує весь архів у target_dir; прогрес — кількість оброблених записів
+pub fn extract_archive(archive: &Path, target_dir: &Path, conflict: ConflictPolicy, job: &mut JobContext<u64>) -> BatchResult {
+    let mut result = BatchResult::default();
+    let mut processed = 0;
+
+    if let Err(err) = fs::create_dir_all(target_dir) {
+        result.errors.push((target_dir.to_path_buf(), err.to_string()));
+        return result;
+    }
+
+    let walk = for_each_entry(archive, |entry, data| {
+        if job.is_cancelled() {
+            result.cancelled = true;
+            return Err(io::Error::from(ErrorKind::Interrupted));
+        }
+
+        processed += 1;
+        job.report(processed);
+
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(raw_path) => {
+                result.errors.push((raw_path, "небезпечний шлях у архіві, запис пропущено".to_string()));
+                return Ok(());
+            }
+        };
+
+        let mut output = target_dir.join(&entry.path);
+
+        // папки зливаються з наявними, конфлікт можливий лише для файлів
+        if !entry.is_dir && fs::symlink_metadata(&output).is_ok() {
+            match conflict {
+                ConflictPolicy::Skip => return Ok(()),
+                ConflictPolicy::KeepBoth => output = free_path(&output),
+                ConflictPolicy::Overwrite => {
+                    if let Err(err) = fs::remove_file(&output) {
+                        result.errors.push((output, err.to_string()));
+                        return Ok(());
+                    }
+                },
+            }
+        }
+
+        match write_entry(&entry, data, &output) {
+            Ok(()) if !entry.is_dir => result.done += 1,
+            Ok(()) => {},
+            Err(err) => result.errors.push((output, err.to_string())),
+        }
+
+        Ok(())
+    });
+
+    if let Err(err) = walk
+        && !result.cancelled
+    {
+        result.errors.push((archive.to_path_buf(), err.to_string()));
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jobs::job::Job;
+
+    use eframe::egui;
+
+    use std::{
+        env,
+        fs::File,
+        io::Write,
+        path::PathBuf,
+        thread,
+        time::Duration
+    };
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("file_explorer_extract_{}_{}", name, std::process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    fn extract(archive: &Path, target_dir: &Path, conflict: ConflictPolicy) -> BatchResult {
+        let (archive, target_dir) = (archive.to_path_buf(), target_dir.to_path_buf());
+        let mut job = Job::spawn(&egui::Context::default(), move |job| extract_archive(&archive, &target_dir, conflict, job));
+
+        while !job.is_finished() {
+            thread::sleep(Duration::from_millis(10));
+            job.poll();
+        }
+
+        job.result.unwrap()
+    }
+
+    // tar::Builder сам не пропускає "..", тому назву записуємо прямо в заголовок
+    fn append_raw(builder: &mut tar::Builder<File>, name: &str, data: &[u8]) {
+        let mut header = tar::Header::new_old();
+        header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+
+        builder.append(&header, data).unwrap();
+    }
+
+    #[test]
+    fn tar_entries_outside_target_are_rejected() {
+        let dir = temp_dir("tar");
+        let archive = dir.join("evil.tar");
+
+        let mut builder = tar::Builder::new(File::create(&archive).unwrap());
+        append_raw(&mut builder, "../escaped.txt", b"evil");
+        append_raw(&mut builder, "inner/../../escaped2.txt", b"evil");
+        append_raw(&mut builder, "good.txt", b"good");
+        builder.into_inner().unwrap();
+
+        let target = dir.join("out");
+        let result = extract(&archive, &target, ConflictPolicy::Skip);
+
+        assert_eq!(result.done, 1);
+        assert_eq!(result.errors.len(), 2);
+        assert_eq!(result.errors[0].0, PathBuf::from("../escaped.txt"));
+        assert_eq!(fs::read_to_string(target.join("good.txt")).unwrap(), "good");
+        assert!(!dir.join("escaped.txt").exists());
+        assert!(!dir.join("escaped2.txt").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn zip_entries_outside_target_are_rejected() {
+        let dir = temp_dir("zip");
+        let archive = dir.join("evil.zip");
+
+        let mut zip = zip::ZipWriter::new(File::create(&archive).unwrap());
+
+        for (name, data) in [("../escaped.txt", "evil"), ("/absolute.txt", "evil"), ("good.txt", "good")] {
+            zip.start_file(name, zip::write::SimpleFileOptions::default()).unwrap();
+            zip.write_all(data.as_bytes()).unwrap();
+        }
+
+        zip.finish().unwrap();
+
+        let target = dir.join("out");
+        let result = extract(&archive, &target, ConflictPolicy::Skip);
+
+        assert_eq!(result.done, 1);
+        assert_eq!(result.errors.len(), 2);
+        assert_eq!(fs::read_dir(&target).unwrap().count(), 1);
+        assert!(!dir.join("escaped.txt").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn conflicts_follow_the_policy() {
+        let dir = temp_dir("conflict");
+        let archive = dir.join("a.tar");
+
+        let mut builder = tar::Builder::new(File::create(&archive).unwrap());
+        append_raw(&mut builder, "a.txt", b"new");
+        builder.into_inner().unwrap();
+
+        let target = dir.join("out");
+        fs::create_dir(&target).unwrap();
+        fs::write(target.join("a.txt"), "old").unwrap();
+
+        assert_eq!(extract(&archive, &target, ConflictPolicy::Skip).done, 0);
+        assert_eq!(fs::read_to_string(target.join("a.txt")).unwrap(), "old");
+
+        assert_eq!(extract(&archive, &target, ConflictPolicy::KeepBoth).done, 1);
+        assert_eq!(fs::read_to_string(target.join("a.txt")).unwrap(), "old");
+        assert_eq!(fs::read_dir(&target).unwrap().count(), 2);
+
+        assert_eq!(extract(&archive, &target, ConflictPolicy::Overwrite).done, 1);
+        assert_eq!(fs::read_to_string(target.join("a.txt")).unwrap(), "new");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,77 @@
+use std::{
+    ops::RangeInclusive,
+    path::Path
+};
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+    TarXz,
+    TarZst
+}
+
+impl ArchiveFormat {
+    pub const ALL: [ArchiveFormat; 5] = [
+        ArchiveFormat::Zip,
+        ArchiveFormat::Tar,
+        ArchiveFormat::TarGz,
+        ArchiveFormat::TarXz,
+        ArchiveFormat::TarZst,
+    ];
+
+    // перше розширення — основне, решта — скорочені варіанти
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            ArchiveFormat::Zip => &[".zip"],
+            ArchiveFormat::Tar => &[".tar"],
+            ArchiveFormat::TarGz => &[".tar.gz", ".tgz"],
+            ArchiveFormat::TarXz => &[".tar.xz", ".txz"],
+            ArchiveFormat::TarZst => &[".tar.zst", ".tzst"],
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        self.extensions()[0].trim_start_matches('.')
+    }
+
+    // None — формат без стиснення
+    pub fn level_range(self) -> Option<RangeInclusive<u32>> {
+        match self {
+            ArchiveFormat::Zip | ArchiveFormat::TarGz | ArchiveFormat::TarXz => Some(0..=9),
+            ArchiveFormat::TarZst => Some(1..=19),
+            ArchiveFormat::Tar => None,
+        }
+    }
+
+    pub fn default_level(self) -> u32 {
+        match self {
+            ArchiveFormat::TarZst => 3,
+            ArchiveFormat::Tar => 0,
+            _ => 6,
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<ArchiveFormat> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+
+        ArchiveFormat::ALL
+            .into_iter()
+            .find(|format| format.extensions().iter().any(|extension| name.ends_with(extension) && name.len() > extension.len()))
+    }
+}
+
+// назва архіву без розширення: "проєкт.tar.gz" -> "проєкт"
+pub fn archive_stem(path: &Path) -> String {
+    let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+
+    match ArchiveFormat::from_path(path) {
+        Some(format) => {
+            let extension = format.extensions().iter().find(|extension| name.to_lowercase().ends_with(*extension)).map_or(0, |extension| extension.len());
+
+            name[..name.len() - extension].to_string()
+        },
+        None => name,
+    }
+}
@@ -0,0 +1,104 @@
+pub mod extract;
+pub mod format;
+pub mod reader;
+pub mod writer;
+
+use crate::jobs::job::{BatchResult, JobContext};
+use crate::vfs::transfer::TransferProgress;
+
+use format::ArchiveFormat;
+use reader::extract_entries;
+
+use std::path::{Path, PathBuf};
+
+// шлях на кшталт /home/user/a.zip/docs ділиться на сам архів і папку всередині нього
+pub fn split_archive_path(path: &Path) -> Option<(PathBuf, PathBuf)> {
+    path.ancestors()
+        .find(|ancestor| ArchiveFormat::from_path(ancestor).is_some() && ancestor.is_file())
+        .map(|archive| (archive.to_path_buf(), path.strip_prefix(archive).unwrap_or(Path::new("")).to_path_buf()))
+}
+
+// аналог transfer_local для елементів усередині архівів: записи одного архіву витягуються за один прохід,
+// бо стиснений tar доводиться розпаковувати з початку
+pub fn extract_into(sources: &[PathBuf], dst_dir: &Path, job: &mut JobContext<TransferProgress>) -> BatchResult {
+    let mut result = BatchResult::default();
+    let mut groups: Vec<(PathBuf, Vec<PathBuf>)> = Vec::new();
+
+    for source in sources {
+        match split_archive_path(source).filter(|(_, inner)| !inner.as_os_str().is_empty()) {
+            Some((archive, inner)) => match groups.iter_mut().find(|(known, _)| *known == archive) {
+                Some((_, inners)) => inners.push(inner),
+                None => groups.push((archive, vec![inner])),
+            },
+            None => result.errors.push((source.clone(), "не є елементом архіву".to_string())),
+        }
+    }
+
+    for (archive, inners) in groups {
+        if job.is_cancelled() {
+            result.cancelled = true;
+            break;
+        }
+
+        match extract_entries(&archive, &inners, dst_dir) {
+            Ok(targets) => result.done += targets.len() as u64,
+            Err(err) => result.errors.push((archive, err.to_string())),
+        }
+
+        job.report(TransferProgress { files: result.done, bytes: 0 });
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jobs::job::Job;
+
+    use eframe::egui;
+
+    use std::{env, fs, fs::File, thread, time::Duration};
+
+    #[test]
+    fn extract_into_reads_each_archive_once() {
+        let dir = env::temp_dir().join(format!("file_explorer_archive_into_{}", std::process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("out")).unwrap();
+
+        let archive = dir.join("test.tar");
+        let mut builder = tar::Builder::new(File::create(&archive).unwrap());
+
+        for (name, data) in [("docs/a.txt", "a"), ("b.txt", "bb")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+
+            builder.append_data(&mut header, name, data.as_bytes()).unwrap();
+        }
+
+        builder.finish().unwrap();
+        drop(builder);
+
+        let sources = vec![archive.join("docs"), archive.join("b.txt"), dir.join("plain")];
+        let out = dir.join("out");
+        let mut job = Job::spawn(&egui::Context::default(), move |job| extract_into(&sources, &out, job));
+
+        while !job.is_finished() {
+            thread::sleep(Duration::from_millis(10));
+            job.poll();
+        }
+
+        let result = job.result.unwrap();
+
+        assert_eq!(result.done, 2);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(fs::read_to_string(dir.join("out/docs/a.txt")).unwrap(), "a");
+        assert_eq!(fs::read_to_string(dir.join("out/b.txt")).unwrap(), "bb");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
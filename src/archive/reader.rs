@@ -0,0 +1,299 @@
+use super::format::ArchiveFormat;
+
+use chrono::{Local, NaiveDate};
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    fs::File,
+    io,
+    io::{BufReader, ErrorKind, Read},
+    path::{Component, Path, PathBuf},
+    time::{Duration, SystemTime}
+};
+
+#[derive(Debug, Clone)]
+pub struct ArchiveEntry {
+    // шлях усередині архіву, без початкового "/"
+    pub path: PathBuf,
+    pub is_dir: bool,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+// лишаємо тільки звичайні складові шляху: "../" та абсолютні шляхи можуть вийти за межі папки
+pub fn safe_path(path: &Path) -> Option<PathBuf> {
+    let mut safe = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => safe.push(part),
+            Component::CurDir => {},
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+
+    (!safe.as_os_str().is_empty()).then_some(safe)
+}
+
+fn unsupported(archive: &Path) -> io::Error {
+    io::Error::new(ErrorKind::Unsupported, format!("{} — непідтримуваний формат архіву", archive.display()))
+}
+
+fn open_tar(archive: &Path, format: ArchiveFormat) -> io::Result<tar::Archive<Box<dyn Read>>> {
+    let file = BufReader::new(File::open(archive)?);
+
+    let reader: Box<dyn Read> = match format {
+        ArchiveFormat::Tar => Box::new(file),
+        ArchiveFormat::TarGz => Box::new(flate2::read::GzDecoder::new(file)),
+        ArchiveFormat::TarXz => Box::new(xz2::read::XzDecoder::new(file)),
+        ArchiveFormat::TarZst => Box::new(zstd::stream::read::Decoder::with_buffer(file)?),
+        ArchiveFormat::Zip => return Err(unsupported(archive)),
+    };
+
+    Ok(tar::Archive::new(reader))
+}
+
+fn zip_time(time: zip::DateTime) -> Option<SystemTime> {
+    let local = NaiveDate::from_ymd_opt(time.year().into(), time.month().into(), time.day().into())?
+        .and_hms_opt(time.hour().into(), time.minute().into(), time.second().into())?
+        .and_local_timezone(Local)
+        .single()?;
+
+    Some(local.into())
+}
+
+// обходить записи архіву по черзі; вміст запису можна прочитати лише всередині visit.
+// запис з небезпечним шляхом передається як Err з його початковою назвою
+pub fn for_each_entry(
+    archive: &Path,
+    mut visit: impl FnMut(Result<ArchiveEntry, PathBuf>, &mut dyn Read) -> io::Result<()>,
+) -> io::Result<()> {
+    let format = ArchiveFormat::from_path(archive).ok_or_else(|| unsupported(archive))?;
+
+    if format == ArchiveFormat::Zip {
+        let mut zip = zip::ZipArchive::new(BufReader::new(File::open(archive)?))?;
+
+        for index in 0..zip.len() {
+            let mut file = zip.by_index(index)?;
+
+            // посилання можуть вказувати за межі папки, куди витягується архів
+            if file.is_symlink() {
+                continue;
+            }
+
+            let Some(path) = safe_path(Path::new(file.name())) else {
+                visit(Err(PathBuf::from(file.name())), &mut file)?;
+                continue;
+            };
+
+            let entry = ArchiveEntry {
+                path,
+                is_dir: file.is_dir(),
+                size: file.size(),
+                modified: file.last_modified().and_then(zip_time),
+            };
+
+            visit(Ok(entry), &mut file)?;
+        }
+
+        return Ok(());
+    }
+
+    let mut tar = open_tar(archive, format)?;
+
+    for file in tar.entries()? {
+        let mut file = file?;
+        let entry_type = file.header().entry_type();
+
+        if !entry_type.is_file() && !entry_type.is_dir() {
+            continue;
+        }
+
+        let Some(path) = safe_path(&file.path()?) else {
+            let raw_path = file.path()?.to_path_buf();
+
+            visit(Err(raw_path), &mut file)?;
+            continue;
+        };
+
+        let header = file.header();
+
+        let entry = ArchiveEntry {
+            path,
+            is_dir: entry_type.is_dir(),
+            size: header.size().unwrap_or(0),
+            modified: header.mtime().ok().map(|mtime| SystemTime::UNIX_EPOCH + Duration::from_secs(mtime)),
+        };
+
+        visit(Ok(entry), &mut file)?;
+    }
+
+    Ok(())
+}
+
+// tar часто не містить окремих записів для папок, тому батьківські папки додаємо самі
+pub fn list_entries(archive: &Path) -> io::Result<Vec<ArchiveEntry>> {
+    let mut entries: BTreeMap<PathBuf, ArchiveEntry> = BTreeMap::new();
+
+    for_each_entry(archive, |entry, _| {
+        let Ok(entry) = entry else {
+            return Ok(());
+        };
+
+        for parent in entry.path.ancestors().skip(1).filter(|parent| !parent.as_os_str().is_empty()) {
+            entries.entry(parent.to_path_buf()).or_insert_with(|| ArchiveEntry {
+                path: parent.to_path_buf(),
+                is_dir: true,
+                size: 0,
+                modified: None,
+            });
+        }
+
+        entries.insert(entry.path.clone(), entry);
+
+        Ok(())
+    })?;
+
+    Ok(entries.into_values().collect())
+}
+
+// безпосередні нащадки папки inner ("" — корінь архіву)
+pub fn children<'a>(entries: &'a [ArchiveEntry], inner: &Path) -> Vec<&'a ArchiveEntry> {
+    entries
+        .iter()
+        .filter(|entry| entry.path.parent() == Some(inner))
+        .collect()
+}
+
+// витягує вибрані записи (папки — разом із вмістом) у target_dir, не перезаписуючи наявні елементи
+pub fn extract_entries(archive: &Path, selected: &[PathBuf], target_dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut targets = Vec::new();
+
+    for inner in selected {
+        let target = target_dir.join(inner.file_name().unwrap_or(inner.as_os_str()));
+
+        if fs::symlink_metadata(&target).is_ok() {
+            return Err(io::Error::new(ErrorKind::AlreadyExists, format!("{} вже існує", target.display())));
+        }
+
+        targets.push(target);
+    }
+
+    for_each_entry(archive, |entry, data| {
+        let Ok(entry) = entry else {
+            return Ok(());
+        };
+
+        let Some((inner, target)) = selected.iter().zip(&targets).find(|(inner, _)| entry.path.starts_with(inner)) else {
+            return Ok(());
+        };
+
+        // join("") додав би завершальний "/", і файл не створився б
+        let output = match entry.path.strip_prefix(inner) {
+            Ok(relative) if !relative.as_os_str().is_empty() => target.join(relative),
+            _ => target.clone(),
+        };
+
+        write_entry(&entry, data, &output)
+    })?;
+
+    Ok(targets)
+}
+
+pub fn write_entry(entry: &ArchiveEntry, data: &mut dyn Read, output: &Path) -> io::Result<()> {
+    if entry.is_dir {
+        return fs::create_dir_all(output);
+    }
+
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = File::create(output)?;
+    io::copy(data, &mut file)?;
+
+    if let Some(modified) = entry.modified {
+        file.set_modified(modified)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("file_explorer_archive_{}_{}", name, std::process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn safe_path_rejects_traversal_and_absolute_paths() {
+        assert_eq!(safe_path(Path::new("docs/a.txt")), Some(PathBuf::from("docs/a.txt")));
+        assert_eq!(safe_path(Path::new("./docs/./a.txt")), Some(PathBuf::from("docs/a.txt")));
+        assert_eq!(safe_path(Path::new("../a.txt")), None);
+        assert_eq!(safe_path(Path::new("docs/../../a.txt")), None);
+        assert_eq!(safe_path(Path::new("docs/..")), None);
+        assert_eq!(safe_path(Path::new("/etc/passwd")), None);
+        assert_eq!(safe_path(Path::new("")), None);
+        assert_eq!(safe_path(Path::new(".")), None);
+    }
+
+    #[test]
+    fn list_entries_adds_missing_parent_folders() {
+        let dir = temp_dir("list");
+        let archive = dir.join("test.tar");
+
+        let mut builder = tar::Builder::new(File::create(&archive).unwrap());
+
+        for (name, data) in [("docs/inner/a.txt", "a"), ("b.txt", "bb")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(1_700_000_000);
+            header.set_cksum();
+
+            builder.append_data(&mut header, name, data.as_bytes()).unwrap();
+        }
+
+        builder.finish().unwrap();
+        drop(builder);
+
+        let entries = list_entries(&archive).unwrap();
+        let paths: Vec<_> = entries.iter().map(|entry| (entry.path.to_string_lossy().to_string(), entry.is_dir)).collect();
+
+        assert_eq!(paths, [
+            ("b.txt".to_string(), false),
+            ("docs".to_string(), true),
+            ("docs/inner".to_string(), true),
+            ("docs/inner/a.txt".to_string(), false),
+        ]);
+        assert_eq!(entries[0].size, 2);
+        assert_eq!(entries[0].modified, Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)));
+
+        let root: Vec<_> = children(&entries, Path::new("")).iter().map(|entry| entry.path.clone()).collect();
+        assert_eq!(root, [PathBuf::from("b.txt"), PathBuf::from("docs")]);
+        assert_eq!(children(&entries, Path::new("docs")).len(), 1);
+
+        let targets = extract_entries(&archive, &[PathBuf::from("docs")], &dir).unwrap();
+        assert_eq!(targets, [dir.join("docs")]);
+        assert_eq!(fs::read_to_string(dir.join("docs/inner/a.txt")).unwrap(), "a");
+        assert_eq!(extract_entries(&archive, &[PathBuf::from("docs")], &dir).unwrap_err().kind(), ErrorKind::AlreadyExists);
+
+        assert_eq!(
+            crate::archive::split_archive_path(&archive.join("docs/inner")),
+            Some((archive.clone(), PathBuf::from("docs/inner")))
+        );
+        assert_eq!(crate::archive::split_archive_path(&dir.join("docs/inner")), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,295 @@
+use super::format::ArchiveFormat;
+use crate::jobs::job::{BatchResult, JobContext};
+use crate::listing::pattern::matches_pattern;
+
+use chrono::{DateTime, Datelike, Local, Timelike};
+use zip::{write::SimpleFileOptions, CompressionMethod};
+
+use std::{
+    fs,
+    fs::File,
+    io,
+    io::{BufWriter, Seek, Write},
+    path::{Path, PathBuf},
+    time::SystemTime
+};
+
+#[derive(Debug, Clone)]
+pub struct CompressOptions {
+    pub format: ArchiveFormat,
+    pub level: u32,
+    pub exclude: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompressProgress {
+    pub done: u64,
+    pub total: u64,
+}
+
+// шаблони виключення розділяються комами: "*.log, target, .git"
+pub fn parse_patterns(text: &str) -> Vec<String> {
+    text.split([',', ';'])
+        .map(|pattern| pattern.trim().to_string())
+        .filter(|pattern| !pattern.is_empty())
+        .collect()
+}
+
+// шаблон порівнюється і з назвою елемента, і з його шляхом в архіві
+fn is_excluded(name_in_archive: &Path, patterns: &[String]) -> bool {
+    let name: Vec<char> = name_in_archive.file_name().unwrap_or_default().to_string_lossy().chars().collect();
+    let relative: Vec<char> = name_in_archive.to_string_lossy().replace('\\', "/").chars().collect();
+
+    patterns.iter().any(|pattern| {
+        let pattern: Vec<char> = pattern.chars().collect();
+
+        matches_pattern(&pattern, &name) || matches_pattern(&pattern, &relative)
+    })
+}
+
+fn collect_entries(path: &Path, name: PathBuf, patterns: &[String], entries: &mut Vec<(PathBuf, PathBuf)>, errors: &mut Vec<(PathBuf, String)>) {
+    if is_excluded(&name, patterns) {
+        return;
+    }
+
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            errors.push((path.to_path_buf(), err.to_string()));
+            return;
+        }
+    };
+
+    entries.push((path.to_path_buf(), name.clone()));
+
+    if !metadata.is_dir() {
+        return;
+    }
+
+    match fs::read_dir(path) {
+        Ok(read_dir) => {
+            let mut children: Vec<PathBuf> = read_dir.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect();
+            children.sort();
+
+            for child in children {
+                let child_name = name.join(child.file_name().unwrap_or_default());
+
+                collect_entries(&child, child_name, patterns, entries, errors);
+            }
+        },
+        Err(err) => errors.push((path.to_path_buf(), err.to_string())),
+    }
+}
+
+pub fn compress(sources: &[PathBuf], output: &Path, options: &CompressOptions, job: &mut JobContext<CompressProgress>) -> BatchResult {
+    let mut result = BatchResult::default();
+    let mut entries = Vec::new();
+
+    for source in sources {
+        let name = PathBuf::from(source.file_name().unwrap_or(source.as_os_str()));
+
+        collect_entries(source, name, &options.exclude, &mut entries, &mut result.errors);
+    }
+
+    // архів, що створюється всередині однієї з папок, не повинен потрапити сам у себе
+    entries.retain(|(path, _)| path != output);
+
+    if fs::symlink_metadata(output).is_ok() {
+        result.errors.push((output.to_path_buf(), format!("{} вже існує", output.display())));
+        return result;
+    }
+
+    if let Err(err) = write_archive(&entries, output, options, job, &mut result) {
+        result.errors.push((output.to_path_buf(), err.to_string()));
+    }
+
+    // недописаний архів не лишаємо
+    if result.cancelled || result.errors.iter().any(|(path, _)| path == output) {
+        let _ = fs::remove_file(output);
+    }
+
+    result
+}
+
+fn write_archive(
+    entries: &[(PathBuf, PathBuf)],
+    output: &Path,
+    options: &CompressOptions,
+    job: &mut JobContext<CompressProgress>,
+    result: &mut BatchResult,
+) -> io::Result<()> {
+    let file = BufWriter::new(File::create_new(output)?);
+    let level = options.level;
+
+    let file = match options.format {
+        ArchiveFormat::Zip => write_zip(file, entries, level, job, result)?,
+        ArchiveFormat::Tar => write_tar(file, entries, job, result)?,
+        ArchiveFormat::TarGz => write_tar(flate2::write::GzEncoder::new(file, flate2::Compression::new(level)), entries, job, result)?.finish()?,
+        ArchiveFormat::TarXz => write_tar(xz2::write::XzEncoder::new(file, level), entries, job, result)?.finish()?,
+        ArchiveFormat::TarZst => write_tar(zstd::stream::write::Encoder::new(file, level as i32)?, entries, job, result)?.finish()?,
+    };
+
+    file.into_inner().map_err(|err| err.into_error())?.sync_all()
+}
+
+fn write_tar<W: Write>(
+    writer: W,
+    entries: &[(PathBuf, PathBuf)],
+    job: &mut JobContext<CompressProgress>,
+    result: &mut BatchResult,
+) -> io::Result<W> {
+    let mut builder = tar::Builder::new(writer);
+    builder.follow_symlinks(false);
+
+    for (index, (path, name)) in entries.iter().enumerate() {
+        if job.is_cancelled() {
+            result.cancelled = true;
+            break;
+        }
+
+        match builder.append_path_with_name(path, name) {
+            Ok(()) => result.done += 1,
+            Err(err) => result.errors.push((path.clone(), err.to_string())),
+        }
+
+        job.report(CompressProgress { done: index as u64 + 1, total: entries.len() as u64 });
+    }
+
+    builder.into_inner()
+}
+
+fn zip_time(time: SystemTime) -> Option<zip::DateTime> {
+    let local: DateTime<Local> = time.into();
+
+    zip::DateTime::from_date_and_time(
+        local.year().try_into().ok()?,
+        local.month() as u8,
+        local.day() as u8,
+        local.hour() as u8,
+        local.minute() as u8,
+        local.second() as u8,
+    ).ok()
+}
+
+fn write_zip<W: Write + Seek>(
+    writer: W,
+    entries: &[(PathBuf, PathBuf)],
+    level: u32,
+    job: &mut JobContext<CompressProgress>,
+    result: &mut BatchResult,
+) -> io::Result<W> {
+    let mut zip = zip::ZipWriter::new(writer);
+
+    let base_options = if level == 0 {
+        SimpleFileOptions::default().compression_method(CompressionMethod::Stored)
+    } else {
+        SimpleFileOptions::default().compression_method(CompressionMethod::Deflated).compression_level(Some(level.into()))
+    };
+
+    for (index, (path, name)) in entries.iter().enumerate() {
+        if job.is_cancelled() {
+            result.cancelled = true;
+            break;
+        }
+
+        let mut add_entry = || -> io::Result<()> {
+            let metadata = fs::symlink_metadata(path)?;
+            let name = name.to_string_lossy().replace('\\', "/");
+
+            let mut options = base_options.large_file(metadata.len() >= u32::MAX as u64);
+
+            if let Some(modified) = metadata.modified().ok().and_then(zip_time) {
+                options = options.last_modified_time(modified);
+            }
+
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+
+                options = options.unix_permissions(metadata.permissions().mode());
+            }
+
+            if metadata.file_type().is_symlink() {
+                zip.add_symlink(name, fs::read_link(path)?.to_string_lossy(), options)?;
+            } else if metadata.is_dir() {
+                zip.add_directory(name, options)?;
+            } else {
+                let mut file = File::open(path)?;
+
+                zip.start_file(name, options)?;
+                io::copy(&mut file, &mut zip)?;
+            }
+
+            Ok(())
+        };
+
+        match add_entry() {
+            Ok(()) => result.done += 1,
+            Err(err) => result.errors.push((path.clone(), err.to_string())),
+        }
+
+        job.report(CompressProgress { done: index as u64 + 1, total: entries.len() as u64 });
+    }
+
+    zip.finish().map_err(io::Error::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::archive::reader::list_entries;
+    use crate::jobs::job::Job;
+
+    use eframe::egui;
+
+    use std::{env, thread, time::Duration};
+
+    #[test]
+    fn patterns_are_split_and_trimmed() {
+        assert_eq!(parse_patterns(" *.log, target ;.git,, "), ["*.log", "target", ".git"]);
+        assert!(parse_patterns("").is_empty());
+    }
+
+    #[test]
+    fn exclusion_matches_name_or_relative_path() {
+        let patterns = parse_patterns("*.log, docs/tmp");
+
+        assert!(is_excluded(Path::new("project/debug.log"), &patterns));
+        assert!(is_excluded(Path::new("docs/tmp"), &patterns));
+        assert!(!is_excluded(Path::new("project/docs/tmp"), &patterns));
+        assert!(!is_excluded(Path::new("project/main.rs"), &patterns));
+    }
+
+    #[test]
+    fn every_format_round_trips() {
+        let dir = env::temp_dir().join(format!("file_explorer_compress_{}", std::process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("project/src")).unwrap();
+        fs::write(dir.join("project/src/main.rs"), "fn main() {}").unwrap();
+        fs::write(dir.join("project/debug.log"), "log").unwrap();
+
+        for format in [ArchiveFormat::Zip, ArchiveFormat::Tar, ArchiveFormat::TarGz, ArchiveFormat::TarXz, ArchiveFormat::TarZst] {
+            let output = dir.join(format!("project.{}", format.extensions()[0]));
+            let options = CompressOptions { format, level: format.default_level(), exclude: parse_patterns("*.log") };
+            let sources = vec![dir.join("project")];
+            let job_output = output.clone();
+
+            let mut job = Job::spawn(&egui::Context::default(), move |job| compress(&sources, &job_output, &options, job));
+
+            while !job.is_finished() {
+                thread::sleep(Duration::from_millis(10));
+                job.poll();
+            }
+
+            let result = job.result.unwrap();
+            assert!(result.errors.is_empty(), "{:?}: {:?}", format, result.errors);
+
+            let paths: Vec<_> = list_entries(&output).unwrap().into_iter().map(|entry| entry.path).collect();
+            assert_eq!(paths, [PathBuf::from("project"), PathBuf::from("project/src"), PathBuf::from("project/src/main.rs")], "{:?}", format);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,2 +1,3 @@
 pub mod disk_list;
-pub mod get_disk_info;
\ No newline at end of file
+pub mod get_disk_info;
+pub mod udisks;
\ No newline at end of file
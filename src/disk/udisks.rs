@@ -0,0 +1,441 @@
+use eframe::egui;
+
+#[cfg(target_os = "linux")]
+use dbus::{
+    arg::{PropMap, RefArg},
+    blocking::Connection,
+    message::MatchRule,
+    Path as ObjectPath
+};
+
+use std::{
+    io,
+    path::PathBuf,
+    sync::mpsc::{channel, Receiver, Sender},
+    thread
+};
+
+#[cfg(target_os = "linux")]
+use std::{collections::HashMap, time::Duration};
+
+#[cfg(target_os = "linux")]
+const UDISKS: &str = "org.freedesktop.UDisks2";
+
+#[cfg(target_os = "linux")]
+const UDISKS_PATH: &str = "/org/freedesktop/UDisks2";
+
+// монтування може чекати, поки користувач введе пароль у вікні polkit
+#[cfg(target_os = "linux")]
+const ACTION_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[cfg(target_os = "linux")]
+const QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[cfg(target_os = "linux")]
+type ManagedObjects = HashMap<ObjectPath<'static>, HashMap<String, PropMap>>;
+
+// розділ з файловою системою, який UDisks2 не вважає системним
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemovableDrive {
+    // об'єкт блочного пристрою, через нього монтуємо і відмонтовуємо
+    pub object: String,
+    // об'єкт фізичного пристрою, через нього вилучаємо
+    pub drive: Option<String>,
+    pub device: String,
+    pub label: String,
+    pub size: u64,
+    pub mount_point: Option<PathBuf>,
+    pub can_eject: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DriveAction {
+    Mount,
+    Unmount,
+    Eject,
+}
+
+#[cfg(target_os = "linux")]
+fn dbus_error(err: dbus::Error) -> io::Error {
+    let kind = match err.name() {
+        Some(name) if name.starts_with("org.freedesktop.UDisks2.Error.NotAuthorized") => io::ErrorKind::PermissionDenied,
+        Some("org.freedesktop.UDisks2.Error.DeviceBusy") => io::ErrorKind::ResourceBusy,
+        _ => io::ErrorKind::Other,
+    };
+
+    io::Error::new(kind, err.message().unwrap_or("помилка D-Bus").to_string())
+}
+
+// рядки з масивів байтів UDisks2 (ay) закінчуються нульовим байтом
+#[cfg(target_os = "linux")]
+fn byte_string(arg: &dyn RefArg) -> String {
+    let bytes: Vec<u8> = arg.as_iter()
+        .map(|bytes| bytes.filter_map(|byte| byte.as_u64()).map(|byte| byte as u8).collect())
+        .unwrap_or_default();
+
+    String::from_utf8_lossy(&bytes).trim_end_matches('\0').to_string()
+}
+
+#[cfg(target_os = "linux")]
+fn property<'a>(properties: &'a PropMap, name: &str) -> Option<&'a dyn RefArg> {
+    properties.get(name).map(|variant| &*variant.0)
+}
+
+#[cfg(target_os = "linux")]
+fn property_str(properties: &PropMap, name: &str) -> String {
+    property(properties, name).and_then(|value| value.as_str()).unwrap_or_default().to_string()
+}
+
+#[cfg(target_os = "linux")]
+fn property_bool(properties: &PropMap, name: &str) -> bool {
+    property(properties, name).and_then(|value| value.as_u64()).is_some_and(|value| value != 0)
+}
+
+#[cfg(target_os = "linux")]
+fn managed_objects(connection: &Connection) -> io::Result<ManagedObjects> {
+    let proxy = connection.with_proxy(UDISKS, UDISKS_PATH, QUERY_TIMEOUT);
+
+    let (objects,): (ManagedObjects,) = proxy
+        .method_call("org.freedesktop.DBus.ObjectManager", "GetManagedObjects", ())
+        .map_err(dbus_error)?;
+
+    Ok(objects)
+}
+
+#[cfg(target_os = "linux")]
+fn system_bus() -> io::Result<Connection> {
+    Connection::new_system().map_err(dbus_error)
+}
+
+#[cfg(target_os = "linux")]
+pub fn removable_drives() -> io::Result<Vec<RemovableDrive>> {
+    Ok(drives_from_objects(&managed_objects(&system_bus()?)?))
+}
+
+// розділи з файловою системою, крім тих, які UDisks2 радить приховати або вважає системними
+#[cfg(target_os = "linux")]
+fn drives_from_objects(objects: &ManagedObjects) -> Vec<RemovableDrive> {
+    let mut drives = Vec::new();
+
+    for (object, interfaces) in objects {
+        let (Some(block), Some(filesystem)) = (
+            interfaces.get("org.freedesktop.UDisks2.Block"),
+            interfaces.get("org.freedesktop.UDisks2.Filesystem"),
+        ) else {
+            continue;
+        };
+
+        if property_bool(block, "HintIgnore") || property_bool(block, "HintSystem") {
+            continue;
+        }
+
+        let drive_object = Some(property_str(block, "Drive")).filter(|drive| drive != "/");
+        let drive_properties = drive_object.as_ref()
+            .and_then(|drive| objects.get(&ObjectPath::from(drive.clone())))
+            .and_then(|interfaces| interfaces.get("org.freedesktop.UDisks2.Drive"));
+
+        let device = property(block, "PreferredDevice").map(byte_string).unwrap_or_default();
+
+        let mount_point = property(filesystem, "MountPoints")
+            .and_then(|mount_points| mount_points.as_iter()?.next().map(byte_string))
+            .filter(|mount_point| !mount_point.is_empty())
+            .map(PathBuf::from);
+
+        // без мітки розділу показуємо модель пристрою, а якщо нема і її - ім'я пристрою
+        let label = [
+            property_str(block, "IdLabel"),
+            drive_properties.map(|drive| format!("{} {}", property_str(drive, "Vendor"), property_str(drive, "Model"))).unwrap_or_default(),
+            device.trim_start_matches("/dev/").to_string(),
+        ]
+            .into_iter()
+            .map(|label| label.trim().to_string())
+            .find(|label| !label.is_empty())
+            .unwrap_or_default();
+
+        drives.push(RemovableDrive {
+            object: object.to_string(),
+            can_eject: drive_properties.is_some_and(|drive| property_bool(drive, "Ejectable") || property_bool(drive, "CanPowerOff")),
+            drive: drive_object,
+            size: property(block, "Size").and_then(|size| size.as_u64()).unwrap_or(0),
+            device,
+            label,
+            mount_point,
+        });
+    }
+
+    drives.sort_by(|a, b| a.device.cmp(&b.device));
+
+    drives
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn removable_drives() -> io::Result<Vec<RemovableDrive>> {
+    Ok(Vec::new())
+}
+
+#[cfg(target_os = "linux")]
+pub fn mount(drive: &RemovableDrive) -> io::Result<PathBuf> {
+    let connection = system_bus()?;
+    let proxy = connection.with_proxy(UDISKS, drive.object.as_str(), ACTION_TIMEOUT);
+
+    let (mount_point,): (String,) = proxy
+        .method_call("org.freedesktop.UDisks2.Filesystem", "Mount", (PropMap::new(),))
+        .map_err(dbus_error)?;
+
+    Ok(PathBuf::from(mount_point))
+}
+
+#[cfg(target_os = "linux")]
+pub fn unmount(drive: &RemovableDrive) -> io::Result<()> {
+    let connection = system_bus()?;
+    let proxy = connection.with_proxy(UDISKS, drive.object.as_str(), ACTION_TIMEOUT);
+
+    proxy
+        .method_call("org.freedesktop.UDisks2.Filesystem", "Unmount", (PropMap::new(),))
+        .map_err(dbus_error)
+}
+
+// перед вимкненням відмонтовуємо всі розділи цього пристрою, а не лише вибраний
+#[cfg(target_os = "linux")]
+pub fn eject(drive: &RemovableDrive) -> io::Result<()> {
+    let Some(drive_object) = &drive.drive else {
+        return Err(io::Error::new(io::ErrorKind::Unsupported, "пристрій не можна вилучити"));
+    };
+
+    for partition in removable_drives()? {
+        if partition.drive.as_ref() == Some(drive_object) && partition.mount_point.is_some() {
+            unmount(&partition)?;
+        }
+    }
+
+    let connection = system_bus()?;
+    let objects = managed_objects(&connection)?;
+    let can_power_off = objects.get(&ObjectPath::from(drive_object.clone()))
+        .and_then(|interfaces| interfaces.get("org.freedesktop.UDisks2.Drive"))
+        .is_some_and(|properties| property_bool(properties, "CanPowerOff"));
+
+    // флешки і диски вимикаємо повністю, оптичні приводи просто відкриваємо
+    let method = if can_power_off { "PowerOff" } else { "Eject" };
+
+    connection.with_proxy(UDISKS, drive_object.as_str(), ACTION_TIMEOUT)
+        .method_call("org.freedesktop.UDisks2.Drive", method, (PropMap::new(),))
+        .map_err(dbus_error)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn mount(_drive: &RemovableDrive) -> io::Result<PathBuf> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "операційна система не підтримується"))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn unmount(_drive: &RemovableDrive) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "операційна система не підтримується"))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn eject(_drive: &RemovableDrive) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "операційна система не підтримується"))
+}
+
+// після змонтування повертає нову точку монтування, щоб туди можна було перейти
+pub fn run_action(action: DriveAction, drive: &RemovableDrive) -> io::Result<Option<PathBuf>> {
+    match action {
+        DriveAction::Mount => mount(drive).map(Some),
+        DriveAction::Unmount => unmount(drive).map(|_| None),
+        DriveAction::Eject => eject(drive).map(|_| None),
+    }
+}
+
+// список знімних дисків або помилка, яку треба показати користувачу
+type DriveList = Result<Vec<RemovableDrive>, String>;
+
+// потік слухає сигнали UDisks2 про підключення, відключення і монтування пристроїв,
+// а окремий потік перечитує список дисків, щоб виклики D-Bus не блокували інтерфейс
+#[derive(Debug)]
+pub struct DriveWatcher {
+    requests: Sender<()>,
+    drives: Receiver<DriveList>,
+    channels: Option<(Receiver<()>, Sender<DriveList>)>,
+}
+
+impl Default for DriveWatcher {
+    fn default() -> Self {
+        let (requests, request_receiver) = channel();
+        let (drive_sender, drives) = channel();
+
+        Self {
+            requests,
+            drives,
+            channels: Some((request_receiver, drive_sender)),
+        }
+    }
+}
+
+impl DriveWatcher {
+    pub fn start(&mut self, ctx: egui::Context) {
+        let Some((requests, drives)) = self.channels.take() else {
+            return;
+        };
+
+        let errors = drives.clone();
+        let watch_ctx = ctx.clone();
+
+        thread::spawn(move || {
+            while requests.recv().is_ok() {
+                // кілька сигналів поспіль дають одне читання
+                requests.try_iter().for_each(drop);
+
+                if drives.send(removable_drives().map_err(|err| err.to_string())).is_err() {
+                    break;
+                }
+
+                ctx.request_repaint();
+            }
+        });
+
+        let sender = self.requests.clone();
+
+        thread::spawn(move || {
+            if let Err(err) = watch(sender) {
+                let _ = errors.send(Err(format!("стеження за пристроями зупинено: {}", err)));
+                watch_ctx.request_repaint();
+            }
+        });
+
+        self.refresh();
+    }
+
+    pub fn refresh(&self) {
+        let _ = self.requests.send(());
+    }
+
+    // усі відповіді потоків з минулого кадру: помилки для тостів і списки дисків
+    pub fn receive(&self) -> Vec<DriveList> {
+        self.drives.try_iter().collect()
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn watch(sender: Sender<()>) -> io::Result<()> {
+    let connection = system_bus()?;
+
+    let notify = move || {
+        let _ = sender.send(());
+    };
+
+    // відправника не фільтруємо: сигнали приходять від унікального імені, а не від org.freedesktop.UDisks2
+    for member in ["InterfacesAdded", "InterfacesRemoved"] {
+        let notify = notify.clone();
+        let rule = MatchRule::new_signal("org.freedesktop.DBus.ObjectManager", member).with_namespaced_path(UDISKS_PATH);
+
+        connection.add_match(rule, move |_: (), _, _| {
+            notify();
+            true
+        }).map_err(dbus_error)?;
+    }
+
+    // точки монтування змінюються і тоді, коли пристрій монтує інша програма
+    let rule = MatchRule::new_signal("org.freedesktop.DBus.Properties", "PropertiesChanged").with_namespaced_path(UDISKS_PATH);
+
+    connection.add_match(rule, move |(interface,): (String,), _, _| {
+        if interface == "org.freedesktop.UDisks2.Filesystem" {
+            notify();
+        }
+
+        true
+    }).map_err(dbus_error)?;
+
+    loop {
+        connection.process(Duration::from_secs(60)).map_err(dbus_error)?;
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn watch(_sender: Sender<()>) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    use dbus::arg::Variant;
+
+    fn props(values: Vec<(&str, Box<dyn RefArg>)>) -> PropMap {
+        values.into_iter().map(|(name, value)| (name.to_string(), Variant(value))).collect()
+    }
+
+    fn c_string(value: &str) -> Vec<u8> {
+        let mut bytes = value.as_bytes().to_vec();
+        bytes.push(0);
+
+        bytes
+    }
+
+    fn partition(device: &str, label: &str, drive: &str, mount_point: Option<&str>, hints: (bool, bool)) -> HashMap<String, PropMap> {
+        let block = props(vec![
+            ("PreferredDevice", Box::new(c_string(device))),
+            ("IdLabel", Box::new(label.to_string())),
+            ("Drive", Box::new(ObjectPath::from(drive.to_string()))),
+            ("Size", Box::new(1024_u64)),
+            ("HintIgnore", Box::new(hints.0)),
+            ("HintSystem", Box::new(hints.1)),
+        ]);
+
+        let mount_points: Vec<Vec<u8>> = mount_point.into_iter().map(c_string).collect();
+        let filesystem = props(vec![("MountPoints", Box::new(mount_points))]);
+
+        HashMap::from([
+            ("org.freedesktop.UDisks2.Block".to_string(), block),
+            ("org.freedesktop.UDisks2.Filesystem".to_string(), filesystem),
+        ])
+    }
+
+    fn objects() -> ManagedObjects {
+        let drive = props(vec![
+            ("Vendor", Box::new("Kingston".to_string())),
+            ("Model", Box::new("DataTraveler".to_string())),
+            ("Ejectable", Box::new(false)),
+            ("CanPowerOff", Box::new(true)),
+        ]);
+
+        let block_only = HashMap::from([(
+            "org.freedesktop.UDisks2.Block".to_string(),
+            props(vec![("PreferredDevice", Box::new(c_string("/dev/sdb")))]),
+        )]);
+
+        HashMap::from([
+            (ObjectPath::from("/org/freedesktop/UDisks2/drives/usb"), HashMap::from([("org.freedesktop.UDisks2.Drive".to_string(), drive)])),
+            (ObjectPath::from("/org/freedesktop/UDisks2/block_devices/sdb1"), partition("/dev/sdb1", "", "/org/freedesktop/UDisks2/drives/usb", Some("/media/usb"), (false, false))),
+            (ObjectPath::from("/org/freedesktop/UDisks2/block_devices/sdb2"), partition("/dev/sdb2", "Фото", "/org/freedesktop/UDisks2/drives/usb", None, (false, false))),
+            (ObjectPath::from("/org/freedesktop/UDisks2/block_devices/sda1"), partition("/dev/sda1", "root", "/", Some("/"), (false, true))),
+            (ObjectPath::from("/org/freedesktop/UDisks2/block_devices/loop0"), partition("/dev/loop0", "snap", "/", None, (true, false))),
+            (ObjectPath::from("/org/freedesktop/UDisks2/block_devices/sdb"), block_only),
+        ])
+    }
+
+    #[test]
+    fn system_ignored_and_whole_disks_are_hidden() {
+        let devices: Vec<String> = drives_from_objects(&objects()).into_iter().map(|drive| drive.device).collect();
+
+        assert_eq!(devices, ["/dev/sdb1", "/dev/sdb2"]);
+    }
+
+    #[test]
+    fn drives_are_decoded_from_properties() {
+        let drives = drives_from_objects(&objects());
+
+        assert_eq!(drives[0], RemovableDrive {
+            object: "/org/freedesktop/UDisks2/block_devices/sdb1".to_string(),
+            drive: Some("/org/freedesktop/UDisks2/drives/usb".to_string()),
+            device: "/dev/sdb1".to_string(),
+            // без мітки розділу назва береться з моделі пристрою
+            label: "Kingston DataTraveler".to_string(),
+            size: 1024,
+            mount_point: Some(PathBuf::from("/media/usb")),
+            can_eject: true,
+        });
+        assert_eq!(drives[1].label, "Фото");
+        assert_eq!(drives[1].mount_point, None);
+    }
+}
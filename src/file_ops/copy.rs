@@ -0,0 +1,259 @@
+use crate::jobs::job::{BatchResult, JobContext};
+use crate::vfs::transfer::TransferProgress;
+
+use std::{
+    fs,
+    io,
+    io::ErrorKind,
+    path::Path,
+    path::PathBuf
+};
+
+// викликається після кожного скопійованого файлу з його розміром, помилка зупиняє копіювання
+type OnFile<'a> = &'a mut dyn FnMut(u64) -> io::Result<()>;
+
+// копіює файл або папку (рекурсивно) всередину dst_dir, не перезаписуючи наявні елементи;
+// недокопійована ціль видаляється: до копіювання її не існувало
+fn copy_into(src: &Path, dst_dir: &Path, on_file: OnFile) -> io::Result<()> {
+    let target = target_path(src, dst_dir)?;
+
+    if let Err(err) = copy_tree(src, &target, on_file) {
+        let _ = remove_path(&target);
+
+        return Err(err);
+    }
+
+    Ok(())
+}
+
+// повертає true, якщо елемент просто перейменовано і on_file не викликався
+fn move_into(src: &Path, dst_dir: &Path, on_file: OnFile) -> io::Result<bool> {
+    let target = target_path(src, dst_dir)?;
+
+    match fs::rename(src, &target) {
+        Ok(()) => Ok(true),
+        // rename не працює між різними файловими системами, тому копіюємо і видаляємо
+        Err(err) if err.kind() == ErrorKind::CrossesDevices => {
+            if let Err(err) = copy_tree(src, &target, on_file) {
+                let _ = remove_path(&target);
+
+                return Err(err);
+            }
+
+            remove_path(src)?;
+
+            Ok(false)
+        },
+        Err(err) => Err(err),
+    }
+}
+
+fn remove_path(path: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+// копіювання і переміщення між локальними папками у фоні; скасування перевіряється після кожного файлу.
+// done - кількість скопійованих файлів плюс елементи, переміщені простим перейменуванням
+pub fn transfer_local(sources: &[PathBuf], target_dir: &Path, is_move: bool, job: &mut JobContext<TransferProgress>) -> BatchResult {
+    let mut result = BatchResult::default();
+    let mut progress = TransferProgress::default();
+    let mut renamed = 0;
+
+    for source in sources {
+        if job.is_cancelled() {
+            result.cancelled = true;
+            break;
+        }
+
+        let mut on_file = |size: u64| {
+            if job.is_cancelled() {
+                return Err(io::Error::from(ErrorKind::Interrupted));
+            }
+
+            progress.files += 1;
+            progress.bytes += size;
+            job.report(progress);
+
+            Ok(())
+        };
+
+        let copied = if is_move {
+            move_into(source, target_dir, &mut on_file)
+        } else {
+            copy_into(source, target_dir, &mut on_file).map(|_| false)
+        };
+
+        match copied {
+            Ok(true) => renamed += 1,
+            Ok(false) => {},
+            Err(err) if err.kind() == ErrorKind::Interrupted => {
+                result.cancelled = true;
+                break;
+            },
+            Err(err) => result.errors.push((source.clone(), err.to_string())),
+        }
+    }
+
+    result.done = progress.files + renamed;
+
+    result
+}
+
+pub fn copy_recursive(src: &Path, dst: &Path) -> io::Result<()> {
+    copy_tree(src, dst, &mut |_| Ok(()))
+}
+
+fn copy_tree(src: &Path, dst: &Path, on_file: OnFile) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(src)?;
+
+    if metadata.file_type().is_symlink() {
+        copy_symlink(src, dst)
+    } else if metadata.is_dir() {
+        fs::create_dir(dst)?;
+
+        for entry in fs::read_dir(src)? {
+            let entry = entry?;
+
+            copy_tree(&entry.path(), &dst.join(entry.file_name()), on_file)?;
+        }
+
+        fs::set_permissions(dst, metadata.permissions())
+    } else {
+        if fs::symlink_metadata(dst).is_ok() {
+            return Err(io::Error::new(ErrorKind::AlreadyExists, format!("{} вже існує", dst.display())));
+        }
+
+        fs::copy(src, dst)?;
+
+        // час зміни зберігаємо, щоб порівняння папок не вважало копію іншим файлом;
+        // файл лише для читання відкрити на запис не вийде, тоді лишаємо як є
+        if let Ok(modified) = metadata.modified()
+            && let Ok(file) = fs::File::options().write(true).open(dst)
+        {
+            file.set_modified(modified)?;
+        }
+
+        on_file(metadata.len())
+    }
+}
+fn target_path(src: &Path, dst_dir: &Path) -> io::Result<PathBuf> {
+    let name = src.file_name().ok_or_else(|| {
+        io::Error::new(ErrorKind::InvalidInput, format!("некоректний шлях: {}", src.display()))
+    })?;
+
+    let target = dst_dir.join(name);
+
+    if dst_dir.starts_with(src) {
+        return Err(io::Error::new(ErrorKind::InvalidInput, "не можна скопіювати папку саму в себе"));
+    }
+
+    if fs::symlink_metadata(&target).is_ok() {
+        return Err(io::Error::new(ErrorKind::AlreadyExists, format!("{} вже існує", target.display())));
+    }
+
+    Ok(target)
+}
+
+#[cfg(unix)]
+fn copy_symlink(src: &Path, dst: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(src)?, dst)
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(src: &Path, dst: &Path) -> io::Result<()> {
+    fs::copy(src, dst).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jobs::job::Job;
+
+    use eframe::egui;
+
+    use std::{env, thread, time::Duration};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("file_explorer_copy_{}_{}", name, std::process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    fn run(sources: Vec<PathBuf>, target_dir: PathBuf, is_move: bool) -> BatchResult {
+        let mut job = Job::spawn(&egui::Context::default(), move |job| transfer_local(&sources, &target_dir, is_move, job));
+
+        while !job.is_finished() {
+            thread::sleep(Duration::from_millis(10));
+            job.poll();
+        }
+
+        job.result.unwrap()
+    }
+
+    #[test]
+    fn copy_copies_tree_and_refuses_existing() {
+        let dir = temp_dir("copy");
+
+        fs::create_dir_all(dir.join("src/inner")).unwrap();
+        fs::write(dir.join("src/inner/a.txt"), "a").unwrap();
+        fs::write(dir.join("src/b.txt"), "b").unwrap();
+        fs::create_dir(dir.join("dst")).unwrap();
+
+        let result = run(vec![dir.join("src")], dir.join("dst"), false);
+
+        assert_eq!((result.done, result.errors.len()), (2, 0));
+        assert_eq!(fs::read_to_string(dir.join("dst/src/inner/a.txt")).unwrap(), "a");
+
+        let existing = run(vec![dir.join("src")], dir.join("dst"), false);
+        let into_itself = run(vec![dir.join("src")], dir.join("src/inner"), false);
+
+        assert_eq!((existing.done, existing.errors.len()), (0, 1));
+        assert_eq!((into_itself.done, into_itself.errors.len()), (0, 1));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn failed_copy_leaves_no_partial_target() {
+        let dir = temp_dir("partial");
+
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/a.txt"), "a").unwrap();
+        fs::write(dir.join("src/b.txt"), "b").unwrap();
+        fs::create_dir(dir.join("dst")).unwrap();
+
+        let result = copy_into(&dir.join("src"), &dir.join("dst"), &mut |_| Err(io::Error::from(ErrorKind::Interrupted)));
+
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::Interrupted);
+        assert!(!dir.join("dst/src").exists());
+        assert!(dir.join("src/a.txt").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn move_renames_and_counts_items() {
+        let dir = temp_dir("move");
+
+        fs::write(dir.join("a.txt"), "a").unwrap();
+        fs::create_dir_all(dir.join("folder/inner")).unwrap();
+        fs::create_dir(dir.join("dst")).unwrap();
+
+        let result = run(vec![dir.join("a.txt"), dir.join("folder")], dir.join("dst"), true);
+
+        assert_eq!((result.done, result.errors.len()), (2, 0));
+        assert!(!dir.join("a.txt").exists());
+        assert_eq!(fs::read_to_string(dir.join("dst/a.txt")).unwrap(), "a");
+        assert!(dir.join("dst/folder/inner").is_dir());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,175 @@
+use crate::vfs::Vfs;
+use crate::xdg::user_dirs::templates_dir;
+
+use std::{
+    fs,
+    io,
+    io::ErrorKind,
+    path::Path,
+    path::PathBuf
+};
+
+pub const NEW_FOLDER_NAME: &str = "Нова папка";
+pub const NEW_FILE_NAME: &str = "Новий файл";
+
+// "Нова папка", "Нова папка (2)", "Нова папка (3)"... розширення зберігається в кінці
+fn candidate_name(stem: &str, extension: Option<&str>, index: usize) -> String {
+    let mut name = if index < 2 {
+        stem.to_string()
+    } else {
+        format!("{} ({})", stem, index)
+    };
+
+    if let Some(extension) = extension {
+        name.push('.');
+        name.push_str(extension);
+    }
+
+    name
+}
+
+// створює елемент з першим вільним ім'ям, create повертає AlreadyExists якщо ім'я зайняте
+fn create_with_free_name(
+    dir: &Path,
+    stem: &str,
+    extension: Option<&str>,
+    create: impl Fn(&Path) -> io::Result<()>,
+) -> io::Result<PathBuf> {
+    for index in 1.. {
+        let path = dir.join(candidate_name(stem, extension, index));
+
+        match create(&path) {
+            Ok(()) => return Ok(path),
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+    }
+
+    unreachable!()
+}
+
+// перше вільне ім'я поруч із path: "звіт.txt" -> "звіт (2).txt"
+pub fn free_path(path: &Path) -> PathBuf {
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = path.extension().map(|extension| extension.to_string_lossy());
+
+    (1..)
+        .map(|index| dir.join(candidate_name(&stem, extension.as_deref(), index)))
+        .find(|candidate| fs::symlink_metadata(candidate).is_err())
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+pub fn create_folder(dir: &Path) -> io::Result<PathBuf> {
+    create_with_free_name(dir, NEW_FOLDER_NAME, None, |path| fs::create_dir(path))
+}
+
+// папка в просторі без доступу через std::fs, наприклад на сервері
+pub fn create_folder_in(vfs: &dyn Vfs, dir: &Path) -> io::Result<PathBuf> {
+    create_with_free_name(dir, NEW_FOLDER_NAME, None, |path| vfs.mkdir(path))
+}
+
+pub fn create_file(dir: &Path) -> io::Result<PathBuf> {
+    create_with_free_name(dir, NEW_FILE_NAME, None, |path| {
+        fs::OpenOptions::new().write(true).create_new(true).open(path).map(|_| ())
+    })
+}
+
+pub fn create_from_template(dir: &Path, template: &Path) -> io::Result<PathBuf> {
+    let stem = template.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = template.extension().map(|extension| extension.to_string_lossy());
+
+    create_with_free_name(dir, &stem, extension.as_deref(), |path| {
+        let mut source = fs::File::open(template)?;
+        let mut target = fs::OpenOptions::new().write(true).create_new(true).open(path)?;
+
+        io::copy(&mut source, &mut target).map(|_| ())
+    })
+}
+
+pub fn templates() -> Vec<PathBuf> {
+    let mut templates: Vec<PathBuf> = match fs::read_dir(templates_dir()) {
+        Ok(read_dir) => read_dir
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    templates.sort();
+
+    templates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("file_explorer_create_{}_{}", name, std::process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn candidate_names_are_numbered_before_extension() {
+        assert_eq!(candidate_name("Нова папка", None, 1), "Нова папка");
+        assert_eq!(candidate_name("Нова папка", None, 2), "Нова папка (2)");
+        assert_eq!(candidate_name("звіт", Some("txt"), 3), "звіт (3).txt");
+    }
+
+    #[test]
+    fn created_items_take_the_first_free_name() {
+        let dir = temp_dir("free");
+
+        assert_eq!(create_folder(&dir).unwrap(), dir.join("Нова папка"));
+        assert_eq!(create_folder(&dir).unwrap(), dir.join("Нова папка (2)"));
+        assert_eq!(create_file(&dir).unwrap(), dir.join("Новий файл"));
+        assert_eq!(create_file(&dir).unwrap(), dir.join("Новий файл (2)"));
+        assert!(dir.join("Нова папка (2)").is_dir());
+        assert!(dir.join("Новий файл (2)").is_file());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn files_and_folders_block_each_others_names() {
+        let dir = temp_dir("collide");
+
+        fs::write(dir.join("Нова папка"), "").unwrap();
+        fs::create_dir(dir.join("Новий файл")).unwrap();
+
+        assert_eq!(create_folder(&dir).unwrap(), dir.join("Нова папка (2)"));
+        assert_eq!(create_file(&dir).unwrap(), dir.join("Новий файл (2)"));
+
+        fs::write(dir.join("звіт.txt"), "").unwrap();
+        assert_eq!(free_path(&dir.join("звіт.txt")), dir.join("звіт (2).txt"));
+        assert_eq!(free_path(&dir.join("інший.txt")), dir.join("інший.txt"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn template_copy_keeps_content_and_extension() {
+        let dir = temp_dir("template");
+        let template = dir.join("Документ.md");
+
+        fs::write(&template, "# заголовок").unwrap();
+        fs::create_dir(dir.join("target")).unwrap();
+
+        let created = create_from_template(&dir.join("target"), &template).unwrap();
+        let second = create_from_template(&dir.join("target"), &template).unwrap();
+
+        assert_eq!(created, dir.join("target/Документ.md"));
+        assert_eq!(second, dir.join("target/Документ (2).md"));
+        assert_eq!(fs::read_to_string(second).unwrap(), "# заголовок");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,69 @@
+use std::{
+    fs,
+    io,
+    io::ErrorKind,
+    path::Path,
+    path::PathBuf
+};
+
+// шлях з новим ім'ям у тій самій папці, якщо ім'я допустиме
+pub fn renamed_path(path: &Path, new_name: &str) -> io::Result<PathBuf> {
+    let new_name = new_name.trim();
+
+    if new_name.is_empty() || new_name == "." || new_name == ".." || new_name.contains('/') {
+        return Err(io::Error::new(ErrorKind::InvalidInput, format!("некоректне ім'я: {}", new_name)));
+    }
+
+    Ok(path.with_file_name(new_name))
+}
+
+pub fn rename_in_place(path: &Path, new_name: &str) -> io::Result<PathBuf> {
+    let new_path = renamed_path(path, new_name)?;
+
+    if new_path == path {
+        return Ok(new_path);
+    }
+
+    if fs::symlink_metadata(&new_path).is_ok() {
+        return Err(io::Error::new(ErrorKind::AlreadyExists, format!("{} вже існує", new_path.display())));
+    }
+
+    fs::rename(path, &new_path)?;
+
+    Ok(new_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+
+    #[test]
+    fn invalid_names_are_rejected() {
+        let path = Path::new("/tmp/a.txt");
+
+        for name in ["", "   ", ".", "..", "a/b", "/"] {
+            assert_eq!(renamed_path(path, name).unwrap_err().kind(), ErrorKind::InvalidInput, "{:?}", name);
+        }
+
+        assert_eq!(renamed_path(path, " b.txt ").unwrap(), PathBuf::from("/tmp/b.txt"));
+    }
+
+    #[test]
+    fn rename_refuses_existing_names() {
+        let dir = env::temp_dir().join(format!("file_explorer_rename_{}", std::process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("folder")).unwrap();
+        fs::write(dir.join("a.txt"), "a").unwrap();
+
+        assert_eq!(rename_in_place(&dir.join("a.txt"), "folder").unwrap_err().kind(), ErrorKind::AlreadyExists);
+        assert_eq!(rename_in_place(&dir.join("a.txt"), "a.txt").unwrap(), dir.join("a.txt"));
+        assert_eq!(rename_in_place(&dir.join("a.txt"), "b.txt").unwrap(), dir.join("b.txt"));
+        assert_eq!(fs::read_to_string(dir.join("b.txt")).unwrap(), "a");
+        assert!(!dir.join("a.txt").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
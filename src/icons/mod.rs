@@ -0,0 +1,117 @@
+use eframe::egui::ImageSource;
+
+// svg іконки вшиті в бінарник, тож програма не залежить від робочої папки під час запуску
+macro_rules! solid_icon {
+    ($name:literal) => {
+        eframe::egui::include_image!(concat!("../../assets/Font_Awesome_Icons/solid/", $name, ".svg"))
+    };
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Icon {
+    AngleRight,
+    ArrowDownAZ,
+    ArrowLeft,
+    ArrowRight,
+    ArrowRightArrowLeft,
+    ArrowRotateRight,
+    ArrowUp,
+    ArrowUpAZ,
+    Bookmark,
+    Cloud,
+    CodeCompare,
+    Eject,
+    Equals,
+    Eye,
+    EyeSlash,
+    File,
+    FileAudio,
+    FileCode,
+    FileCsv,
+    FileExcel,
+    FileImage,
+    FileLines,
+    FilePdf,
+    FilePowerpoint,
+    FileVideo,
+    FileWord,
+    FileZipper,
+    Folder,
+    Gear,
+    Grip,
+    HardDrive,
+    House,
+    Image,
+    List,
+    MagnifyingGlass,
+    MagnifyingGlassMinus,
+    MagnifyingGlassPlus,
+    Moon,
+    Pen,
+    Plus,
+    Server,
+    Sun,
+    TableColumns,
+    TableList,
+    Trash,
+    Xmark
+}
+
+impl Icon {
+    pub fn source(self) -> ImageSource<'static> {
+        match self {
+            Icon::AngleRight => solid_icon!("angle-right"),
+            Icon::ArrowDownAZ => solid_icon!("arrow-down-a-z"),
+            Icon::ArrowLeft => solid_icon!("arrow-left"),
+            Icon::ArrowRight => solid_icon!("arrow-right"),
+            Icon::ArrowRightArrowLeft => solid_icon!("arrow-right-arrow-left"),
+            Icon::ArrowRotateRight => solid_icon!("arrow-rotate-right"),
+            Icon::ArrowUp => solid_icon!("arrow-up"),
+            Icon::ArrowUpAZ => solid_icon!("arrow-up-a-z"),
+            Icon::Bookmark => solid_icon!("bookmark"),
+            Icon::Cloud => solid_icon!("cloud"),
+            Icon::CodeCompare => solid_icon!("code-compare"),
+            Icon::Eject => solid_icon!("eject"),
+            Icon::Equals => solid_icon!("equals"),
+            Icon::Eye => solid_icon!("eye"),
+            Icon::EyeSlash => solid_icon!("eye-slash"),
+            Icon::File => solid_icon!("file"),
+            Icon::FileAudio => solid_icon!("file-audio"),
+            Icon::FileCode => solid_icon!("file-code"),
+            Icon::FileCsv => solid_icon!("file-csv"),
+            Icon::FileExcel => solid_icon!("file-excel"),
+            Icon::FileImage => solid_icon!("file-image"),
+            Icon::FileLines => solid_icon!("file-lines"),
+            Icon::FilePdf => solid_icon!("file-pdf"),
+            Icon::FilePowerpoint => solid_icon!("file-powerpoint"),
+            Icon::FileVideo => solid_icon!("file-video"),
+            Icon::FileWord => solid_icon!("file-word"),
+            Icon::FileZipper => solid_icon!("file-zipper"),
+            Icon::Folder => solid_icon!("folder"),
+            Icon::Gear => solid_icon!("gear"),
+            Icon::Grip => solid_icon!("grip"),
+            Icon::HardDrive => solid_icon!("hard-drive"),
+            Icon::House => solid_icon!("house"),
+            Icon::Image => solid_icon!("image"),
+            Icon::List => solid_icon!("list"),
+            Icon::MagnifyingGlass => solid_icon!("magnifying-glass"),
+            Icon::MagnifyingGlassMinus => solid_icon!("magnifying-glass-minus"),
+            Icon::MagnifyingGlassPlus => solid_icon!("magnifying-glass-plus"),
+            Icon::Moon => solid_icon!("moon"),
+            Icon::Pen => solid_icon!("pen"),
+            Icon::Plus => solid_icon!("plus"),
+            Icon::Server => solid_icon!("server"),
+            Icon::Sun => solid_icon!("sun"),
+            Icon::TableColumns => solid_icon!("table-columns"),
+            Icon::TableList => solid_icon!("table-list"),
+            Icon::Trash => solid_icon!("trash"),
+            Icon::Xmark => solid_icon!("xmark"),
+        }
+    }
+}
+
+impl From<Icon> for ImageSource<'static> {
+    fn from(icon: Icon) -> Self {
+        icon.source()
+    }
+}
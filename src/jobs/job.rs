@@ -0,0 +1,114 @@
+use eframe::egui;
+
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{channel, Receiver, Sender},
+        Arc
+    },
+    thread,
+    time::{Duration, Instant}
+};
+
+// не частіше ніж раз на 100 мс, щоб потік не засипав інтерфейс повідомленнями
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+// робота у фоновому потоці з проміжним прогресом і можливістю скасування
+#[derive(Debug)]
+pub struct Job<P, T> {
+    progress_receiver: Receiver<P>,
+    result_receiver: Receiver<T>,
+    cancelled: Arc<AtomicBool>,
+    pub progress: Option<P>,
+    pub result: Option<T>,
+}
+
+// підсумок задачі, що обробляє багато файлів і не зупиняється на першій помилці
+#[derive(Debug, Default)]
+pub struct BatchResult {
+    pub done: u64,
+    pub errors: Vec<(PathBuf, String)>,
+    pub cancelled: bool,
+}
+
+pub struct JobContext<P> {
+    sender: Sender<P>,
+    cancelled: Arc<AtomicBool>,
+    repaint: egui::Context,
+    last_report: Option<Instant>,
+}
+
+impl<P> JobContext<P> {
+    pub fn report(&mut self, progress: P) {
+        if self.last_report.is_some_and(|last_report| last_report.elapsed() < PROGRESS_INTERVAL) {
+            return;
+        }
+
+        self.last_report = Some(Instant::now());
+
+        let _ = self.sender.send(progress);
+        self.repaint.request_repaint();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    // для паралельної роботи, де контекст не можна передати в інші потоки
+    pub fn cancel_flag(&self) -> Arc<AtomicBool> {
+        self.cancelled.clone()
+    }
+}
+
+impl<P: Send + 'static, T: Send + 'static> Job<P, T> {
+    pub fn spawn(ctx: &egui::Context, work: impl FnOnce(&mut JobContext<P>) -> T + Send + 'static) -> Self {
+        let (progress_sender, progress_receiver) = channel();
+        let (result_sender, result_receiver) = channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        let mut job_context = JobContext {
+            sender: progress_sender,
+            cancelled: cancelled.clone(),
+            repaint: ctx.clone(),
+            last_report: None,
+        };
+
+        thread::spawn(move || {
+            let result = work(&mut job_context);
+
+            let _ = result_sender.send(result);
+            job_context.repaint.request_repaint();
+        });
+
+        Self {
+            progress_receiver,
+            result_receiver,
+            cancelled,
+            progress: None,
+            result: None,
+        }
+    }
+
+    pub fn poll(&mut self) {
+        if let Some(progress) = self.progress_receiver.try_iter().last() {
+            self.progress = Some(progress);
+        }
+
+        if let Ok(result) = self.result_receiver.try_recv() {
+            self.result = Some(result);
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.result.is_some()
+    }
+}
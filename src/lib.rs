@@ -1,2 +1,21 @@
+pub mod analysis;
 pub mod app;
-pub mod disk;
\ No newline at end of file
+pub mod apps;
+pub mod archive;
+pub mod disk;
+pub mod file_ops;
+pub mod icons;
+pub mod jobs;
+pub mod listing;
+pub mod mime;
+pub mod navigation;
+pub mod notifications;
+pub mod preview;
+pub mod process;
+pub mod remote;
+pub mod properties;
+pub mod settings;
+pub mod sidebar;
+pub mod tabs;
+pub mod vfs;
+pub mod xdg;
\ No newline at end of file
@@ -0,0 +1,44 @@
+use chrono::{DateTime, Local};
+
+use std::time::SystemTime;
+
+const SIZE_UNITS: [&str; 5] = ["Б", "КБ", "МБ", "ГБ", "ТБ"];
+
+pub fn format_size(size: u64) -> String {
+    let mut value = size as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < SIZE_UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", size, SIZE_UNITS[0])
+    } else {
+        format!("{:.1} {}", value, SIZE_UNITS[unit])
+    }
+}
+
+pub fn format_time(time: SystemTime) -> String {
+    DateTime::<Local>::from(time).format("%d.%m.%Y %H:%M").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_use_binary_units() {
+        assert_eq!(format_size(0), "0 Б");
+        assert_eq!(format_size(1023), "1023 Б");
+        assert_eq!(format_size(1024), "1.0 КБ");
+        assert_eq!(format_size(1536), "1.5 КБ");
+        assert_eq!(format_size(5 * 1024 * 1024 * 1024), "5.0 ГБ");
+    }
+
+    #[test]
+    fn largest_unit_is_terabytes() {
+        assert_eq!(format_size(2048 * 1024_u64.pow(4)), "2048.0 ТБ");
+    }
+}
@@ -0,0 +1,46 @@
+// * — будь-яка кількість символів, ? — один символ
+pub fn matches_pattern(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skip| matches_pattern(rest, &name[skip..])),
+        Some(('?', rest)) => !name.is_empty() && matches_pattern(rest, &name[1..]),
+        Some((symbol, rest)) => name.first() == Some(symbol) && matches_pattern(rest, &name[1..]),
+    }
+}
+
+pub fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    matches_pattern(&pattern, &name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn star_and_question_mark() {
+        assert!(wildcard_match("*.rs", "main.rs"));
+        assert!(wildcard_match("*.rs", ".rs"));
+        assert!(!wildcard_match("*.rs", "main.rsx"));
+        assert!(wildcard_match("file?.txt", "file1.txt"));
+        assert!(!wildcard_match("file?.txt", "file.txt"));
+        assert!(wildcard_match("*", ""));
+        assert!(wildcard_match("a*b*c", "aXXbYYc"));
+        assert!(!wildcard_match("a*b*c", "aXXbYY"));
+    }
+
+    #[test]
+    fn matching_is_by_characters_not_bytes() {
+        assert!(wildcard_match("звіт-?.pdf", "звіт-Ї.pdf"));
+        assert!(wildcard_match("*.тхт", "файл.тхт"));
+    }
+
+    #[test]
+    fn literal_match_is_case_sensitive() {
+        assert!(wildcard_match("server", "server"));
+        assert!(!wildcard_match("server", "Server"));
+        assert!(!wildcard_match("", "a"));
+    }
+}
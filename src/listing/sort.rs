@@ -0,0 +1,138 @@
+use crate::vfs::VfsEntry;
+
+use serde::{Deserialize, Serialize};
+
+use std::cmp::Ordering;
+
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum SortKey {
+    Name,
+    Size,
+    Modified,
+    Extension
+}
+
+impl SortKey {
+    pub const ALL: [SortKey; 4] = [SortKey::Name, SortKey::Size, SortKey::Modified, SortKey::Extension];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortKey::Name => "Ім'я",
+            SortKey::Size => "Розмір",
+            SortKey::Modified => "Дата зміни",
+            SortKey::Extension => "Тип",
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SortOrder {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+impl Default for SortOrder {
+    fn default() -> Self {
+        Self {
+            key: SortKey::Name,
+            descending: false,
+        }
+    }
+}
+
+struct SortableEntry {
+    entry: VfsEntry,
+    name: String,
+}
+
+// папки завжди йдуть перед файлами, незалежно від напрямку сортування
+pub fn sort_entries(entries: Vec<VfsEntry>, order: SortOrder) -> Vec<VfsEntry> {
+    let mut sortable: Vec<SortableEntry> = entries
+        .into_iter()
+        .map(|entry| SortableEntry {
+            name: entry.name.to_lowercase(),
+            entry,
+        })
+        .collect();
+
+    sortable.sort_by(|a, b| {
+        let ordering = match order.key {
+            SortKey::Name => Ordering::Equal,
+            SortKey::Size => a.entry.size.cmp(&b.entry.size),
+            SortKey::Modified => a.entry.modified.cmp(&b.entry.modified),
+            SortKey::Extension => extension(&a.name).cmp(extension(&b.name)),
+        }
+        .then_with(|| a.name.cmp(&b.name));
+
+        let ordering = if order.descending { ordering.reverse() } else { ordering };
+
+        b.entry.is_dir.cmp(&a.entry.is_dir).then(ordering)
+    });
+
+    sortable.into_iter().map(|sortable| sortable.entry).collect()
+}
+
+fn extension(name: &str) -> &str {
+    match name.rfind('.') {
+        Some(index) if index > 0 => &name[index + 1..],
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{
+        path::PathBuf,
+        time::{Duration, UNIX_EPOCH}
+    };
+
+    fn entry(name: &str, is_dir: bool, size: u64, modified: u64) -> VfsEntry {
+        VfsEntry {
+            path: PathBuf::from("/").join(name),
+            name: name.to_string(),
+            is_dir,
+            size,
+            modified: Some(UNIX_EPOCH + Duration::from_secs(modified)),
+        }
+    }
+
+    fn names(entries: Vec<VfsEntry>) -> Vec<String> {
+        entries.into_iter().map(|entry| entry.name).collect()
+    }
+
+    fn sample() -> Vec<VfsEntry> {
+        vec![
+            entry("b.txt", false, 30, 1),
+            entry("A.rs", false, 10, 3),
+            entry("zeta", true, 0, 2),
+            entry(".hidden", false, 20, 4),
+            entry("Alpha", true, 0, 5),
+        ]
+    }
+
+    #[test]
+    fn folders_first_then_case_insensitive_names() {
+        assert_eq!(names(sort_entries(sample(), SortOrder::default())), ["Alpha", "zeta", ".hidden", "A.rs", "b.txt"]);
+    }
+
+    #[test]
+    fn descending_keeps_folders_first() {
+        let order = SortOrder { key: SortKey::Name, descending: true };
+
+        assert_eq!(names(sort_entries(sample(), order)), ["zeta", "Alpha", "b.txt", "A.rs", ".hidden"]);
+    }
+
+    #[test]
+    fn sort_by_size_modified_and_extension() {
+        let by_size = SortOrder { key: SortKey::Size, descending: false };
+        let by_modified = SortOrder { key: SortKey::Modified, descending: true };
+        let by_extension = SortOrder { key: SortKey::Extension, descending: false };
+
+        assert_eq!(names(sort_entries(sample(), by_size)), ["Alpha", "zeta", "A.rs", ".hidden", "b.txt"]);
+        assert_eq!(names(sort_entries(sample(), by_modified)), ["Alpha", "zeta", ".hidden", "A.rs", "b.txt"]);
+        // ".hidden" без розширення, бо крапка на початку імені
+        assert_eq!(names(sort_entries(sample(), by_extension)), ["Alpha", "zeta", ".hidden", "A.rs", "b.txt"]);
+    }
+}
@@ -2,6 +2,7 @@
 #![allow(rustdoc::missing_crate_level_docs)] // it's an example
 
 use File_Explorer::app::FileExplorerApp;
+use File_Explorer::settings::config::load_settings;
 use eframe::egui;
 use display_info::DisplayInfo;
 use std::sync::Arc;
@@ -12,13 +13,16 @@ fn main() -> eframe::Result {
     let display_info = DisplayInfo::all().unwrap(); // getting information about the display
 
     let icon = include_bytes!("../assets/logo.png");
+
+    // розмір вікна з попереднього запуску, інакше половина екрану
+    let inner_size = load_settings().window_size.unwrap_or([
+        (display_info[0].width / 2) as f32,
+        (display_info[0].height / 2) as f32,
+    ]);
     
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
-            .with_inner_size([
-                (display_info[0].width / 2) as f32,
-                (display_info[0].height / 2) as f32,
-            ])
+            .with_inner_size(inner_size)
             .with_icon(
                 // display our icon
                 Arc::new(eframe::icon_data::from_png_bytes(&icon[..]).unwrap()),
@@ -33,7 +37,7 @@ fn main() -> eframe::Result {
             // This gives us image support:
             egui_extras::install_image_loaders(&cc.egui_ctx);
 
-            Ok(Box::<FileExplorerApp>::default())
+            Ok(Box::new(FileExplorerApp::new(cc)))
         }),
     )
 }
@@ -0,0 +1,428 @@
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::Mutex
+};
+
+use crate::mime::magic::{parse_magic, MagicSection};
+use crate::xdg::data_dirs;
+
+#[derive(Debug)]
+struct Glob {
+    weight: u32,
+    mime: String,
+    pattern: String,
+    case_sensitive: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct MimeDatabase {
+    // "*.ext" — найчастіший вид шаблону, тому шукаємо його через HashMap
+    extensions: HashMap<String, (u32, String)>,
+    literals: HashMap<String, String>,
+    globs: Vec<Glob>,
+    magic: Vec<MagicSection>,
+    aliases: HashMap<String, String>,
+    parents: HashMap<String, Vec<String>>,
+    generic_icons: HashMap<String, String>,
+    mime_dirs: Vec<PathBuf>,
+    names: Mutex<HashMap<String, String>>,
+}
+
+// на випадок, коли shared-mime-info не встановлено
+const BUILTIN_GLOBS: &[(&str, &str)] = &[
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("bmp", "image/bmp"),
+    ("webp", "image/webp"),
+    ("svg", "image/svg+xml"),
+    ("ico", "image/vnd.microsoft.icon"),
+    ("pdf", "application/pdf"),
+    ("zip", "application/zip"),
+    ("tar", "application/x-tar"),
+    ("gz", "application/gzip"),
+    ("tgz", "application/x-compressed-tar"),
+    ("tar.gz", "application/x-compressed-tar"),
+    ("xz", "application/x-xz"),
+    ("tar.xz", "application/x-xz-compressed-tar"),
+    ("bz2", "application/x-bzip2"),
+    ("zst", "application/zstd"),
+    ("7z", "application/x-7z-compressed"),
+    ("rar", "application/vnd.rar"),
+    ("iso", "application/x-cd-image"),
+    ("deb", "application/vnd.debian.binary-package"),
+    ("rpm", "application/x-rpm"),
+    ("mp3", "audio/mpeg"),
+    ("ogg", "audio/ogg"),
+    ("flac", "audio/flac"),
+    ("wav", "audio/x-wav"),
+    ("mp4", "video/mp4"),
+    ("mkv", "video/x-matroska"),
+    ("webm", "video/webm"),
+    ("avi", "video/x-msvideo"),
+    ("mov", "video/quicktime"),
+    ("txt", "text/plain"),
+    ("md", "text/markdown"),
+    ("csv", "text/csv"),
+    ("html", "text/html"),
+    ("htm", "text/html"),
+    ("css", "text/css"),
+    ("rs", "text/rust"),
+    ("py", "text/x-python3"),
+    ("js", "application/javascript"),
+    ("c", "text/x-csrc"),
+    ("h", "text/x-chdr"),
+    ("cpp", "text/x-c++src"),
+    ("java", "text/x-java"),
+    ("go", "text/x-go"),
+    ("sh", "application/x-shellscript"),
+    ("json", "application/json"),
+    ("toml", "application/toml"),
+    ("yaml", "application/yaml"),
+    ("yml", "application/yaml"),
+    ("xml", "application/xml"),
+    ("doc", "application/msword"),
+    ("docx", "application/vnd.openxmlformats-officedocument.wordprocessingml.document"),
+    ("odt", "application/vnd.oasis.opendocument.text"),
+    ("xls", "application/vnd.ms-excel"),
+    ("xlsx", "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"),
+    ("ods", "application/vnd.oasis.opendocument.spreadsheet"),
+    ("ppt", "application/vnd.ms-powerpoint"),
+    ("pptx", "application/vnd.openxmlformats-officedocument.presentationml.presentation"),
+    ("odp", "application/vnd.oasis.opendocument.presentation"),
+];
+
+const BUILTIN_MAGIC: &[(usize, &[u8], &str)] = &[
+    (0, b"\x89PNG", "image/png"),
+    (0, b"\xff\xd8\xff", "image/jpeg"),
+    (0, b"GIF8", "image/gif"),
+    (0, b"%PDF-", "application/pdf"),
+    (0, b"PK\x03\x04", "application/zip"),
+    (0, b"\x1f\x8b", "application/gzip"),
+    (0, b"BZh", "application/x-bzip2"),
+    (0, b"\xfd7zXZ\x00", "application/x-xz"),
+    (0, b"\x28\xb5\x2f\xfd", "application/zstd"),
+    (0, b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+    (0, b"Rar!", "application/vnd.rar"),
+    (257, b"ustar", "application/x-tar"),
+    (0, b"\x7fELF", "application/x-executable"),
+    (0, b"ID3", "audio/mpeg"),
+    (0, b"OggS", "audio/ogg"),
+    (0, b"fLaC", "audio/flac"),
+    (8, b"WAVE", "audio/x-wav"),
+    (8, b"WEBP", "image/webp"),
+    (8, b"AVI ", "video/x-msvideo"),
+    (4, b"ftyp", "video/mp4"),
+    (0, b"\x1a\x45\xdf\xa3", "video/x-matroska"),
+    (0, b"SQLite format 3\x00", "application/vnd.sqlite3"),
+    (0, b"<?xml", "application/xml"),
+    (0, b"#!", "application/x-shellscript"),
+];
+
+const BUILTIN_NAMES: &[(&str, &str)] = &[
+    ("inode/directory", "Папка"),
+    ("text/plain", "Текстовий документ"),
+    ("application/octet-stream", "Невідомий тип"),
+    ("application/pdf", "Документ PDF"),
+    ("application/zip", "Архів ZIP"),
+];
+
+impl MimeDatabase {
+    pub fn load() -> Self {
+        let mut database = MimeDatabase::default();
+
+        // системні каталоги читаємо першими, щоб користувацькі записи їх перекривали
+        for dir in data_dirs().into_iter().rev() {
+            let mime_dir = dir.join("mime");
+
+            let Ok(globs) = fs::read_to_string(mime_dir.join("globs2")) else {
+                continue;
+            };
+
+            database.parse_globs(&globs);
+
+            if let Ok(magic) = fs::read(mime_dir.join("magic")) {
+                database.magic.extend(parse_magic(&magic));
+            }
+
+            for (file, map) in [("aliases", &mut database.aliases), ("generic-icons", &mut database.generic_icons)] {
+                for (mime, value) in read_pairs(&mime_dir.join(file), ' ', ':') {
+                    map.insert(mime, value);
+                }
+            }
+
+            for (mime, parent) in read_pairs(&mime_dir.join("subclasses"), ' ', ' ') {
+                database.parents.entry(mime).or_default().push(parent);
+            }
+
+            database.mime_dirs.insert(0, mime_dir);
+        }
+
+        if database.mime_dirs.is_empty() {
+            for (extension, mime) in BUILTIN_GLOBS {
+                database.extensions.insert(extension.to_string(), (50, mime.to_string()));
+            }
+
+            for (offset, value, mime) in BUILTIN_MAGIC {
+                database.magic.push(MagicSection::new(50, mime, *offset, value));
+            }
+        }
+
+        database.magic.sort_by_key(|section| Reverse(section.priority));
+        database.globs.sort_by(|a, b| b.weight.cmp(&a.weight).then(b.pattern.len().cmp(&a.pattern.len())));
+
+        database
+    }
+
+    fn parse_globs(&mut self, globs: &str) {
+        for line in globs.lines().filter(|line| !line.starts_with('#')) {
+            let mut fields = line.split(':');
+
+            let (Some(weight), Some(mime), Some(pattern)) = (fields.next(), fields.next(), fields.next()) else {
+                continue;
+            };
+
+            let weight: u32 = weight.parse().unwrap_or(50);
+            let case_sensitive = fields.next().is_some_and(|flags| flags.split(',').any(|flag| flag == "cs"));
+
+            let is_wildcard = |text: &str| text.contains(['*', '?', '[']);
+
+            if let Some(extension) = pattern.strip_prefix("*.")
+                && !case_sensitive
+                && !is_wildcard(extension)
+            {
+                let extension = extension.to_lowercase();
+
+                if self.extensions.get(&extension).is_none_or(|(old_weight, _)| weight >= *old_weight) {
+                    self.extensions.insert(extension, (weight, mime.to_string()));
+                }
+            } else if !is_wildcard(pattern) {
+                let pattern = if case_sensitive { pattern.to_string() } else { pattern.to_lowercase() };
+                self.literals.insert(pattern, mime.to_string());
+            } else {
+                self.globs.push(Glob {
+                    weight,
+                    mime: mime.to_string(),
+                    pattern: pattern.to_string(),
+                    case_sensitive,
+                });
+            }
+        }
+    }
+
+    pub fn mime_by_name(&self, name: &str) -> Option<String> {
+        let lowercase = name.to_lowercase();
+
+        if let Some(mime) = self.literals.get(name).or_else(|| self.literals.get(&lowercase)) {
+            return Some(mime.clone());
+        }
+
+        // спершу найдовше розширення: "a.tar.gz" -> "tar.gz", потім "gz"
+        for (index, _) in lowercase.match_indices('.') {
+            if let Some((_, mime)) = self.extensions.get(&lowercase[index + 1..]) {
+                return Some(mime.clone());
+            }
+        }
+
+        self.globs
+            .iter()
+            .find(|glob| match glob.case_sensitive {
+                true => glob_matches(&glob.pattern, name),
+                false => glob_matches(&glob.pattern.to_lowercase(), &lowercase),
+            })
+            .map(|glob| glob.mime.clone())
+    }
+
+    pub fn mime_by_content(&self, data: &[u8]) -> Option<String> {
+        self.magic
+            .iter()
+            .find(|section| section.matches(data))
+            .map(|section| section.mime.clone())
+    }
+
+    // скільки байтів читати з файлу для перевірки сигнатур
+    pub fn magic_extent(&self) -> usize {
+        self.magic.iter().map(MagicSection::extent).max().unwrap_or(0).min(64 * 1024)
+    }
+
+    pub fn unalias(&self, mime: &str) -> String {
+        self.aliases.get(mime).cloned().unwrap_or_else(|| mime.to_string())
+    }
+
+    pub fn parents(&self, mime: &str) -> Vec<String> {
+        let mut parents = self.parents.get(mime).cloned().unwrap_or_default();
+
+        // неявні батьки зі специфікації
+        if mime.starts_with("text/") && mime != "text/plain" {
+            parents.push("text/plain".to_string());
+        }
+
+        if mime != "application/octet-stream" && !mime.starts_with("inode/") {
+            parents.push("application/octet-stream".to_string());
+        }
+
+        parents
+    }
+
+    pub fn generic_icon(&self, mime: &str) -> Option<&str> {
+        self.generic_icons.get(mime).map(String::as_str)
+    }
+
+    pub fn is_subclass(&self, mime: &str, ancestor: &str) -> bool {
+        let mut queue = vec![mime.to_string()];
+        let mut depth = 0;
+
+        while let Some(current) = queue.pop() {
+            if current == ancestor {
+                return true;
+            }
+
+            depth += 1;
+            if depth > 32 {
+                break;
+            }
+
+            queue.extend(self.parents(&current));
+        }
+
+        false
+    }
+
+    pub fn comment(&self, mime: &str) -> String {
+        if let Some(name) = self.names.lock().unwrap().get(mime) {
+            return name.clone();
+        }
+
+        let name = self.mime_dirs
+            .iter()
+            .find_map(|dir| fs::read_to_string(dir.join(format!("{}.xml", mime))).ok())
+            .and_then(|xml| find_comment(&xml))
+            .or_else(|| BUILTIN_NAMES.iter().find(|(known, _)| *known == mime).map(|(_, name)| name.to_string()))
+            .unwrap_or_else(|| fallback_name(mime));
+
+        self.names.lock().unwrap().insert(mime.to_string(), name.clone());
+
+        name
+    }
+}
+
+fn read_pairs(path: &PathBuf, separator: char, alt_separator: char) -> Vec<(String, String)> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    content
+        .lines()
+        .filter_map(|line| {
+            line.split_once(separator)
+                .or_else(|| line.split_once(alt_separator))
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+// опис українською, якщо він є, інакше — стандартний англійський
+fn find_comment(xml: &str) -> Option<String> {
+    let mut default = None;
+
+    for part in xml.split("<comment").skip(1) {
+        let Some((attributes, rest)) = part.split_once('>') else {
+            continue;
+        };
+
+        let Some((text, _)) = rest.split_once("</comment>") else {
+            continue;
+        };
+
+        let text = unescape_xml(text.trim());
+
+        if attributes.contains("xml:lang=\"uk\"") {
+            return Some(text);
+        }
+
+        if !attributes.contains("xml:lang") && default.is_none() {
+            default = Some(text);
+        }
+    }
+
+    default
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn fallback_name(mime: &str) -> String {
+    match mime.split('/').next() {
+        Some("image") => "Зображення".to_string(),
+        Some("audio") => "Аудіо".to_string(),
+        Some("video") => "Відео".to_string(),
+        Some("text") => "Текстовий документ".to_string(),
+        _ => mime.to_string(),
+    }
+}
+
+// підтримуються лише '*' і '?', класи символів у globs2 трапляються рідко
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_matches_stars_and_question_marks() {
+        assert!(glob_matches("*.tar.gz", "archive.tar.gz"));
+        assert!(glob_matches("Makefile", "Makefile"));
+        assert!(glob_matches("*.?pp", "main.cpp"));
+        assert!(glob_matches("*a*b", "xxaxxab"));
+        assert!(!glob_matches("*.tar.gz", "archive.tar.xz"));
+        assert!(!glob_matches("*.?pp", "main.pp"));
+    }
+
+    #[test]
+    fn comment_prefers_ukrainian_then_default() {
+        let xml = r#"<mime-type type="text/plain"><comment>plain text document</comment><comment xml:lang="de">Textdokument</comment><comment xml:lang="uk">звичайний текстовий документ</comment></mime-type>"#;
+
+        assert_eq!(find_comment(xml).as_deref(), Some("звичайний текстовий документ"));
+        assert_eq!(find_comment(r#"<comment xml:lang="de">X</comment><comment>Tom &amp; Jerry</comment>"#).as_deref(), Some("Tom & Jerry"));
+        assert_eq!(find_comment("<mime-type/>"), None);
+    }
+
+    #[test]
+    fn fallback_names_by_media_type() {
+        assert_eq!(fallback_name("image/x-new"), "Зображення");
+        assert_eq!(fallback_name("application/x-new"), "application/x-new");
+    }
+}
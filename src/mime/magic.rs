@@ -0,0 +1,256 @@
+// розбір бінарного файлу magic з shared-mime-info:
+// [пріоритет:mime]
+// [відступ]>зсув=<довжина u16 be><значення>[&маска][~розмір слова][+довжина діапазону]
+
+#[derive(Debug)]
+pub struct MagicSection {
+    pub priority: u32,
+    pub mime: String,
+    rules: Vec<MagicRule>,
+}
+
+#[derive(Debug)]
+struct MagicRule {
+    offset: usize,
+    value: Vec<u8>,
+    mask: Option<Vec<u8>>,
+    range: usize,
+    children: Vec<MagicRule>,
+}
+
+impl MagicSection {
+    pub fn new(priority: u32, mime: &str, offset: usize, value: &[u8]) -> Self {
+        Self {
+            priority,
+            mime: mime.to_string(),
+            rules: vec![MagicRule {
+                offset,
+                value: value.to_vec(),
+                mask: None,
+                range: 1,
+                children: Vec::new(),
+            }],
+        }
+    }
+
+    pub fn matches(&self, data: &[u8]) -> bool {
+        self.rules.iter().any(|rule| rule.matches(data))
+    }
+
+    // скільки байтів з початку файлу потрібно для перевірки всіх правил секції
+    pub fn extent(&self) -> usize {
+        self.rules.iter().map(MagicRule::extent).max().unwrap_or(0)
+    }
+}
+
+impl MagicRule {
+    // вкладене правило перевіряється лише якщо збіглося батьківське
+    fn matches(&self, data: &[u8]) -> bool {
+        let matched = (self.offset..self.offset + self.range.max(1)).any(|start| {
+            let Some(window) = data.get(start..start + self.value.len()) else {
+                return false;
+            };
+
+            match &self.mask {
+                Some(mask) => window
+                    .iter()
+                    .zip(&self.value)
+                    .zip(mask)
+                    .all(|((byte, value), mask)| byte & mask == value & mask),
+                None => window == self.value.as_slice(),
+            }
+        });
+
+        matched && (self.children.is_empty() || self.children.iter().any(|child| child.matches(data)))
+    }
+
+    fn extent(&self) -> usize {
+        let own = self.offset + self.range.max(1) + self.value.len();
+
+        self.children.iter().map(MagicRule::extent).fold(own, usize::max)
+    }
+}
+
+pub fn parse_magic(data: &[u8]) -> Vec<MagicSection> {
+    let mut sections: Vec<MagicSection> = Vec::new();
+    let Some(mut data) = data.strip_prefix(b"MIME-Magic\0\n".as_slice()) else {
+        return sections;
+    };
+
+    while !data.is_empty() {
+        if data[0] == b'[' {
+            let Some(end) = data.iter().position(|&byte| byte == b'\n') else {
+                break;
+            };
+
+            let header = String::from_utf8_lossy(&data[1..end]);
+            let header = header.trim_end_matches(']');
+
+            if let Some((priority, mime)) = header.split_once(':') {
+                sections.push(MagicSection {
+                    priority: priority.parse().unwrap_or(50),
+                    mime: mime.to_string(),
+                    rules: Vec::new(),
+                });
+            }
+
+            data = &data[end + 1..];
+            continue;
+        }
+
+        match parse_rule(data) {
+            Some((indent, rule, rest)) => {
+                if let Some(section) = sections.last_mut() {
+                    insert_rule(&mut section.rules, indent, rule);
+                }
+
+                data = rest;
+            },
+            // незрозумілий рядок пропускаємо, як вимагає специфікація
+            None => match data.iter().position(|&byte| byte == b'\n') {
+                Some(end) => data = &data[end + 1..],
+                None => break,
+            },
+        }
+    }
+
+    sections
+}
+
+fn insert_rule(rules: &mut Vec<MagicRule>, indent: usize, rule: MagicRule) {
+    if indent == 0 {
+        rules.push(rule);
+    } else if let Some(parent) = rules.last_mut() {
+        insert_rule(&mut parent.children, indent - 1, rule);
+    }
+}
+
+fn parse_number(data: &[u8]) -> (usize, &[u8]) {
+    let digits = data.iter().take_while(|byte| byte.is_ascii_digit()).count();
+    let number = std::str::from_utf8(&data[..digits]).ok().and_then(|digits| digits.parse().ok()).unwrap_or(0);
+
+    (number, &data[digits..])
+}
+
+fn parse_rule(data: &[u8]) -> Option<(usize, MagicRule, &[u8])> {
+    let (indent, data) = parse_number(data);
+    let data = data.strip_prefix(b">")?;
+
+    let (offset, data) = parse_number(data);
+    let data = data.strip_prefix(b"=")?;
+
+    let len = u16::from_be_bytes([*data.first()?, *data.get(1)?]) as usize;
+    let value = data.get(2..2 + len)?.to_vec();
+    let mut data = &data[2 + len..];
+    let mut word_size = 1;
+
+    let mut rule = MagicRule {
+        offset,
+        value,
+        mask: None,
+        range: 1,
+        children: Vec::new(),
+    };
+
+    loop {
+        match data.first()? {
+            b'&' => {
+                rule.mask = Some(data.get(1..1 + len)?.to_vec());
+                data = &data[1 + len..];
+            },
+            b'~' => {
+                let (size, rest) = parse_number(&data[1..]);
+                word_size = size;
+                data = rest;
+            },
+            b'+' => {
+                let (range, rest) = parse_number(&data[1..]);
+                rule.range = range;
+                data = rest;
+            },
+            b'\n' => {
+                // слова у файлі записані big-endian, а файл читається в порядку байтів процесора
+                if cfg!(target_endian = "little") && word_size > 1 && len.is_multiple_of(word_size) {
+                    swap_words(&mut rule.value, word_size);
+
+                    if let Some(mask) = &mut rule.mask {
+                        swap_words(mask, word_size);
+                    }
+                }
+
+                return Some((indent, rule, &data[1..]));
+            },
+            _ => return None,
+        }
+    }
+}
+
+fn swap_words(bytes: &mut [u8], word_size: usize) {
+    for word in bytes.chunks_mut(word_size) {
+        word.reverse();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn magic(body: &[u8]) -> Vec<u8> {
+        [b"MIME-Magic\0\n".as_slice(), body].concat()
+    }
+
+    #[test]
+    fn parses_sections_with_nested_rules() {
+        let data = magic(b"[80:image/png]\n>0=\x00\x04\x89PNG\n[50:text/x-nested]\n>0=\x00\x01a\n1>2=\x00\x01c\n");
+        let sections = parse_magic(&data);
+
+        assert_eq!(sections.len(), 2);
+        assert_eq!((sections[0].priority, sections[0].mime.as_str()), (80, "image/png"));
+        assert!(sections[0].matches(b"\x89PNG\r\n"));
+        assert!(!sections[0].matches(b"GIF89a"));
+
+        assert!(sections[1].matches(b"abc"));
+        assert!(!sections[1].matches(b"abd"));
+        assert_eq!(sections[1].extent(), 4);
+    }
+
+    #[test]
+    fn mask_and_range() {
+        let data = magic(b"[50:application/x-test]\n>4=\x00\x02AB&\xff\xdf+3\n");
+        let sections = parse_magic(&data);
+
+        assert!(sections[0].matches(b"......Ab"));
+        assert!(!sections[0].matches(b"........AB"));
+        assert_eq!(sections[0].extent(), 9);
+    }
+
+    #[test]
+    fn word_sized_values_use_host_byte_order() {
+        let data = magic(b"[50:application/x-host16]\n>0=\x00\x02\x12\x34~2\n");
+        let sections = parse_magic(&data);
+
+        let host = 0x1234_u16.to_ne_bytes();
+
+        assert!(sections[0].matches(&host));
+        assert!(!sections[0].matches(&0x3412_u16.to_ne_bytes()));
+    }
+
+    #[test]
+    fn word_size_swaps_mask_too() {
+        let data = magic(b"[50:application/x-host32]\n>0=\x00\x04\x00\x00\x01\x02&\x00\x00\xff\xff~4\n");
+        let sections = parse_magic(&data);
+
+        assert!(sections[0].matches(&0xAAAA_0102_u32.to_ne_bytes()));
+        assert!(!sections[0].matches(&0xAAAA_0201_u32.to_ne_bytes()));
+    }
+
+    #[test]
+    fn unknown_lines_are_skipped() {
+        let data = magic(b"[50:text/x-a]\n>0=\x00\x01a?junk\n>0=\x00\x01b\n");
+        let sections = parse_magic(&data);
+
+        assert!(!sections[0].matches(b"a"));
+        assert!(sections[0].matches(b"b"));
+        assert!(parse_magic(b"not magic").is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+pub mod database;
+pub mod magic;
+
+use std::{
+    fs::{self, File},
+    io::Read,
+    path::Path,
+    sync::OnceLock
+};
+
+use crate::icons::Icon;
+use crate::mime::database::MimeDatabase;
+
+#[derive(Debug, Clone)]
+pub struct FileType {
+    pub mime: String,
+    pub name: String,
+    pub icon: Icon,
+}
+
+// база завантажується один раз при першому зверненні
+pub fn database() -> &'static MimeDatabase {
+    static DATABASE: OnceLock<MimeDatabase> = OnceLock::new();
+
+    DATABASE.get_or_init(MimeDatabase::load)
+}
+
+pub fn file_type(path: &Path) -> FileType {
+    from_mime(detect(path))
+}
+
+pub fn from_mime(mime: String) -> FileType {
+    FileType {
+        name: type_name(&mime),
+        icon: icon_for(&mime),
+        mime,
+    }
+}
+
+// тип лише за назвою, без звернень до диска; None — потрібен аналіз вмісту
+pub fn detect_by_name(path: &Path) -> Option<String> {
+    let database = database();
+
+    path.file_name()
+        .and_then(|name| database.mime_by_name(&name.to_string_lossy()))
+        .map(|mime| database.unalias(&mime))
+}
+
+// розширення має перевагу, вміст файлу читаємо лише коли за назвою тип невідомий
+pub fn detect(path: &Path) -> String {
+    let database = database();
+
+    if path.is_dir() {
+        return "inode/directory".to_string();
+    }
+
+    if let Some(mime) = detect_by_name(path) {
+        return mime;
+    }
+
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.len() == 0 => return "application/x-zerosize".to_string(),
+        Err(_) => return "application/octet-stream".to_string(),
+        _ => {},
+    }
+
+    let Ok(file) = File::open(path) else {
+        return "application/octet-stream".to_string();
+    };
+
+    let mut data = Vec::new();
+    let extent = database.magic_extent().max(1024) as u64;
+
+    if let Err(err) = file.take(extent).read_to_end(&mut data) {
+        println!("{:#?}", err);
+        return "application/octet-stream".to_string();
+    }
+
+    if let Some(mime) = database.mime_by_content(&data) {
+        return database.unalias(&mime);
+    }
+
+    if looks_like_text(&data) {
+        "text/plain".to_string()
+    } else {
+        "application/octet-stream".to_string()
+    }
+}
+
+fn looks_like_text(data: &[u8]) -> bool {
+    let sample = &data[..data.len().min(1024)];
+
+    !sample.contains(&0) && (std::str::from_utf8(sample).is_ok() || sample.iter().all(|byte| !byte.is_ascii_control() || byte.is_ascii_whitespace()))
+}
+
+pub fn type_name(mime: &str) -> String {
+    database().comment(mime)
+}
+
+pub fn icon_for(mime: &str) -> Icon {
+    let database = database();
+    let mut queue = vec![mime.to_string()];
+    let mut depth = 0;
+
+    while !queue.is_empty() && depth < 8 {
+        for mime in &queue {
+            if let Some(icon) = own_icon(database, mime) {
+                return icon;
+            }
+        }
+
+        queue = queue.iter().flat_map(|mime| database.parents(mime)).collect();
+        depth += 1;
+    }
+
+    Icon::File
+}
+
+fn own_icon(database: &MimeDatabase, mime: &str) -> Option<Icon> {
+    let icon = match mime {
+        "inode/directory" => Icon::Folder,
+        "application/pdf" => Icon::FilePdf,
+        "text/csv" => Icon::FileCsv,
+        "application/zip" | "application/gzip" | "application/x-tar" | "application/x-xz" | "application/x-bzip2"
+        | "application/zstd" | "application/x-7z-compressed" | "application/vnd.rar" => Icon::FileZipper,
+        "text/plain" | "text/markdown" | "application/x-zerosize" => Icon::FileLines,
+        "application/json" | "application/xml" | "application/javascript" | "application/toml" | "application/yaml" => Icon::FileCode,
+        _ => match database.generic_icon(mime) {
+            Some("image-x-generic") => Icon::FileImage,
+            Some("audio-x-generic") => Icon::FileAudio,
+            Some("video-x-generic") => Icon::FileVideo,
+            Some("package-x-generic") => Icon::FileZipper,
+            Some("text-x-script") => Icon::FileCode,
+            Some("x-office-document") => Icon::FileWord,
+            Some("x-office-spreadsheet") => Icon::FileExcel,
+            Some("x-office-presentation") => Icon::FilePowerpoint,
+            _ => match mime.split_once('/') {
+                Some(("image", _)) => Icon::FileImage,
+                Some(("audio", _)) => Icon::FileAudio,
+                Some(("video", _)) => Icon::FileVideo,
+                Some(("text", _)) => Icon::FileCode,
+                _ => return None,
+            },
+        },
+    };
+
+    Some(icon)
+}
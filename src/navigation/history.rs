@@ -0,0 +1,127 @@
+use std::path::PathBuf;
+
+const MAX_HISTORY_LEN: usize = 100;
+
+#[derive(Debug, Default, Clone)]
+pub struct History {
+    back: Vec<PathBuf>,
+    forward: Vec<PathBuf>,
+}
+
+impl History {
+    // викликається при звичайному переході, гілка "вперед" після цього втрачає сенс
+    pub fn visit(&mut self, from: PathBuf) {
+        if self.back.last() != Some(&from) {
+            self.back.push(from);
+        }
+
+        if self.back.len() > MAX_HISTORY_LEN {
+            self.back.remove(0);
+        }
+
+        self.forward.clear();
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+
+    // steps = 1 це попередня папка, 2 - та, що перед нею, і т.д.
+    pub fn go_back(&mut self, current: PathBuf, steps: usize) -> Option<PathBuf> {
+        Self::jump(&mut self.back, &mut self.forward, current, steps)
+    }
+
+    pub fn go_forward(&mut self, current: PathBuf, steps: usize) -> Option<PathBuf> {
+        Self::jump(&mut self.forward, &mut self.back, current, steps)
+    }
+
+    // найсвіжіші записи першими
+    pub fn back_entries(&self) -> impl Iterator<Item = &PathBuf> {
+        self.back.iter().rev()
+    }
+
+    pub fn forward_entries(&self) -> impl Iterator<Item = &PathBuf> {
+        self.forward.iter().rev()
+    }
+
+    fn jump(from: &mut Vec<PathBuf>, to: &mut Vec<PathBuf>, current: PathBuf, steps: usize) -> Option<PathBuf> {
+        if steps == 0 || steps > from.len() {
+            return None;
+        }
+
+        to.push(current);
+
+        for _ in 1..steps {
+            let skipped = from.pop()?;
+
+            to.push(skipped);
+        }
+
+        from.pop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn back_and_forward_jump_several_steps() {
+        let mut history = History::default();
+
+        history.visit(PathBuf::from("/a"));
+        history.visit(PathBuf::from("/b"));
+        history.visit(PathBuf::from("/c"));
+
+        assert_eq!(history.go_back(PathBuf::from("/d"), 2), Some(PathBuf::from("/b")));
+        assert_eq!(history.back_entries().collect::<Vec<_>>(), [&PathBuf::from("/a")]);
+        assert_eq!(history.forward_entries().collect::<Vec<_>>(), [&PathBuf::from("/c"), &PathBuf::from("/d")]);
+
+        assert_eq!(history.go_forward(PathBuf::from("/b"), 1), Some(PathBuf::from("/c")));
+        assert_eq!(history.forward_entries().collect::<Vec<_>>(), [&PathBuf::from("/d")]);
+    }
+
+    #[test]
+    fn visit_clears_forward_and_skips_duplicates() {
+        let mut history = History::default();
+
+        history.visit(PathBuf::from("/a"));
+        history.visit(PathBuf::from("/a"));
+        history.visit(PathBuf::from("/b"));
+        history.go_back(PathBuf::from("/c"), 1);
+
+        assert!(history.can_go_forward());
+
+        history.visit(PathBuf::from("/b"));
+
+        assert!(!history.can_go_forward());
+        assert_eq!(history.back_entries().count(), 2);
+    }
+
+    #[test]
+    fn invalid_steps_do_nothing() {
+        let mut history = History::default();
+
+        history.visit(PathBuf::from("/a"));
+
+        assert_eq!(history.go_back(PathBuf::from("/b"), 0), None);
+        assert_eq!(history.go_back(PathBuf::from("/b"), 2), None);
+        assert!(!history.can_go_forward());
+    }
+
+    #[test]
+    fn length_is_limited() {
+        let mut history = History::default();
+
+        for index in 0..MAX_HISTORY_LEN + 10 {
+            history.visit(PathBuf::from(format!("/{}", index)));
+        }
+
+        assert_eq!(history.back_entries().count(), MAX_HISTORY_LEN);
+        assert_eq!(history.back_entries().last(), Some(&PathBuf::from("/10")));
+    }
+}
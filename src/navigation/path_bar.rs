@@ -0,0 +1,227 @@
+use crate::xdg::home_dir;
+
+use std::{
+    env,
+    fs,
+    path::Component,
+    path::MAIN_SEPARATOR,
+    path::Path,
+    path::PathBuf
+};
+
+pub struct Breadcrumb {
+    pub label: String,
+    pub path: PathBuf,
+}
+
+pub fn breadcrumbs(path: &Path) -> Vec<Breadcrumb> {
+    let mut breadcrumbs: Vec<Breadcrumb> = Vec::new();
+    let mut current = PathBuf::new();
+
+    for component in path.components() {
+        current.push(component);
+
+        match component {
+            // на windows корінь вже входить у префікс диска ("C:\")
+            Component::RootDir if !breadcrumbs.is_empty() => {
+                if let Some(last) = breadcrumbs.last_mut() {
+                    last.path = current.clone();
+                }
+            },
+            Component::RootDir => {
+                breadcrumbs.push(Breadcrumb { label: "/".to_string(), path: current.clone() });
+            },
+            Component::Prefix(prefix) => {
+                breadcrumbs.push(Breadcrumb { label: prefix.as_os_str().to_string_lossy().to_string(), path: current.clone() });
+            },
+            Component::Normal(name) => {
+                breadcrumbs.push(Breadcrumb { label: name.to_string_lossy().to_string(), path: current.clone() });
+            },
+            Component::CurDir | Component::ParentDir => {},
+        }
+    }
+
+    breadcrumbs
+}
+
+pub fn subfolders(path: &Path, show_hidden: bool) -> Vec<PathBuf> {
+    let mut folders: Vec<PathBuf> = match fs::read_dir(path) {
+        Ok(read_dir) => read_dir
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .filter(|path| show_hidden || !is_hidden(path))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    folders.sort_by_key(|path| path.file_name().unwrap_or_default().to_string_lossy().to_lowercase());
+
+    folders
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name.to_string_lossy().starts_with('.'))
+}
+
+// розгортає "~" та змінні оточення ($HOME, ${HOME}) у введеному шляху
+pub fn expand_path(input: &str) -> PathBuf {
+    expand_path_with(input, &home_dir(), &|name| env::var(name).ok())
+}
+
+// змінні читаються через lookup, щоб тести не змінювали оточення всього процесу
+fn expand_path_with(input: &str, home: &Path, lookup: &dyn Fn(&str) -> Option<String>) -> PathBuf {
+    let input = input.trim();
+    let mut expanded = String::new();
+
+    if input == "~" {
+        return home.to_path_buf();
+    } else if let Some(rest) = input.strip_prefix("~/") {
+        expanded.push_str(&home.to_string_lossy());
+        expanded.push('/');
+        expanded.push_str(&expand_variables(rest, lookup));
+    } else {
+        expanded.push_str(&expand_variables(input, lookup));
+    }
+
+    PathBuf::from(expanded)
+}
+
+fn expand_variables(input: &str, lookup: &dyn Fn(&str) -> Option<String>) -> String {
+    let mut expanded = String::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != '$' {
+            expanded.push(ch);
+            continue;
+        }
+
+        let mut name = String::new();
+
+        if chars.peek() == Some(&'{') {
+            chars.next();
+
+            for ch in chars.by_ref() {
+                if ch == '}' {
+                    break;
+                }
+
+                name.push(ch);
+            }
+        } else {
+            while let Some(&ch) = chars.peek() {
+                if !(ch.is_ascii_alphanumeric() || ch == '_') {
+                    break;
+                }
+
+                name.push(ch);
+                chars.next();
+            }
+        }
+
+        match lookup(&name) {
+            Some(value) if !name.is_empty() => expanded.push_str(&value),
+            // невідомі змінні лишаємо як є, щоб не зламати шлях
+            _ => {
+                expanded.push('$');
+                expanded.push_str(&name);
+            }
+        }
+    }
+
+    expanded
+}
+
+// варіанти доповнення для останнього компонента, у тому вигляді, як їх ввів користувач.
+// відносний шлях доповнюється від base - папки поточної вкладки
+pub fn completions(base: &Path, input: &str) -> Vec<String> {
+    let (typed_dir, prefix) = match input.rfind(['/', '\\']) {
+        Some(index) => (&input[..=index], &input[index + 1..]),
+        None => ("", input),
+    };
+
+    let show_hidden = prefix.starts_with('.');
+    let prefix = prefix.to_lowercase();
+
+    let mut completions: Vec<String> = match fs::read_dir(base.join(expand_path(typed_dir))) {
+        Ok(read_dir) => read_dir
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| show_hidden || !name.starts_with('.'))
+            .filter(|name| name.to_lowercase().starts_with(&prefix))
+            .map(|name| format!("{}{}{}", typed_dir, name, MAIN_SEPARATOR))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    completions.sort_by_key(|completion| completion.to_lowercase());
+
+    completions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("file_explorer_path_bar_{}_{}", name, std::process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    fn expand(input: &str) -> PathBuf {
+        let lookup = |name: &str| (name == "PROJECT").then(|| "value".to_string());
+
+        expand_path_with(input, Path::new("/home/user"), &lookup)
+    }
+
+    #[test]
+    fn expand_path_expands_home_and_variables() {
+        assert_eq!(expand("~"), PathBuf::from("/home/user"));
+        assert_eq!(expand(" ~/docs "), PathBuf::from("/home/user/docs"));
+        assert_eq!(expand("~/$PROJECT"), PathBuf::from("/home/user/value"));
+        assert_eq!(expand("/a/$PROJECT/b"), PathBuf::from("/a/value/b"));
+        assert_eq!(expand("/a/${PROJECT}b"), PathBuf::from("/a/valueb"));
+    }
+
+    #[test]
+    fn expand_path_keeps_unknown_variables() {
+        assert_eq!(expand("/a/$MISSING/b"), PathBuf::from("/a/$MISSING/b"));
+        assert_eq!(expand("/price$"), PathBuf::from("/price$"));
+    }
+
+    #[test]
+    fn breadcrumbs_follow_components() {
+        let labels: Vec<String> = breadcrumbs(Path::new("/usr/local/bin")).into_iter().map(|crumb| crumb.label).collect();
+
+        assert_eq!(labels, ["/", "usr", "local", "bin"]);
+        assert_eq!(breadcrumbs(Path::new("/usr/local")).last().unwrap().path, PathBuf::from("/usr/local"));
+    }
+
+    #[test]
+    fn completions_resolve_relative_input_against_base() {
+        let base = temp_dir("completions");
+
+        fs::create_dir_all(base.join("Documents/Projects")).unwrap();
+        fs::create_dir_all(base.join("Downloads")).unwrap();
+        fs::create_dir_all(base.join(".hidden")).unwrap();
+        fs::write(base.join("Document.txt"), "").unwrap();
+
+        let separator = MAIN_SEPARATOR;
+
+        assert_eq!(completions(&base, "do"), [format!("Documents{}", separator), format!("Downloads{}", separator)]);
+        assert_eq!(completions(&base, "Documents/p"), [format!("Documents/Projects{}", separator)]);
+        assert_eq!(completions(&base, "."), [format!(".hidden{}", separator)]);
+
+        let absolute = format!("{}/Down", base.to_string_lossy());
+
+        assert_eq!(completions(Path::new("/"), &absolute), [format!("{}/Downloads{}", base.to_string_lossy(), separator)]);
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+}
@@ -0,0 +1,75 @@
+use eframe::{
+    egui,
+    egui::vec2
+};
+
+use std::time::{Duration, Instant};
+
+const TOAST_LIFETIME: Duration = Duration::from_secs(8);
+
+#[derive(Debug)]
+struct Toast {
+    title: String,
+    text: String,
+    created: Instant,
+}
+
+#[derive(Debug, Default)]
+pub struct Toasts {
+    toasts: Vec<Toast>,
+}
+
+impl Toasts {
+    pub fn push(&mut self, title: impl Into<String>, text: impl Into<String>) {
+        self.toasts.push(Toast {
+            title: title.into(),
+            text: text.into(),
+            created: Instant::now(),
+        });
+    }
+
+    // сповіщення показуються в правому нижньому куті і зникають самі
+    pub fn show(&mut self, ctx: &egui::Context) {
+        self.toasts.retain(|toast| toast.created.elapsed() < TOAST_LIFETIME);
+
+        if self.toasts.is_empty() {
+            return;
+        }
+
+        let mut closed = None;
+
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, vec2(-12.0, -12.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.set_max_width(360.0);
+
+                for (index, toast) in self.toasts.iter().enumerate() {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.strong(&toast.title);
+
+                            if ui.small_button("✕").clicked() {
+                                closed = Some(index);
+                            }
+                        });
+
+                        ui.label(&toast.text);
+                    });
+                }
+            });
+
+        if let Some(index) = closed {
+            self.toasts.remove(index);
+        }
+
+        let next_expiry = self.toasts
+            .iter()
+            .map(|toast| TOAST_LIFETIME.saturating_sub(toast.created.elapsed()))
+            .min();
+
+        if let Some(next_expiry) = next_expiry {
+            ctx.request_repaint_after(next_expiry);
+        }
+    }
+}
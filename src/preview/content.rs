@@ -0,0 +1,152 @@
+use crate::mime::{database, file_type, FileType};
+use crate::preview::{hex::hex_dump, pdf::render_first_page};
+
+use std::{
+    fs::{self, File},
+    io::{self, Read},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime}
+};
+
+// з файлу читаються лише перші 64 КБ, щоб великі файли не гальмували перегляд
+pub const PREVIEW_LIMIT: usize = 64 * 1024;
+
+// як часто перевіряти, чи не змінився показаний файл
+pub const PREVIEW_REFRESH: Duration = Duration::from_secs(2);
+
+// формати, які вміють відкривати встановлені завантажувачі egui_extras
+const IMAGE_TYPES: [&str; 5] = ["image/png", "image/jpeg", "image/gif", "image/webp", "image/svg+xml"];
+
+#[derive(Debug)]
+pub enum PreviewContent {
+    Text { text: String, language: String, truncated: bool },
+    Image { uri: String, dimensions: Option<(u32, u32)> },
+    // відрендерена сторінка або причина, чому рендер не вдався
+    Pdf(Result<String, String>),
+    Binary { dump: String, truncated: bool },
+    Folder { items: usize },
+    Error(String),
+}
+
+// увесь перегляд читається у фоновій задачі, бо папка може бути на повільному чи мережевому диску
+#[derive(Debug)]
+pub struct Preview {
+    pub path: PathBuf,
+    pub modified: Option<SystemTime>,
+    pub size: u64,
+    pub file_type: FileType,
+    pub content: PreviewContent,
+    // None — вмістити в панель
+    pub zoom: Option<f32>,
+}
+
+impl Preview {
+    pub fn load(path: &Path) -> Preview {
+        let metadata = fs::metadata(path).ok();
+        let file_type = file_type(path);
+
+        let mut preview = Preview {
+            path: path.to_path_buf(),
+            modified: metadata.as_ref().and_then(|metadata| metadata.modified().ok()),
+            size: metadata.as_ref().map_or(0, |metadata| metadata.len()),
+            content: PreviewContent::Error(String::new()),
+            file_type,
+            zoom: None,
+        };
+
+        preview.content = match preview.load_content() {
+            Ok(content) => content,
+            Err(err) => PreviewContent::Error(err.to_string()),
+        };
+
+        preview
+    }
+
+    // None, якщо час зміни той самий і перечитувати нічого
+    pub fn reload_if_changed(path: &Path, modified: Option<SystemTime>) -> Option<Preview> {
+        let current = fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+
+        (current != modified).then(|| Preview::load(path))
+    }
+
+    fn load_content(&self) -> io::Result<PreviewContent> {
+        let mime = self.file_type.mime.as_str();
+
+        if self.path.is_dir() {
+            return Ok(PreviewContent::Folder { items: fs::read_dir(&self.path)?.count() });
+        }
+
+        if IMAGE_TYPES.contains(&mime) {
+            return Ok(PreviewContent::Image {
+                uri: format!("file://{}", self.path.display()),
+                dimensions: image::image_dimensions(&self.path).ok(),
+            });
+        }
+
+        if mime == "application/pdf" {
+            let page = render_first_page(&self.path)
+                .map(|image| format!("file://{}", image.display()))
+                .map_err(|err| err.to_string());
+
+            return Ok(PreviewContent::Pdf(page));
+        }
+
+        let mut data = Vec::new();
+        File::open(&self.path)?.take(PREVIEW_LIMIT as u64).read_to_end(&mut data)?;
+
+        let truncated = self.size > PREVIEW_LIMIT as u64;
+
+        if is_text(mime, &data) {
+            // обрізаний файл може закінчуватись посеред символу utf-8
+            let text = String::from_utf8_lossy(&data).to_string();
+
+            let language = self.path
+                .extension()
+                .map(|extension| extension.to_string_lossy().to_string())
+                .unwrap_or_else(|| "txt".to_string());
+
+            return Ok(PreviewContent::Text { text, language, truncated });
+        }
+
+        Ok(PreviewContent::Binary { dump: hex_dump(&data[..data.len().min(4096)]), truncated: data.len() > 4096 })
+    }
+}
+
+// нульовий байт видає двійковий файл, навіть якщо за назвою це текст
+fn is_text(mime: &str, data: &[u8]) -> bool {
+    let is_text_type = database().is_subclass(mime, "text/plain") || mime == "application/x-zerosize";
+
+    is_text_type && !data.contains(&0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+
+    #[test]
+    fn text_needs_text_type_and_no_zero_bytes() {
+        assert!(is_text("text/plain", b"hello"));
+        assert!(is_text("application/x-zerosize", b""));
+        assert!(!is_text("text/plain", b"he\0llo"));
+        assert!(!is_text("application/octet-stream", b"hello"));
+    }
+
+    #[test]
+    fn folder_preview_counts_items() {
+        let dir = env::temp_dir().join(format!("file_explorer_preview_{}", std::process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("inner")).unwrap();
+        fs::write(dir.join("a.txt"), "a").unwrap();
+
+        let preview = Preview::load(&dir);
+
+        assert!(matches!(preview.content, PreviewContent::Folder { items: 2 }));
+        assert!(Preview::reload_if_changed(&dir, preview.modified).is_none());
+        assert!(Preview::reload_if_changed(&dir, None).is_some());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,49 @@
+// класичний вигляд: зсув, 16 байтів у hex і їх ascii представлення
+pub fn hex_dump(bytes: &[u8]) -> String {
+    let mut dump = String::new();
+
+    for (line, chunk) in bytes.chunks(16).enumerate() {
+        dump.push_str(&format!("{:08x}  ", line * 16));
+
+        for index in 0..16 {
+            match chunk.get(index) {
+                Some(byte) => dump.push_str(&format!("{:02x} ", byte)),
+                None => dump.push_str("   "),
+            }
+
+            if index == 7 {
+                dump.push(' ');
+            }
+        }
+
+        dump.push_str(" |");
+        dump.extend(chunk.iter().map(|&byte| if byte.is_ascii_graphic() || byte == b' ' { byte as char } else { '.' }));
+        dump.push_str("|\n");
+    }
+
+    dump
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_line_shows_offset_hex_and_ascii() {
+        assert_eq!(
+            hex_dump(b"Hello, world!\n\x00\xff"),
+            "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 00 ff  |Hello, world!...|\n",
+        );
+    }
+
+    #[test]
+    fn short_last_line_is_padded() {
+        let dump = hex_dump(&[b'a'; 18]);
+        let lines: Vec<&str> = dump.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1], format!("00000010  61 61 {} |aa|", " ".repeat(3 * 14 + 1)));
+        assert_eq!(lines[0].len(), lines[1].len() + 14);
+        assert_eq!(hex_dump(&[]), "");
+    }
+}
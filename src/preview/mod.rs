@@ -0,0 +1,3 @@
+pub mod content;
+pub mod hex;
+pub mod pdf;
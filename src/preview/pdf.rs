@@ -0,0 +1,44 @@
+use crate::xdg::cache_home;
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::SystemTime
+};
+
+// перша сторінка рендериться утилітою pdftoppm з poppler у кеш
+pub fn render_first_page(path: &Path) -> io::Result<PathBuf> {
+    let modified = path.metadata()?.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    modified.hash(&mut hasher);
+
+    let cache_dir = cache_home().join("file_explorer/previews");
+    let output = cache_dir.join(format!("{:016x}", hasher.finish()));
+    let image = output.with_extension("png");
+
+    if image.exists() {
+        return Ok(image);
+    }
+
+    std::fs::create_dir_all(&cache_dir)?;
+
+    let status = Command::new("pdftoppm")
+        .args(["-png", "-f", "1", "-l", "1", "-scale-to", "1024", "-singlefile"])
+        .arg(path)
+        .arg(&output)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+
+    if !status.success() || !image.exists() {
+        return Err(io::Error::other("pdftoppm не зміг відобразити сторінку"));
+    }
+
+    Ok(image)
+}
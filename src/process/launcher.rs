@@ -0,0 +1,100 @@
+use eframe::egui;
+
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+
+use std::{
+    process::{Command, Stdio},
+    sync::mpsc::{channel, Receiver, Sender},
+    thread
+};
+
+#[derive(Debug)]
+pub struct LaunchFailure {
+    pub program: String,
+    pub message: String,
+}
+
+// процеси запускаються відокремлено від провідника, про невдалий запуск повідомляється через канал
+#[derive(Debug)]
+pub struct Launcher {
+    sender: Sender<LaunchFailure>,
+    receiver: Receiver<LaunchFailure>,
+    repaint: Option<egui::Context>,
+}
+
+impl Default for Launcher {
+    fn default() -> Self {
+        let (sender, receiver) = channel();
+
+        Self {
+            sender,
+            receiver,
+            repaint: None,
+        }
+    }
+}
+
+impl Launcher {
+    // без контексту помилка з'явиться лише при наступній перемальовці
+    pub fn set_repaint_context(&mut self, ctx: egui::Context) {
+        self.repaint = Some(ctx);
+    }
+
+    pub fn spawn(&self, mut command: Command) {
+        let program = command.get_program().to_string_lossy().to_string();
+
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+
+        // власна група процесів: Ctrl+C у терміналі, з якого запущено провідник, не зачіпає відкриті програми
+        #[cfg(unix)]
+        command.process_group(0);
+
+        match command.spawn() {
+            // потік лише чекає завершення, щоб процес не лишився зомбі
+            Ok(mut child) => {
+                thread::spawn(move || {
+                    let _ = child.wait();
+                });
+            },
+            Err(err) => {
+                let _ = self.sender.send(LaunchFailure { program, message: err.to_string() });
+
+                if let Some(ctx) = &self.repaint {
+                    ctx.request_repaint();
+                }
+            }
+        }
+    }
+
+    pub fn failures(&self) -> Vec<LaunchFailure> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_spawn_failures_are_reported() {
+        let launcher = Launcher::default();
+
+        launcher.spawn(Command::new("file-explorer-missing-program"));
+
+        let failures = launcher.failures();
+
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].program, "file-explorer-missing-program");
+
+        #[cfg(unix)]
+        {
+            launcher.spawn(Command::new("false"));
+
+            assert!(launcher.failures().is_empty());
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use std::fs;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Account {
+    pub id: u32,
+    pub name: String,
+}
+
+// /etc/passwd і /etc/group: ім'я:пароль:id:...
+fn read_accounts(path: &str) -> Vec<Account> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    let mut accounts: Vec<Account> = content
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split(':');
+            let name = fields.next()?;
+            let id = fields.nth(1)?.parse().ok()?;
+
+            Some(Account { id, name: name.to_string() })
+        })
+        .collect();
+
+    accounts.sort_by_key(|account| account.id);
+    accounts
+}
+
+pub fn users() -> Vec<Account> {
+    read_accounts("/etc/passwd")
+}
+
+pub fn groups() -> Vec<Account> {
+    read_accounts("/etc/group")
+}
+
+pub fn user_name(uid: u32) -> String {
+    users().into_iter().find(|user| user.id == uid).map_or(uid.to_string(), |user| user.name)
+}
+
+pub fn group_name(gid: u32) -> String {
+    groups().into_iter().find(|group| group.id == gid).map_or(gid.to_string(), |group| group.name)
+}
+
+#[cfg(unix)]
+pub fn current_uid() -> u32 {
+    unsafe { libc::geteuid() }
+}
+
+#[cfg(not(unix))]
+pub fn current_uid() -> u32 {
+    0
+}
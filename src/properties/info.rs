@@ -0,0 +1,110 @@
+#[cfg(unix)]
+use crate::properties::accounts::{group_name, user_name};
+
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+    time::SystemTime
+};
+
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
+
+#[derive(Debug, Clone)]
+pub struct FileProperties {
+    pub path: PathBuf,
+    pub is_dir: bool,
+    pub size: u64,
+    pub created: Option<SystemTime>,
+    pub modified: Option<SystemTime>,
+    pub accessed: Option<SystemTime>,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    pub owner: Option<String>,
+    pub group: Option<String>,
+    pub mode: Option<u32>,
+    pub inode: Option<u64>,
+    pub links: Option<u64>,
+    pub symlink_target: Option<PathBuf>,
+}
+
+pub fn read_properties(path: &Path) -> io::Result<FileProperties> {
+    let link_metadata = fs::symlink_metadata(path)?;
+    // для посилання показуємо властивості цілі, якщо вона існує
+    let metadata = fs::metadata(path).unwrap_or_else(|_| link_metadata.clone());
+
+    let symlink_target = if link_metadata.file_type().is_symlink() {
+        fs::read_link(path).ok()
+    } else {
+        None
+    };
+
+    let properties = FileProperties {
+        path: path.to_path_buf(),
+        is_dir: metadata.is_dir(),
+        size: metadata.len(),
+        created: metadata.created().ok(),
+        modified: metadata.modified().ok(),
+        accessed: metadata.accessed().ok(),
+        uid: None,
+        gid: None,
+        owner: None,
+        group: None,
+        mode: None,
+        inode: None,
+        links: None,
+        symlink_target,
+    };
+
+    #[cfg(unix)]
+    let properties = FileProperties {
+        uid: Some(metadata.uid()),
+        gid: Some(metadata.gid()),
+        owner: Some(user_name(metadata.uid())),
+        group: Some(group_name(metadata.gid())),
+        mode: Some(metadata.mode() & 0o7777),
+        inode: Some(metadata.ino()),
+        links: Some(metadata.nlink()),
+        ..properties
+    };
+
+    Ok(properties)
+}
+
+// rwxr-xr-x з урахуванням setuid, setgid і sticky біта
+pub fn mode_string(mode: u32) -> String {
+    let mut chars: Vec<char> = "rwxrwxrwx"
+        .chars()
+        .enumerate()
+        .map(|(index, c)| if mode & (0o400 >> index) != 0 { c } else { '-' })
+        .collect();
+
+    for (bit, index, lower, upper) in [(0o4000, 2, 's', 'S'), (0o2000, 5, 's', 'S'), (0o1000, 8, 't', 'T')] {
+        if mode & bit != 0 {
+            chars[index] = if chars[index] == 'x' { lower } else { upper };
+        }
+    }
+
+    chars.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mode_string_shows_rwx_bits() {
+        assert_eq!(mode_string(0o755), "rwxr-xr-x");
+        assert_eq!(mode_string(0o640), "rw-r-----");
+        assert_eq!(mode_string(0), "---------");
+    }
+
+    #[test]
+    fn special_bits_replace_execute_flags() {
+        assert_eq!(mode_string(0o4755), "rwsr-xr-x");
+        assert_eq!(mode_string(0o2750), "rwxr-s---");
+        assert_eq!(mode_string(0o1777), "rwxrwxrwt");
+        assert_eq!(mode_string(0o7644), "rwSr-Sr-T");
+    }
+}
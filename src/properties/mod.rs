@@ -0,0 +1,4 @@
+pub mod accounts;
+pub mod info;
+pub mod permissions;
+pub mod size;
@@ -0,0 +1,193 @@
+use crate::jobs::job::{BatchResult, JobContext};
+
+use std::{
+    fs,
+    io,
+    path::Path
+};
+
+#[cfg(unix)]
+pub fn set_mode(path: &Path, mode: u32) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+}
+
+// None залишає власника або групу без змін; як і chmod, змінює ціль посилання, бо діалог показує саме її
+#[cfg(unix)]
+pub fn set_owner(path: &Path, uid: Option<u32>, gid: Option<u32>) -> io::Result<()> {
+    std::os::unix::fs::chown(path, uid, gid)
+}
+
+#[cfg(not(unix))]
+pub fn set_mode(_path: &Path, _mode: u32) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "права доступу unix не підтримуються"))
+}
+
+#[cfg(not(unix))]
+pub fn set_owner(_path: &Path, _uid: Option<u32>, _gid: Option<u32>) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "власники unix не підтримуються"))
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum RecursiveTarget {
+    All,
+    FilesOnly,
+    FoldersOnly
+}
+
+impl RecursiveTarget {
+    pub const ALL: [RecursiveTarget; 3] = [RecursiveTarget::All, RecursiveTarget::FilesOnly, RecursiveTarget::FoldersOnly];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RecursiveTarget::All => "Файли та папки",
+            RecursiveTarget::FilesOnly => "Лише файли",
+            RecursiveTarget::FoldersOnly => "Лише папки",
+        }
+    }
+
+    fn includes(self, is_dir: bool) -> bool {
+        match self {
+            RecursiveTarget::All => true,
+            RecursiveTarget::FilesOnly => !is_dir,
+            RecursiveTarget::FoldersOnly => is_dir,
+        }
+    }
+}
+
+// None — відповідну властивість не змінювати
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PermissionChange {
+    pub mode: Option<u32>,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+}
+
+impl PermissionChange {
+    pub fn apply(&self, path: &Path) -> io::Result<()> {
+        if let Some(mode) = self.mode {
+            set_mode(path, mode)?;
+        }
+
+        if self.uid.is_some() || self.gid.is_some() {
+            set_owner(path, self.uid, self.gid)?;
+        }
+
+        Ok(())
+    }
+}
+
+// символьні посилання пропускаються: chmod змінив би ціль посилання
+pub fn apply_recursive(root: &Path, change: PermissionChange, target: RecursiveTarget, job: &mut JobContext<u64>) -> BatchResult {
+    let mut result = BatchResult::default();
+    let mut stack = vec![root.to_path_buf()];
+
+    while let Some(path) = stack.pop() {
+        if job.is_cancelled() {
+            result.cancelled = true;
+            break;
+        }
+
+        let Ok(metadata) = path.symlink_metadata() else {
+            continue;
+        };
+
+        if metadata.file_type().is_symlink() {
+            continue;
+        }
+
+        // права папки змінюються до читання, тож надані r/x дозволяють зайти в закриту папку
+        if target.includes(metadata.is_dir()) {
+            match change.apply(&path) {
+                Ok(()) => result.done += 1,
+                Err(err) => result.errors.push((path.clone(), err.to_string())),
+            }
+        }
+
+        if metadata.is_dir() {
+            match fs::read_dir(&path) {
+                Ok(read_dir) => stack.extend(read_dir.filter_map(|dir_entry| dir_entry.ok()).map(|dir_entry| dir_entry.path())),
+                Err(err) => result.errors.push((path, err.to_string())),
+            }
+        }
+
+        job.report(result.done);
+    }
+
+    result
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    use crate::jobs::job::Job;
+
+    use eframe::egui;
+
+    use std::{
+        env,
+        os::unix::fs::{MetadataExt, PermissionsExt},
+        path::PathBuf,
+        thread,
+        time::Duration
+    };
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("file_explorer_permissions_{}_{}", name, std::process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("nested")).unwrap();
+
+        dir
+    }
+
+    fn run(root: PathBuf, change: PermissionChange, target: RecursiveTarget) -> BatchResult {
+        let mut job = Job::spawn(&egui::Context::default(), move |job| apply_recursive(&root, change, target, job));
+
+        while !job.is_finished() {
+            thread::sleep(Duration::from_millis(10));
+            job.poll();
+        }
+
+        job.result.unwrap()
+    }
+
+    fn mode(path: &Path) -> u32 {
+        fs::metadata(path).unwrap().permissions().mode() & 0o7777
+    }
+
+    #[test]
+    fn group_change_keeps_modes() {
+        let dir = temp_dir("group");
+
+        fs::write(dir.join("nested/key"), "key").unwrap();
+        set_mode(&dir.join("nested/key"), 0o600).unwrap();
+
+        let gid = fs::metadata(&dir).unwrap().gid();
+        let result = run(dir.clone(), PermissionChange { gid: Some(gid), ..Default::default() }, RecursiveTarget::All);
+
+        assert_eq!(result.done, 3);
+        assert_eq!(mode(&dir.join("nested/key")), 0o600);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn folders_are_opened_before_listing() {
+        let dir = temp_dir("closed");
+
+        fs::write(dir.join("nested/file"), "file").unwrap();
+        set_mode(&dir.join("nested"), 0o000).unwrap();
+
+        let result = run(dir.clone(), PermissionChange { mode: Some(0o755), ..Default::default() }, RecursiveTarget::FoldersOnly);
+
+        assert!(result.errors.is_empty());
+        assert_eq!(result.done, 2);
+        assert_eq!(mode(&dir.join("nested")), 0o755);
+        assert_eq!(mode(&dir.join("nested/file")) & 0o111, 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
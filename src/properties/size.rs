@@ -0,0 +1,47 @@
+use crate::jobs::job::JobContext;
+
+use std::{
+    fs,
+    path::Path
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FolderSize {
+    pub bytes: u64,
+    pub files: u64,
+    pub folders: u64,
+}
+
+// символьні посилання не розкриваються, тож цикли в дереві не страшні
+pub fn folder_size(path: &Path, job: &mut JobContext<FolderSize>) -> FolderSize {
+    let mut size = FolderSize::default();
+    let mut stack = vec![path.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        if job.is_cancelled() {
+            break;
+        }
+
+        let Ok(read_dir) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for dir_entry in read_dir.filter_map(|dir_entry| dir_entry.ok()) {
+            let Ok(metadata) = dir_entry.path().symlink_metadata() else {
+                continue;
+            };
+
+            if metadata.is_dir() {
+                size.folders += 1;
+                stack.push(dir_entry.path());
+            } else {
+                size.files += 1;
+                size.bytes += metadata.len();
+            }
+        }
+
+        job.report(size);
+    }
+
+    size
+}
@@ -0,0 +1,30 @@
+use keyring::Entry;
+
+use std::io;
+
+const SERVICE: &str = "file_explorer";
+
+// паролі і секретні ключі профілів зберігаються в сховищі системи (Secret Service, Keychain, Credential Manager)
+fn entry(profile_name: &str) -> io::Result<Entry> {
+    Entry::new(SERVICE, &format!("remote:{}", profile_name)).map_err(io::Error::other)
+}
+
+pub fn save_secret(profile_name: &str, secret: &str) -> io::Result<()> {
+    entry(profile_name)?.set_password(secret).map_err(io::Error::other)
+}
+
+// профіль без збереженого секрету підключається з порожнім паролем
+pub fn load_secret(profile_name: &str) -> io::Result<String> {
+    match entry(profile_name)?.get_password() {
+        Ok(secret) => Ok(secret),
+        Err(keyring::Error::NoEntry) => Ok(String::new()),
+        Err(err) => Err(io::Error::other(err)),
+    }
+}
+
+pub fn delete_secret(profile_name: &str) -> io::Result<()> {
+    match entry(profile_name)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(err) => Err(io::Error::other(err)),
+    }
+}
@@ -0,0 +1,3 @@
+pub mod credentials;
+pub mod profiles;
+pub mod ssh_config;
@@ -0,0 +1,103 @@
+use super::credentials::load_secret;
+use crate::vfs::{
+    s3::{S3Vfs, S3_SCHEME},
+    webdav::{WebDavVfs, WEBDAV_SCHEME},
+    Vfs
+};
+use crate::xdg::config_home;
+
+use serde::{Deserialize, Serialize};
+
+use std::{
+    fs,
+    io,
+    io::ErrorKind,
+    path::PathBuf,
+    sync::Arc
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RemoteKind {
+    #[default]
+    WebDav,
+    S3
+}
+
+impl RemoteKind {
+    pub const ALL: [RemoteKind; 2] = [RemoteKind::WebDav, RemoteKind::S3];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RemoteKind::WebDav => "WebDAV",
+            RemoteKind::S3 => "S3",
+        }
+    }
+}
+
+// збережене підключення; пароль (секретний ключ для S3) лежить окремо, у сховищі паролів системи
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RemoteProfile {
+    pub name: String,
+    pub kind: RemoteKind,
+    // WebDAV — адреса спільної папки, S3 — адреса сервера
+    pub url: String,
+    // WebDAV — ім'я користувача, S3 — ключ доступу
+    pub user: String,
+    pub bucket: String,
+    pub region: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct ProfilesFile {
+    profiles: Vec<RemoteProfile>,
+}
+
+impl RemoteProfile {
+    pub fn root(&self) -> PathBuf {
+        match self.kind {
+            RemoteKind::WebDav => PathBuf::from(format!("{}{}", WEBDAV_SCHEME, self.name)),
+            RemoteKind::S3 => PathBuf::from(format!("{}{}", S3_SCHEME, self.name)),
+        }
+    }
+
+    pub fn connect(&self) -> io::Result<Arc<dyn Vfs>> {
+        let secret = load_secret(&self.name)?;
+
+        Ok(match self.kind {
+            RemoteKind::WebDav => Arc::new(WebDavVfs::connect(self.root(), &self.url, &self.user, &secret)?),
+            RemoteKind::S3 => Arc::new(S3Vfs::connect(self.root(), &self.url, &self.bucket, &self.region, &self.user, &secret)?),
+        })
+    }
+}
+
+fn profiles_path() -> PathBuf {
+    config_home().join("file_explorer").join("remotes.toml")
+}
+
+pub fn load_profiles() -> Vec<RemoteProfile> {
+    let content = fs::read_to_string(profiles_path()).unwrap_or_default();
+
+    match toml::from_str::<ProfilesFile>(&content) {
+        Ok(file) => file.profiles,
+        Err(err) => {
+            println!("не вдалося прочитати профілі підключень: {}", err);
+
+            Vec::new()
+        }
+    }
+}
+
+pub fn save_profiles(profiles: &[RemoteProfile]) -> io::Result<()> {
+    let path = profiles_path();
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let file = ProfilesFile { profiles: profiles.to_vec() };
+    let content = toml::to_string_pretty(&file).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))?;
+
+    fs::write(path, content)
+}
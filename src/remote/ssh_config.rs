@@ -0,0 +1,210 @@
+use crate::listing::pattern::wildcard_match;
+use crate::xdg::home_dir;
+
+use std::{
+    fs,
+    path::PathBuf
+};
+
+// параметри хоста з ~/.ssh/config; як і в ssh, перше знайдене значення має перевагу
+#[derive(Debug, Clone, Default)]
+pub struct SshHost {
+    pub host_name: Option<String>,
+    pub user: Option<String>,
+    pub port: Option<u16>,
+    pub identity_files: Vec<PathBuf>,
+}
+
+pub fn ssh_config_path() -> PathBuf {
+    home_dir().join(".ssh/config")
+}
+
+fn expand_tilde(value: &str) -> PathBuf {
+    match value.strip_prefix("~/") {
+        Some(rest) => home_dir().join(rest),
+        None => PathBuf::from(value),
+    }
+}
+
+// "Keyword value" або "Keyword=value", значення можуть бути в лапках
+fn parse_line(line: &str) -> Option<(String, Vec<String>)> {
+    let line = line.trim();
+
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let split_at = line.find(|symbol: char| symbol.is_whitespace() || symbol == '=')?;
+    let (keyword, rest) = line.split_at(split_at);
+    let rest = rest.trim_start().strip_prefix('=').unwrap_or(rest).trim();
+
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+
+    for symbol in rest.chars() {
+        match symbol {
+            '"' => quoted = !quoted,
+            symbol if symbol.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    args.push(std::mem::take(&mut current));
+                }
+            },
+            symbol => current.push(symbol),
+        }
+    }
+
+    if !current.is_empty() {
+        args.push(current);
+    }
+
+    Some((keyword.to_lowercase(), args))
+}
+
+// "!шаблон" виключає хост, навіть якщо інший шаблон у рядку його вибирає
+fn host_matches(patterns: &[String], alias: &str) -> bool {
+    let mut matched = false;
+
+    for pattern in patterns {
+        if let Some(negated) = pattern.strip_prefix('!') {
+            if wildcard_match(negated, alias) {
+                return false;
+            }
+        } else if wildcard_match(pattern, alias) {
+            matched = true;
+        }
+    }
+
+    matched
+}
+
+fn read_config() -> Vec<(String, Vec<String>)> {
+    fs::read_to_string(ssh_config_path())
+        .map(|text| text.lines().filter_map(parse_line).collect())
+        .unwrap_or_default()
+}
+
+pub fn resolve_host(alias: &str) -> SshHost {
+    resolve_in(read_config(), alias)
+}
+
+// блоки Match і директиву Include не розбираємо, вони потрібні рідко для простих аліасів
+fn resolve_in(config: Vec<(String, Vec<String>)>, alias: &str) -> SshHost {
+    let mut host = SshHost::default();
+    let mut active = true;
+
+    for (keyword, args) in config {
+        match keyword.as_str() {
+            "host" => active = host_matches(&args, alias),
+            "match" => active = false,
+            _ if !active => {},
+            "hostname" => host.host_name = host.host_name.take().or_else(|| args.first().cloned()),
+            "user" => host.user = host.user.take().or_else(|| args.first().cloned()),
+            "port" => host.port = host.port.or_else(|| args.first().and_then(|port| port.parse().ok())),
+            "identityfile" => {
+                if let Some(file) = args.first() {
+                    host.identity_files.push(expand_tilde(file));
+                }
+            },
+            _ => {},
+        }
+    }
+
+    host
+}
+
+// аліаси без шаблонів, для підказок у діалозі підключення
+pub fn host_aliases() -> Vec<String> {
+    let mut aliases: Vec<String> = Vec::new();
+
+    for (keyword, args) in read_config() {
+        if keyword != "host" {
+            continue;
+        }
+
+        for alias in args {
+            if !alias.contains(['*', '?', '!']) && !aliases.contains(&alias) {
+                aliases.push(alias);
+            }
+        }
+    }
+
+    aliases
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Vec<(String, Vec<String>)> {
+        text.lines().filter_map(parse_line).collect()
+    }
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_line_handles_separators_and_quotes() {
+        assert_eq!(parse_line("  HostName example.com  "), Some(("hostname".to_string(), args(&["example.com"]))));
+        assert_eq!(parse_line("Port=2222"), Some(("port".to_string(), args(&["2222"]))));
+        assert_eq!(parse_line("User = admin"), Some(("user".to_string(), args(&["admin"]))));
+        assert_eq!(parse_line("Host web db-*  !db-old"), Some(("host".to_string(), args(&["web", "db-*", "!db-old"]))));
+        assert_eq!(parse_line(r#"IdentityFile "~/keys/my key""#), Some(("identityfile".to_string(), args(&["~/keys/my key"]))));
+        assert_eq!(parse_line("# коментар"), None);
+        assert_eq!(parse_line("   "), None);
+        assert_eq!(parse_line("Keyword"), None);
+    }
+
+    #[test]
+    fn first_value_wins_across_matching_blocks() {
+        let config = parse("
+            Host web
+                HostName web.example.com
+                Port 2222
+
+            Host *
+                User deploy
+                Port 22
+                IdentityFile ~/.ssh/id_work
+        ");
+
+        let host = resolve_in(config.clone(), "web");
+
+        assert_eq!(host.host_name.as_deref(), Some("web.example.com"));
+        assert_eq!(host.port, Some(2222));
+        assert_eq!(host.user.as_deref(), Some("deploy"));
+        assert_eq!(host.identity_files, [home_dir().join(".ssh/id_work")]);
+
+        let other = resolve_in(config, "other");
+
+        assert_eq!(other.host_name, None);
+        assert_eq!(other.port, Some(22));
+    }
+
+    #[test]
+    fn negated_patterns_and_match_blocks_exclude_hosts() {
+        let config = parse("
+            User global
+            Host db-* !db-old
+                HostName db.internal
+            Match user root
+                Port 9999
+        ");
+
+        assert_eq!(resolve_in(config.clone(), "db-main").host_name.as_deref(), Some("db.internal"));
+        assert_eq!(resolve_in(config.clone(), "db-old").host_name, None);
+        // до першого Host параметри стосуються всіх хостів, блок Match пропускається
+        assert_eq!(resolve_in(config.clone(), "db-old").user.as_deref(), Some("global"));
+        assert_eq!(resolve_in(config, "db-main").port, None);
+    }
+
+    #[test]
+    fn host_patterns_match_like_ssh() {
+        assert!(host_matches(&args(&["*.example.com"]), "git.example.com"));
+        assert!(host_matches(&args(&["web?"]), "web1"));
+        assert!(!host_matches(&args(&["web?"]), "web10"));
+        assert!(!host_matches(&args(&["*", "!local*"]), "localhost"));
+        assert!(!host_matches(&args(&["!other"]), "host"));
+    }
+}
@@ -0,0 +1,115 @@
+use crate::listing::sort::SortOrder;
+use crate::tabs::tab::ViewMode;
+use crate::xdg::config_home;
+
+use serde::{Deserialize, Serialize};
+
+use std::{
+    fs,
+    io,
+    io::ErrorKind,
+    ops::RangeInclusive,
+    path::PathBuf
+};
+
+// межі ширини бічної панелі, спільні для поля в налаштуваннях і перетягування краю панелі
+pub const SIDEBAR_WIDTH_RANGE: RangeInclusive<f32> = 100.0..=800.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub dark_mode: bool,
+    pub view_mode: ViewMode,
+    pub sort_order: SortOrder,
+    pub window_size: Option<[f32; 2]>,
+    pub sidebar_width: Option<f32>,
+    pub show_preview: bool,
+    // якщо вимкнено, програма стартує в start_dir (або в робочій папці)
+    pub restore_session: bool,
+    pub start_dir: Option<PathBuf>,
+    pub session: Session,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub tabs: Vec<PathBuf>,
+    pub active_tab: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            dark_mode: true,
+            view_mode: ViewMode::List,
+            sort_order: SortOrder::default(),
+            window_size: None,
+            sidebar_width: None,
+            show_preview: false,
+            restore_session: true,
+            start_dir: None,
+            session: Session::default(),
+        }
+    }
+}
+
+pub fn config_path() -> PathBuf {
+    config_home().join("file_explorer").join("config.toml")
+}
+
+pub fn load_settings() -> Settings {
+    let content = match fs::read_to_string(config_path()) {
+        Ok(content) => content,
+        Err(_) => return Settings::default(),
+    };
+
+    match toml::from_str(&content) {
+        Ok(settings) => settings,
+        Err(err) => {
+            println!("не вдалося прочитати налаштування: {}", err);
+
+            Settings::default()
+        }
+    }
+}
+
+pub fn save_settings(settings: &Settings) -> io::Result<()> {
+    let path = config_path();
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let content = toml::to_string_pretty(settings).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))?;
+
+    fs::write(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_fields_take_defaults() {
+        let settings: Settings = toml::from_str("dark_mode = false\nsidebar_width = 240.0\n").unwrap();
+
+        assert!(!settings.dark_mode);
+        assert_eq!(settings.sidebar_width, Some(240.0));
+        assert!(settings.restore_session);
+        assert_eq!(settings.session, Session::default());
+    }
+
+    #[test]
+    fn settings_round_trip() {
+        let settings = Settings {
+            window_size: Some([1280.0, 720.0]),
+            start_dir: Some(PathBuf::from("/tmp")),
+            session: Session { tabs: vec![PathBuf::from("/a"), PathBuf::from("/b")], active_tab: 1 },
+            ..Settings::default()
+        };
+
+        let content = toml::to_string_pretty(&settings).unwrap();
+
+        assert_eq!(toml::from_str::<Settings>(&content).unwrap(), settings);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::xdg::config_home;
+
+use std::{
+    fs,
+    io,
+    path::Path,
+    path::PathBuf
+};
+
+// закладки зберігаються у форматі gtk: "file:///шлях/у/uri-кодуванні необов'язкова назва"
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bookmark {
+    pub uri: String,
+    pub label: Option<String>,
+}
+
+impl Bookmark {
+    pub fn from_path(path: &Path) -> Self {
+        Self {
+            uri: format!("file://{}", encode_uri_path(&path.to_string_lossy())),
+            label: None,
+        }
+    }
+
+    // None для не локальних закладок (sftp://, smb:// ...), вони лише зберігаються без змін
+    pub fn path(&self) -> Option<PathBuf> {
+        self.uri.strip_prefix("file://").map(|path| PathBuf::from(decode_uri_path(path)))
+    }
+
+    pub fn title(&self) -> String {
+        if let Some(label) = &self.label {
+            return label.clone();
+        }
+
+        match self.path() {
+            Some(path) => path.file_name().unwrap_or(path.as_os_str()).to_string_lossy().to_string(),
+            None => self.uri.clone(),
+        }
+    }
+}
+
+fn bookmarks_path() -> PathBuf {
+    config_home().join("gtk-3.0").join("bookmarks")
+}
+
+pub fn load_bookmarks() -> Vec<Bookmark> {
+    let content = fs::read_to_string(bookmarks_path()).unwrap_or_default();
+
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| match line.split_once(' ') {
+            Some((uri, label)) => Bookmark { uri: uri.to_string(), label: Some(label.to_string()) },
+            None => Bookmark { uri: line.to_string(), label: None },
+        })
+        .collect()
+}
+
+pub fn save_bookmarks(bookmarks: &[Bookmark]) -> io::Result<()> {
+    let path = bookmarks_path();
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut content = String::new();
+
+    for bookmark in bookmarks {
+        content.push_str(&bookmark.uri);
+
+        if let Some(label) = &bookmark.label {
+            content.push(' ');
+            content.push_str(label);
+        }
+
+        content.push('\n');
+    }
+
+    fs::write(path, content)
+}
+
+fn encode_uri_path(path: &str) -> String {
+    let mut encoded = String::new();
+
+    for byte in path.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char);
+            },
+            _ => {
+                encoded.push_str(&format!("%{:02X}", byte));
+            }
+        }
+    }
+
+    encoded
+}
+
+fn decode_uri_path(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index] == b'%'
+            && let Some(hex) = bytes.get(index + 1..index + 3)
+            && let Ok(hex) = std::str::from_utf8(hex)
+            && let Ok(byte) = u8::from_str_radix(hex, 16)
+        {
+            decoded.push(byte);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uri_round_trip_keeps_unicode_and_spaces() {
+        let bookmark = Bookmark::from_path(Path::new("/home/user/Мої документи"));
+
+        assert_eq!(bookmark.uri, "file:///home/user/%D0%9C%D0%BE%D1%97%20%D0%B4%D0%BE%D0%BA%D1%83%D0%BC%D0%B5%D0%BD%D1%82%D0%B8");
+        assert_eq!(bookmark.path(), Some(PathBuf::from("/home/user/Мої документи")));
+        assert_eq!(bookmark.title(), "Мої документи");
+    }
+
+    #[test]
+    fn remote_bookmarks_have_no_path() {
+        let bookmark = Bookmark { uri: "sftp://host/dir".to_string(), label: None };
+
+        assert_eq!(bookmark.path(), None);
+        assert_eq!(bookmark.title(), "sftp://host/dir");
+    }
+
+    #[test]
+    fn label_overrides_title_and_bad_escapes_are_kept() {
+        let bookmark = Bookmark { uri: "file:///tmp/100%zz".to_string(), label: Some("Тимчасові".to_string()) };
+
+        assert_eq!(bookmark.title(), "Тимчасові");
+        assert_eq!(bookmark.path(), Some(PathBuf::from("/tmp/100%zz")));
+    }
+}
@@ -0,0 +1,56 @@
+use crate::navigation::path_bar::subfolders;
+
+use std::{
+    collections::HashMap,
+    path::Path,
+    path::PathBuf
+};
+
+// дерево папок лівої панелі, підпапки читаються лише коли вузол розгорнуто
+#[derive(Debug, Default)]
+pub struct FolderTree {
+    children: HashMap<PathBuf, Vec<PathBuf>>,
+    show_hidden: bool,
+    revealed: Option<PathBuf>,
+}
+
+impl FolderTree {
+    pub fn children(&mut self, path: &Path) -> Vec<PathBuf> {
+        let show_hidden = self.show_hidden;
+
+        self.children
+            .entry(path.to_path_buf())
+            .or_insert_with(|| subfolders(path, show_hidden))
+            .clone()
+    }
+
+    // згорнутий вузол перечитується при наступному розгортанні
+    pub fn forget(&mut self, path: &Path) {
+        self.children.retain(|cached, _| !cached.starts_with(path));
+    }
+
+    pub fn refresh(&mut self) {
+        self.children.clear();
+    }
+
+    pub fn show_hidden(&self) -> bool {
+        self.show_hidden
+    }
+
+    pub fn set_show_hidden(&mut self, show_hidden: bool) {
+        self.show_hidden = show_hidden;
+
+        self.refresh();
+    }
+
+    // повертає true один раз після зміни поточної папки, щоб розгорнути шлях до неї
+    pub fn needs_reveal(&mut self, current_dir: &Path) -> bool {
+        if self.revealed.as_deref() == Some(current_dir) {
+            return false;
+        }
+
+        self.revealed = Some(current_dir.to_path_buf());
+
+        true
+    }
+}
@@ -0,0 +1,3 @@
+pub mod bookmarks;
+pub mod folder_tree;
+pub mod places;
@@ -0,0 +1,31 @@
+use crate::vfs::trash::TRASH_ROOT;
+use crate::xdg::{home_dir, user_dirs::user_dir};
+
+use std::path::PathBuf;
+
+// стандартні папки користувача з user-dirs.dirs, показуються лише ті, що існують; кошик — завжди останній
+pub fn places() -> Vec<(String, PathBuf)> {
+    let mut places: Vec<(String, PathBuf)> = vec![("Домашня папка".to_string(), home_dir())];
+
+    let user_dirs = [
+        ("DESKTOP", "Робочий стіл"),
+        ("DOCUMENTS", "Документи"),
+        ("DOWNLOAD", "Завантаження"),
+        ("MUSIC", "Музика"),
+        ("PICTURES", "Зображення"),
+        ("VIDEOS", "Відео"),
+    ];
+
+    for (name, label) in user_dirs {
+        if let Some(path) = user_dir(name)
+            && path.is_dir()
+            && path != home_dir()
+        {
+            places.push((label.to_string(), path));
+        }
+    }
+
+    places.push(("Кошик".to_string(), PathBuf::from(TRASH_ROOT)));
+
+    places
+}
@@ -0,0 +1,134 @@
+use crate::navigation::history::History;
+
+use serde::{Deserialize, Serialize};
+
+use std::path::{Path, PathBuf};
+
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ViewMode {
+    List,
+    Grid,
+    Details
+}
+
+#[derive(Debug, Clone)]
+pub struct Tab {
+    pub current_dir: PathBuf,
+    pub history: History,
+    pub selected_item: PathBuf,
+    // елементи, виділені через Ctrl+клацання
+    pub marked: Vec<PathBuf>,
+    pub is_disk_selection: bool,
+    pub view_mode: ViewMode,
+    pub scroll_offset: f32,
+    // після перемикання вкладки треба відновити її прокрутку
+    pub restore_scroll: bool,
+}
+
+impl Tab {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            current_dir: path.clone(),
+            history: History::default(),
+            selected_item: path,
+            marked: Vec::new(),
+            is_disk_selection: false,
+            view_mode: ViewMode::List,
+            scroll_offset: 0.0,
+            restore_scroll: true,
+        }
+    }
+
+    pub fn is_selected(&self, path: &Path) -> bool {
+        self.selected_item == path || self.marked.iter().any(|marked| marked == path)
+    }
+
+    pub fn select(&mut self, path: PathBuf) {
+        self.selected_item = path;
+        self.marked.clear();
+    }
+
+    pub fn toggle_marked(&mut self, path: PathBuf) {
+        if self.marked.is_empty() && self.selected_item.parent() == path.parent() && self.selected_item != path {
+            self.marked.push(self.selected_item.clone());
+        }
+
+        match self.marked.iter().position(|marked| *marked == path) {
+            Some(index) => {
+                self.marked.remove(index);
+            },
+            None => self.marked.push(path.clone()),
+        }
+
+        self.selected_item = path;
+    }
+
+    // дія з контекстного меню стосується всього виділення, якщо елемент у нього входить
+    pub fn selection_for(&self, path: &Path) -> Vec<PathBuf> {
+        if self.marked.iter().any(|marked| marked == path) {
+            self.marked.clone()
+        } else {
+            vec![path.to_path_buf()]
+        }
+    }
+
+    pub fn title(&self) -> String {
+        match self.current_dir.file_name() {
+            Some(name) => name.to_string_lossy().to_string(),
+            None => self.current_dir.to_string_lossy().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_ctrl_click_keeps_the_selected_sibling() {
+        let mut tab = Tab::new(PathBuf::from("/home"));
+
+        tab.select(PathBuf::from("/home/a"));
+        tab.toggle_marked(PathBuf::from("/home/b"));
+
+        assert_eq!(tab.marked, [PathBuf::from("/home/a"), PathBuf::from("/home/b")]);
+        assert_eq!(tab.selected_item, PathBuf::from("/home/b"));
+        assert!(tab.is_selected(Path::new("/home/a")));
+
+        tab.toggle_marked(PathBuf::from("/home/a"));
+
+        assert_eq!(tab.marked, [PathBuf::from("/home/b")]);
+        assert!(!tab.is_selected(Path::new("/home/c")));
+
+        tab.select(PathBuf::from("/home/c"));
+
+        assert!(tab.marked.is_empty());
+    }
+
+    #[test]
+    fn ctrl_click_in_another_folder_starts_a_new_selection() {
+        let mut tab = Tab::new(PathBuf::from("/home"));
+
+        tab.select(PathBuf::from("/etc/hosts"));
+        tab.toggle_marked(PathBuf::from("/home/a"));
+
+        assert_eq!(tab.marked, [PathBuf::from("/home/a")]);
+    }
+
+    #[test]
+    fn selection_falls_back_to_the_clicked_item() {
+        let mut tab = Tab::new(PathBuf::from("/home"));
+
+        tab.select(PathBuf::from("/home/a"));
+        tab.toggle_marked(PathBuf::from("/home/b"));
+
+        assert_eq!(tab.selection_for(Path::new("/home/b")), [PathBuf::from("/home/a"), PathBuf::from("/home/b")]);
+        assert_eq!(tab.selection_for(Path::new("/home/c")), [PathBuf::from("/home/c")]);
+    }
+
+    #[test]
+    fn title_is_the_folder_name_or_the_whole_path() {
+        assert_eq!(Tab::new(PathBuf::from("/home/user/Документи")).title(), "Документи");
+        assert_eq!(Tab::new(PathBuf::from("/")).title(), "/");
+    }
+}
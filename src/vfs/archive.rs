@@ -0,0 +1,116 @@
+use super::{entry_name, read_only_error, Vfs, VfsEntry};
+use crate::archive::reader::{children, for_each_entry, list_entries, ArchiveEntry};
+
+use std::{
+    io,
+    io::{Cursor, ErrorKind, Read, Write},
+    path::{Path, PathBuf}
+};
+
+// архів як папка лише для читання; шляхи мають вигляд /шлях/до/архіву.zip/папка
+#[derive(Debug)]
+pub struct ArchiveVfs {
+    archive: PathBuf,
+    entries: Vec<ArchiveEntry>,
+}
+
+impl ArchiveVfs {
+    pub fn open(archive: &Path) -> io::Result<Self> {
+        Ok(Self {
+            archive: archive.to_path_buf(),
+            entries: list_entries(archive)?,
+        })
+    }
+
+    fn inner<'a>(&self, path: &'a Path) -> io::Result<&'a Path> {
+        path.strip_prefix(&self.archive)
+            .map_err(|_| io::Error::new(ErrorKind::NotFound, format!("{} не належить архіву", path.display())))
+    }
+
+    fn to_entry(&self, entry: &ArchiveEntry) -> VfsEntry {
+        VfsEntry {
+            path: self.archive.join(&entry.path),
+            name: entry_name(&entry.path),
+            is_dir: entry.is_dir,
+            size: entry.size,
+            modified: entry.modified,
+        }
+    }
+}
+
+impl Vfs for ArchiveVfs {
+    fn list(&self, dir: &Path) -> io::Result<Vec<VfsEntry>> {
+        let inner = self.inner(dir)?;
+
+        if !self.stat(dir)?.is_dir {
+            return Err(io::Error::new(ErrorKind::NotADirectory, format!("{} не є папкою", dir.display())));
+        }
+
+        Ok(children(&self.entries, inner).into_iter().map(|entry| self.to_entry(entry)).collect())
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<VfsEntry> {
+        let inner = self.inner(path)?;
+
+        if inner.as_os_str().is_empty() {
+            return Ok(VfsEntry {
+                path: self.archive.clone(),
+                name: entry_name(&self.archive),
+                is_dir: true,
+                size: 0,
+                modified: None,
+            });
+        }
+
+        self.entries
+            .iter()
+            .find(|entry| entry.path == inner)
+            .map(|entry| self.to_entry(entry))
+            .ok_or_else(|| io::Error::new(ErrorKind::NotFound, format!("{} не знайдено в архіві", path.display())))
+    }
+
+    // записи стиснутого tar можна прочитати лише по порядку, тому вміст одразу читається в пам'ять
+    fn read(&self, path: &Path) -> io::Result<Box<dyn Read + Send>> {
+        let inner = self.inner(path)?;
+        let mut content = None;
+
+        for_each_entry(&self.archive, |entry, data| {
+            if content.is_none()
+                && let Ok(entry) = entry
+                && entry.path == inner
+                && !entry.is_dir
+            {
+                let mut buffer = Vec::new();
+                data.read_to_end(&mut buffer)?;
+                content = Some(buffer);
+            }
+
+            Ok(())
+        })?;
+
+        match content {
+            Some(content) => Ok(Box::new(Cursor::new(content))),
+            None => Err(io::Error::new(ErrorKind::NotFound, format!("{} не знайдено в архіві", path.display()))),
+        }
+    }
+
+    fn write(&self, path: &Path) -> io::Result<Box<dyn Write + Send>> {
+        Err(read_only_error(path))
+    }
+
+    fn rename(&self, from: &Path, _to: &Path) -> io::Result<()> {
+        Err(read_only_error(from))
+    }
+
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        Err(read_only_error(path))
+    }
+
+    fn mkdir(&self, path: &Path) -> io::Result<()> {
+        Err(read_only_error(path))
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+}
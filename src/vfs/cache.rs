@@ -0,0 +1,34 @@
+use super::VfsEntry;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant}
+};
+
+// список папки показується кожен кадр, тому мережеві бекенди не ходять за ним на сервер щоразу
+const LISTING_TTL: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Default)]
+pub struct ListingCache {
+    listings: Mutex<HashMap<PathBuf, (Instant, Vec<VfsEntry>)>>,
+}
+
+impl ListingCache {
+    pub fn get(&self, dir: &Path) -> Option<Vec<VfsEntry>> {
+        self.listings.lock().unwrap()
+            .get(dir)
+            .filter(|(listed, _)| listed.elapsed() < LISTING_TTL)
+            .map(|(_, entries)| entries.clone())
+    }
+
+    pub fn insert(&self, dir: &Path, entries: &[VfsEntry]) {
+        self.listings.lock().unwrap().insert(dir.to_path_buf(), (Instant::now(), entries.to_vec()));
+    }
+
+    // після змін на сервері простіше забути все, ніж шукати зачеплені папки
+    pub fn clear(&self) {
+        self.listings.lock().unwrap().clear();
+    }
+}
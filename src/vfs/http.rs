@@ -0,0 +1,119 @@
+use crate::xdg::cache_home;
+
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+use std::{
+    fs,
+    fs::File,
+    io,
+    io::{ErrorKind, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+    process,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration
+};
+
+// екрануємо все, крім символів, які RFC 3986 дозволяє залишати як є
+const SEGMENT: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'_').remove(b'.').remove(b'~');
+
+static SPOOL_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+pub type Upload = Box<dyn FnOnce(File, u64) -> io::Result<()> + Send>;
+
+pub fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(10))
+        .timeout_read(Duration::from_secs(60))
+        .build()
+}
+
+pub fn encode_segment(segment: &str) -> String {
+    utf8_percent_encode(segment, SEGMENT).to_string()
+}
+
+pub fn decode(text: &str) -> String {
+    percent_decode_str(text).decode_utf8_lossy().to_string()
+}
+
+// відносний шлях у вигляді "a/b%20c" для адреси на сервері
+pub fn encode_path(relative: &Path) -> String {
+    relative.components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(encode_segment(&name.to_string_lossy())),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+pub fn http_error(err: ureq::Error) -> io::Error {
+    match err {
+        ureq::Error::Status(code, response) => {
+            let kind = match code {
+                401 | 403 => ErrorKind::PermissionDenied,
+                404 => ErrorKind::NotFound,
+                412 => ErrorKind::AlreadyExists,
+                _ => ErrorKind::Other,
+            };
+
+            io::Error::new(kind, format!("сервер відповів {} {}", code, response.status_text()))
+        },
+        ureq::Error::Transport(transport) => io::Error::other(transport.to_string()),
+    }
+}
+
+// вміст спершу пишеться у тимчасовий файл, щоб сервер отримав його з відомою довжиною.
+// на сервер він потрапляє лише під час flush: недописаний (скасований) файл просто відкидається
+pub struct SpoolWriter {
+    spool_path: PathBuf,
+    file: Option<File>,
+    upload: Option<Upload>,
+}
+
+impl SpoolWriter {
+    pub fn new(upload: Upload) -> io::Result<Self> {
+        let spool_dir = cache_home().join("file_explorer/upload");
+
+        fs::create_dir_all(&spool_dir)?;
+
+        let spool_path = spool_dir.join(format!("{}-{}", process::id(), SPOOL_COUNTER.fetch_add(1, Ordering::Relaxed)));
+        let file = File::options().read(true).write(true).create_new(true).open(&spool_path)?;
+
+        Ok(Self {
+            spool_path,
+            file: Some(file),
+            upload: Some(upload),
+        })
+    }
+}
+
+impl Write for SpoolWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.file {
+            Some(file) => file.write(buf),
+            None => Err(io::Error::new(ErrorKind::BrokenPipe, "файл вже надіслано")),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let (Some(mut file), Some(upload)) = (self.file.take(), self.upload.take()) else {
+            return Ok(());
+        };
+
+        file.flush()?;
+
+        let length = file.seek(SeekFrom::End(0))?;
+        file.seek(SeekFrom::Start(0))?;
+
+        let result = upload(file, length);
+        let _ = fs::remove_file(&self.spool_path);
+
+        result
+    }
+}
+
+impl Drop for SpoolWriter {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.spool_path);
+    }
+}
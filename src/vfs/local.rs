@@ -0,0 +1,76 @@
+use super::{entry_name, Vfs, VfsEntry};
+
+use std::{
+    fs,
+    fs::{File, Metadata},
+    io,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    time::SystemTime
+};
+
+#[derive(Debug, Default)]
+pub struct LocalVfs;
+
+fn to_entry(path: PathBuf, metadata: &Metadata) -> VfsEntry {
+    VfsEntry {
+        name: entry_name(&path),
+        is_dir: metadata.is_dir(),
+        size: metadata.len(),
+        modified: metadata.modified().ok(),
+        path,
+    }
+}
+
+impl Vfs for LocalVfs {
+    fn list(&self, dir: &Path) -> io::Result<Vec<VfsEntry>> {
+        let mut entries = Vec::new();
+
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+
+            // недійсне посилання показуємо як є, а не пропускаємо
+            if let Ok(metadata) = fs::metadata(&path).or_else(|_| fs::symlink_metadata(&path)) {
+                entries.push(to_entry(path, &metadata));
+            }
+        }
+
+        Ok(entries)
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<VfsEntry> {
+        Ok(to_entry(path.to_path_buf(), &fs::metadata(path)?))
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Box<dyn Read + Send>> {
+        Ok(Box::new(File::open(path)?))
+    }
+
+    fn write(&self, path: &Path) -> io::Result<Box<dyn Write + Send>> {
+        Ok(Box::new(File::create(path)?))
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        fs::rename(from, to)
+    }
+
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        if fs::symlink_metadata(path)?.is_dir() {
+            fs::remove_dir_all(path)
+        } else {
+            fs::remove_file(path)
+        }
+    }
+
+    fn mkdir(&self, path: &Path) -> io::Result<()> {
+        fs::create_dir(path)
+    }
+
+    fn set_modified(&self, path: &Path, modified: SystemTime) -> io::Result<()> {
+        File::options().write(true).open(path)?.set_modified(modified)
+    }
+
+    fn local_path(&self, path: &Path) -> Option<PathBuf> {
+        Some(path.to_path_buf())
+    }
+}
@@ -0,0 +1,292 @@
+use super::{entry_name, Vfs, VfsEntry};
+
+use std::{
+    collections::BTreeMap,
+    io,
+    io::{Cursor, ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime
+};
+
+#[derive(Debug, Clone)]
+enum MemoryNode {
+    Dir,
+    File(Arc<Vec<u8>>)
+}
+
+type Nodes = Arc<Mutex<BTreeMap<PathBuf, (MemoryNode, SystemTime)>>>;
+
+// файлова система в пам'яті: для перевірки коду, що працює з Vfs, без звернень до диска.
+// корінь "/" існує завжди
+#[derive(Debug, Default, Clone)]
+pub struct MemoryVfs {
+    nodes: Nodes,
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(ErrorKind::NotFound, format!("{} не знайдено", path.display()))
+}
+
+fn is_root(path: &Path) -> bool {
+    path.parent().is_none()
+}
+
+impl MemoryVfs {
+    fn check_parent(nodes: &BTreeMap<PathBuf, (MemoryNode, SystemTime)>, path: &Path) -> io::Result<()> {
+        match path.parent() {
+            Some(parent) if is_root(parent) => Ok(()),
+            Some(parent) => match nodes.get(parent) {
+                Some((MemoryNode::Dir, _)) => Ok(()),
+                Some(_) => Err(io::Error::new(ErrorKind::NotADirectory, format!("{} не є папкою", parent.display()))),
+                None => Err(not_found(parent)),
+            },
+            None => Err(io::Error::new(ErrorKind::InvalidInput, "корінь не можна змінити")),
+        }
+    }
+
+    fn to_entry(path: &Path, node: &MemoryNode, modified: SystemTime) -> VfsEntry {
+        VfsEntry {
+            path: path.to_path_buf(),
+            name: entry_name(path),
+            is_dir: matches!(node, MemoryNode::Dir),
+            size: match node {
+                MemoryNode::File(data) => data.len() as u64,
+                MemoryNode::Dir => 0,
+            },
+            modified: Some(modified),
+        }
+    }
+}
+
+// вміст потрапляє у файлову систему, коли запис завершено
+struct MemoryWriter {
+    nodes: Nodes,
+    path: PathBuf,
+    data: Vec<u8>,
+}
+
+impl Write for MemoryWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+
+        nodes.insert(self.path.clone(), (MemoryNode::File(Arc::new(self.data.clone())), SystemTime::now()));
+
+        Ok(())
+    }
+}
+
+impl Drop for MemoryWriter {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+impl Vfs for MemoryVfs {
+    fn list(&self, dir: &Path) -> io::Result<Vec<VfsEntry>> {
+        let nodes = self.nodes.lock().unwrap();
+
+        if !is_root(dir) {
+            match nodes.get(dir) {
+                Some((MemoryNode::Dir, _)) => {},
+                Some(_) => return Err(io::Error::new(ErrorKind::NotADirectory, format!("{} не є папкою", dir.display()))),
+                None => return Err(not_found(dir)),
+            }
+        }
+
+        Ok(nodes
+            .iter()
+            .filter(|(path, _)| path.parent() == Some(dir))
+            .map(|(path, (node, modified))| Self::to_entry(path, node, *modified))
+            .collect())
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<VfsEntry> {
+        if is_root(path) {
+            return Ok(Self::to_entry(path, &MemoryNode::Dir, SystemTime::UNIX_EPOCH));
+        }
+
+        let nodes = self.nodes.lock().unwrap();
+        let (node, modified) = nodes.get(path).ok_or_else(|| not_found(path))?;
+
+        Ok(Self::to_entry(path, node, *modified))
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Box<dyn Read + Send>> {
+        let nodes = self.nodes.lock().unwrap();
+
+        match nodes.get(path) {
+            Some((MemoryNode::File(data), _)) => Ok(Box::new(Cursor::new(data.as_ref().clone()))),
+            Some(_) => Err(io::Error::new(ErrorKind::IsADirectory, format!("{} є папкою", path.display()))),
+            None => Err(not_found(path)),
+        }
+    }
+
+    fn write(&self, path: &Path) -> io::Result<Box<dyn Write + Send>> {
+        let mut nodes = self.nodes.lock().unwrap();
+
+        Self::check_parent(&nodes, path)?;
+
+        if let Some((MemoryNode::Dir, _)) = nodes.get(path) {
+            return Err(io::Error::new(ErrorKind::IsADirectory, format!("{} є папкою", path.display())));
+        }
+
+        nodes.insert(path.to_path_buf(), (MemoryNode::File(Arc::new(Vec::new())), SystemTime::now()));
+
+        Ok(Box::new(MemoryWriter {
+            nodes: self.nodes.clone(),
+            path: path.to_path_buf(),
+            data: Vec::new(),
+        }))
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+
+        if !nodes.contains_key(from) {
+            return Err(not_found(from));
+        }
+
+        if to.starts_with(from) {
+            return Err(io::Error::new(ErrorKind::InvalidInput, "не можна перемістити папку саму в себе"));
+        }
+
+        Self::check_parent(&nodes, to)?;
+
+        let moved: Vec<PathBuf> = nodes.keys().filter(|path| path.starts_with(from)).cloned().collect();
+
+        for path in moved {
+            if let Some(node) = nodes.remove(&path) {
+                let relative = path.strip_prefix(from).unwrap_or(Path::new(""));
+                let target = if relative.as_os_str().is_empty() { to.to_path_buf() } else { to.join(relative) };
+
+                nodes.insert(target, node);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+
+        if nodes.remove(path).is_none() {
+            return Err(not_found(path));
+        }
+
+        nodes.retain(|node_path, _| !node_path.starts_with(path));
+
+        Ok(())
+    }
+
+    fn mkdir(&self, path: &Path) -> io::Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+
+        Self::check_parent(&nodes, path)?;
+
+        if nodes.contains_key(path) {
+            return Err(io::Error::new(ErrorKind::AlreadyExists, format!("{} вже існує", path.display())));
+        }
+
+        nodes.insert(path.to_path_buf(), (MemoryNode::Dir, SystemTime::now()));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::listing::sort::{sort_entries, SortKey, SortOrder};
+
+    fn write_file(vfs: &MemoryVfs, path: &str, data: &str) {
+        let mut writer = vfs.write(Path::new(path)).unwrap();
+        writer.write_all(data.as_bytes()).unwrap();
+    }
+
+    fn read_file(vfs: &MemoryVfs, path: &str) -> String {
+        let mut data = String::new();
+        vfs.read(Path::new(path)).unwrap().read_to_string(&mut data).unwrap();
+
+        data
+    }
+
+    fn names(entries: Vec<VfsEntry>) -> Vec<String> {
+        entries.into_iter().map(|entry| entry.name).collect()
+    }
+
+    #[test]
+    fn listing_is_sorted_with_folders_first() {
+        let vfs = MemoryVfs::default();
+
+        write_file(&vfs, "/b.txt", "bb");
+        write_file(&vfs, "/A.txt", "aaaa");
+        vfs.mkdir(Path::new("/zeta")).unwrap();
+        vfs.mkdir(Path::new("/alpha")).unwrap();
+        write_file(&vfs, "/alpha/nested.txt", "n");
+
+        let entries = vfs.list(Path::new("/")).unwrap();
+
+        assert_eq!(names(sort_entries(entries.clone(), SortOrder::default())), ["alpha", "zeta", "A.txt", "b.txt"]);
+        assert_eq!(
+            names(sort_entries(entries, SortOrder { key: SortKey::Size, descending: false })),
+            ["alpha", "zeta", "b.txt", "A.txt"]
+        );
+        assert_eq!(names(vfs.list(Path::new("/alpha")).unwrap()), ["nested.txt"]);
+        assert_eq!(vfs.list(Path::new("/b.txt")).unwrap_err().kind(), ErrorKind::NotADirectory);
+        assert_eq!(vfs.list(Path::new("/missing")).unwrap_err().kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn rename_moves_folder_contents() {
+        let vfs = MemoryVfs::default();
+
+        vfs.mkdir(Path::new("/docs")).unwrap();
+        vfs.mkdir(Path::new("/docs/inner")).unwrap();
+        write_file(&vfs, "/docs/inner/a.txt", "a");
+
+        vfs.rename(Path::new("/docs"), Path::new("/papers")).unwrap();
+
+        assert_eq!(read_file(&vfs, "/papers/inner/a.txt"), "a");
+        assert_eq!(vfs.stat(Path::new("/docs")).unwrap_err().kind(), ErrorKind::NotFound);
+        assert_eq!(vfs.rename(Path::new("/papers"), Path::new("/papers/inner/self")).unwrap_err().kind(), ErrorKind::InvalidInput);
+        assert_eq!(vfs.rename(Path::new("/missing"), Path::new("/other")).unwrap_err().kind(), ErrorKind::NotFound);
+        assert_eq!(vfs.rename(Path::new("/papers"), Path::new("/nowhere/papers")).unwrap_err().kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn remove_deletes_folder_with_contents() {
+        let vfs = MemoryVfs::default();
+
+        vfs.mkdir(Path::new("/docs")).unwrap();
+        vfs.mkdir(Path::new("/docs_backup")).unwrap();
+        write_file(&vfs, "/docs/a.txt", "a");
+
+        vfs.remove(Path::new("/docs")).unwrap();
+
+        assert_eq!(names(vfs.list(Path::new("/")).unwrap()), ["docs_backup"]);
+        assert_eq!(vfs.stat(Path::new("/docs/a.txt")).unwrap_err().kind(), ErrorKind::NotFound);
+        assert_eq!(vfs.remove(Path::new("/docs")).unwrap_err().kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn write_requires_existing_parent_folder() {
+        let vfs = MemoryVfs::default();
+
+        assert_eq!(vfs.write(Path::new("/missing/a.txt")).err().unwrap().kind(), ErrorKind::NotFound);
+
+        write_file(&vfs, "/a.txt", "old");
+        write_file(&vfs, "/a.txt", "new");
+
+        assert_eq!(read_file(&vfs, "/a.txt"), "new");
+        assert_eq!(vfs.stat(Path::new("/a.txt")).unwrap().size, 3);
+        assert_eq!(vfs.mkdir(Path::new("/a.txt")).unwrap_err().kind(), ErrorKind::AlreadyExists);
+        assert_eq!(vfs.write(Path::new("/a.txt/b")).err().unwrap().kind(), ErrorKind::NotADirectory);
+    }
+}
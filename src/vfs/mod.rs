@@ -0,0 +1,294 @@
+pub mod archive;
+pub mod cache;
+pub mod http;
+pub mod local;
+pub mod memory;
+pub mod s3;
+pub mod sftp;
+pub mod transfer;
+pub mod trash;
+pub mod webdav;
+
+use crate::archive::format::ArchiveFormat;
+use crate::xdg::cache_home;
+
+use archive::ArchiveVfs;
+use local::LocalVfs;
+use trash::{TrashVfs, TRASH_ROOT};
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fmt::Debug,
+    fs,
+    fs::File,
+    hash::{Hash, Hasher},
+    io,
+    io::{ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime}
+};
+
+// як довго вважаємо відомим, чи є шлях з розширенням архіву справжнім файлом
+const ARCHIVE_CHECK_TTL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone)]
+pub struct VfsEntry {
+    // повний шлях, за яким елемент відкривається в програмі
+    pub path: PathBuf,
+    pub name: String,
+    pub is_dir: bool,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+// спільний інтерфейс для локальних папок, архівів, кошика і віддалених серверів.
+// усі методи отримують повний шлях, корінь свого простору бекенд знає сам
+pub trait Vfs: Debug + Send + Sync {
+    fn list(&self, dir: &Path) -> io::Result<Vec<VfsEntry>>;
+    fn stat(&self, path: &Path) -> io::Result<VfsEntry>;
+    fn read(&self, path: &Path) -> io::Result<Box<dyn Read + Send>>;
+    // створює файл або перезаписує наявний
+    fn write(&self, path: &Path) -> io::Result<Box<dyn Write + Send>>;
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()>;
+    // папки видаляються разом із вмістом
+    fn remove(&self, path: &Path) -> io::Result<()>;
+    fn mkdir(&self, path: &Path) -> io::Result<()>;
+
+    // бекенди без підтримки часу зміни просто лишають поточний
+    fn set_modified(&self, _path: &Path, _modified: SystemTime) -> io::Result<()> {
+        Ok(())
+    }
+
+    // скидає кешований вміст папки, якщо бекенд його кешує
+    fn invalidate(&self, _dir: &Path) {}
+
+    fn is_read_only(&self) -> bool {
+        false
+    }
+
+    // шлях на диску, якщо елемент можна відкрити програмою без копіювання
+    fn local_path(&self, _path: &Path) -> Option<PathBuf> {
+        None
+    }
+}
+
+pub fn read_only_error(path: &Path) -> io::Error {
+    io::Error::new(ErrorKind::PermissionDenied, format!("{} доступний лише для читання", path.display()))
+}
+
+pub fn entry_name(path: &Path) -> String {
+    path.file_name().map_or_else(|| path.to_string_lossy().to_string(), |name| name.to_string_lossy().to_string())
+}
+
+type ArchiveCache = HashMap<PathBuf, (Option<SystemTime>, Arc<ArchiveVfs>)>;
+
+// вибирає бекенд за шляхом: змонтовані простори, потім архіви, інакше локальна файлова система.
+// копія реєстру ділить кеші з оригіналом, тож її можна передати у фонову задачу
+#[derive(Debug, Clone)]
+pub struct VfsRegistry {
+    local: Arc<LocalVfs>,
+    mounts: Vec<(PathBuf, Arc<dyn Vfs>)>,
+    // вміст архіву читаємо один раз, доки архів не зміниться
+    archives: Arc<Mutex<ArchiveCache>>,
+    // is_local і split_archive викликаються кожен кадр, тому результат stat для архівів запам'ятовується
+    archive_checks: Arc<Mutex<HashMap<PathBuf, (Instant, bool)>>>,
+}
+
+impl Default for VfsRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            local: Arc::new(LocalVfs),
+            mounts: Vec::new(),
+            archives: Arc::default(),
+            archive_checks: Arc::default(),
+        };
+
+        registry.mount(PathBuf::from(TRASH_ROOT), Arc::new(TrashVfs));
+
+        registry
+    }
+}
+
+impl VfsRegistry {
+    pub fn mount(&mut self, root: PathBuf, vfs: Arc<dyn Vfs>) {
+        self.unmount(&root);
+        self.mounts.push((root, vfs));
+    }
+
+    pub fn unmount(&mut self, root: &Path) {
+        self.mounts.retain(|(mount_root, _)| mount_root != root);
+    }
+
+    pub fn is_local(&self, path: &Path) -> bool {
+        !self.mounts.iter().any(|(root, _)| path.starts_with(root)) && self.split_archive(path).is_none()
+    }
+
+    fn is_archive_file(&self, path: &Path) -> bool {
+        if ArchiveFormat::from_path(path).is_none() {
+            return false;
+        }
+
+        let mut checks = self.archive_checks.lock().unwrap();
+
+        if let Some((checked, is_file)) = checks.get(path)
+            && checked.elapsed() < ARCHIVE_CHECK_TTL
+        {
+            return *is_file;
+        }
+
+        let is_file = path.is_file();
+        checks.insert(path.to_path_buf(), (Instant::now(), is_file));
+
+        is_file
+    }
+
+    // те саме, що split_archive_path, але без зайвих stat
+    pub fn split_archive(&self, path: &Path) -> Option<(PathBuf, PathBuf)> {
+        path.ancestors()
+            .find(|ancestor| self.is_archive_file(ancestor))
+            .map(|archive| (archive.to_path_buf(), path.strip_prefix(archive).unwrap_or(Path::new("")).to_path_buf()))
+    }
+
+    // бекенд без звертання до диска; None, якщо архів ще не відкривався
+    pub fn cached(&self, path: &Path) -> Option<Arc<dyn Vfs>> {
+        if let Some((_, vfs)) = self.mounts.iter().find(|(root, _)| path.starts_with(root)) {
+            return Some(vfs.clone());
+        }
+
+        let Some((archive, _)) = self.split_archive(path) else {
+            return Some(self.local.clone());
+        };
+
+        self.archives.lock().unwrap().get(&archive).map(|(_, vfs)| vfs.clone() as Arc<dyn Vfs>)
+    }
+
+    // може читати архів цілком, тому для папок в архівах викликається у фоновій задачі
+    pub fn resolve(&self, path: &Path) -> io::Result<Arc<dyn Vfs>> {
+        if let Some((_, vfs)) = self.mounts.iter().find(|(root, _)| path.starts_with(root)) {
+            return Ok(vfs.clone());
+        }
+
+        let Some((archive, _)) = self.split_archive(path) else {
+            return Ok(self.local.clone());
+        };
+
+        let modified = fs::metadata(&archive).and_then(|metadata| metadata.modified()).ok();
+
+        if let Some((cached_modified, cached)) = self.archives.lock().unwrap().get(&archive)
+            && *cached_modified == modified
+        {
+            return Ok(cached.clone());
+        }
+
+        let vfs = Arc::new(ArchiveVfs::open(&archive)?);
+        self.archives.lock().unwrap().insert(archive, (modified, vfs.clone()));
+
+        Ok(vfs)
+    }
+}
+
+// файл без шляху на диску копіюється в кеш, щоб його могла відкрити зовнішня програма
+pub fn fetch_to_cache(vfs: &dyn Vfs, path: &Path) -> io::Result<PathBuf> {
+    if let Some(local_path) = vfs.local_path(path) {
+        return Ok(local_path);
+    }
+
+    let entry = vfs.stat(path)?;
+
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    entry.modified.hash(&mut hasher);
+    entry.size.hash(&mut hasher);
+
+    let cache_dir = cache_home().join("file_explorer/vfs").join(format!("{:016x}", hasher.finish()));
+    let cached = cache_dir.join(&entry.name);
+
+    if cached.exists() {
+        return Ok(cached);
+    }
+
+    fs::create_dir_all(&cache_dir)?;
+
+    let mut reader = vfs.read(path)?;
+    let mut file = File::create(&cached)?;
+
+    if let Err(err) = io::copy(&mut reader, &mut file) {
+        let _ = fs::remove_file(&cached);
+        return Err(err);
+    }
+
+    Ok(cached)
+}
+
+// спільна перевірка для тестів з реальними серверами: dir створюється і видаляється
+#[cfg(test)]
+pub fn check_round_trip(vfs: &dyn Vfs, dir: &Path) {
+    vfs.mkdir(dir).unwrap();
+    vfs.mkdir(&dir.join("inner")).unwrap();
+
+    let mut writer = vfs.write(&dir.join("a.txt")).unwrap();
+    writer.write_all(b"remote").unwrap();
+    writer.flush().unwrap();
+    drop(writer);
+
+    vfs.rename(&dir.join("a.txt"), &dir.join("b.txt")).unwrap();
+    vfs.invalidate(dir);
+
+    let mut names: Vec<_> = vfs.list(dir).unwrap().into_iter().map(|entry| (entry.name, entry.is_dir)).collect();
+    names.sort();
+    assert_eq!(names, [("b.txt".to_string(), false), ("inner".to_string(), true)]);
+
+    let entry = vfs.stat(&dir.join("b.txt")).unwrap();
+    assert_eq!((entry.size, entry.is_dir), (6, false));
+
+    let mut content = String::new();
+    vfs.read(&dir.join("b.txt")).unwrap().read_to_string(&mut content).unwrap();
+    assert_eq!(content, "remote");
+
+    vfs.remove(dir).unwrap();
+    vfs.invalidate(dir);
+    assert!(vfs.stat(dir).is_err());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+
+    #[test]
+    fn archives_are_opened_once_and_shared_with_clones() {
+        let dir = env::temp_dir().join(format!("file_explorer_vfs_registry_{}", std::process::id()));
+        let archive = dir.join("a.tar");
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut builder = tar::Builder::new(File::create(&archive).unwrap());
+        let mut header = tar::Header::new_gnu();
+        header.set_size(4);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, "docs/a.txt", &b"text"[..]).unwrap();
+        builder.into_inner().unwrap();
+
+        let registry = VfsRegistry::default();
+        let inner = archive.join("docs");
+
+        assert!(registry.is_local(&dir));
+        assert!(!registry.is_local(&inner));
+        assert_eq!(registry.split_archive(&inner), Some((archive.clone(), PathBuf::from("docs"))));
+        assert!(registry.cached(&inner).is_none());
+
+        // фонова задача працює з копією реєстру, а відкритий архів бачить і оригінал
+        let names: Vec<_> = registry.clone().resolve(&inner).unwrap().list(&inner).unwrap().into_iter().map(|entry| entry.name).collect();
+
+        assert_eq!(names, ["a.txt"]);
+        assert!(registry.cached(&inner).is_some_and(|vfs| vfs.is_read_only()));
+        assert!(registry.cached(&dir).is_some_and(|vfs| vfs.local_path(&dir).is_some()));
+        assert!(registry.cached(Path::new(TRASH_ROOT)).is_some());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
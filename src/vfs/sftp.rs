@@ -0,0 +1,454 @@
+use super::{cache::ListingCache, entry_name, Vfs, VfsEntry};
+use crate::remote::ssh_config::resolve_host;
+use crate::xdg::home_dir;
+
+use base64::{engine::general_purpose::{STANDARD, STANDARD_NO_PAD}, Engine};
+use sha2::{Digest, Sha256};
+use ssh2::{CheckResult, FileStat, KnownHostFileKind, OpenFlags, OpenType, Session, Sftp};
+
+use std::{
+    error::Error,
+    fmt,
+    fs,
+    fs::OpenOptions,
+    io,
+    io::{ErrorKind, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH}
+};
+
+pub const SFTP_SCHEME: &str = "sftp:";
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SftpAuth {
+    // ssh-agent, потім ключі з ~/.ssh/config і стандартні ~/.ssh/id_*
+    Key { path: Option<PathBuf>, passphrase: String },
+    Password(String),
+}
+
+// host може бути аліасом з ~/.ssh/config, порожні user і port беруться звідти ж
+#[derive(Debug, Clone)]
+pub struct SftpParams {
+    pub host: String,
+    pub user: String,
+    pub port: Option<u16>,
+    pub auth: SftpAuth,
+    // відбиток ключа невідомого сервера, який користувач підтвердив
+    pub trusted_key: Option<String>,
+}
+
+// сервера немає в known_hosts: підключення чекає, поки користувач перевірить відбиток ключа
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownHostKey {
+    pub host: String,
+    pub fingerprint: String,
+}
+
+impl fmt::Display for UnknownHostKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "сервер {} невідомий, відбиток ключа {}", self.host, self.fingerprint)
+    }
+}
+
+impl Error for UnknownHostKey {}
+
+pub fn unknown_host_key(err: &io::Error) -> Option<&UnknownHostKey> {
+    err.get_ref().and_then(|inner| inner.downcast_ref::<UnknownHostKey>())
+}
+
+pub struct SftpVfs {
+    // корінь у програмі: "sftp:user@host" або "sftp:user@host:port"
+    root: PathBuf,
+    home: PathBuf,
+    // сесія має жити, доки використовується sftp
+    _session: Session,
+    sftp: Sftp,
+    listings: ListingCache,
+}
+
+impl fmt::Debug for SftpVfs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SftpVfs").field("root", &self.root).finish_non_exhaustive()
+    }
+}
+
+fn auth_error(message: impl Into<String>) -> io::Error {
+    io::Error::new(ErrorKind::PermissionDenied, message.into())
+}
+
+// відбиток у форматі OpenSSH: SHA256 від ключа в base64 без вирівнювання
+pub fn fingerprint(key: &[u8]) -> String {
+    format!("SHA256:{}", STANDARD_NO_PAD.encode(Sha256::digest(key)))
+}
+
+fn host_pattern(host: &str, port: u16) -> String {
+    if port == 22 { host.to_string() } else { format!("[{}]:{}", host, port) }
+}
+
+// ключ у форматі ssh починається з назви свого типу, наприклад "ssh-ed25519"
+fn known_hosts_line(host: &str, port: u16, key: &[u8]) -> io::Result<String> {
+    let invalid = || io::Error::new(ErrorKind::InvalidData, "невідомий формат ключа сервера");
+
+    let length = u32::from_be_bytes(key.get(..4).ok_or_else(invalid)?.try_into().map_err(|_| invalid())?) as usize;
+    let key_type = key.get(4..4 + length).and_then(|name| std::str::from_utf8(name).ok()).ok_or_else(invalid)?;
+
+    Ok(format!("{} {} {}\n", host_pattern(host, port), key_type, STANDARD.encode(key)))
+}
+
+// дописує один рядок, не переписуючи решту файлу
+fn append_known_host(known_hosts_path: &Path, line: &str) -> io::Result<()> {
+    if let Some(ssh_dir) = known_hosts_path.parent() {
+        fs::create_dir_all(ssh_dir)?;
+    }
+
+    let ends_with_newline = fs::read(known_hosts_path).map_or(true, |content| content.is_empty() || content.ends_with(b"\n"));
+
+    let mut file = OpenOptions::new().create(true).append(true).open(known_hosts_path)?;
+
+    if !ends_with_newline {
+        file.write_all(b"\n")?;
+    }
+
+    file.write_all(line.as_bytes())
+}
+
+// невідомий сервер додається в known_hosts лише після підтвердження відбитка, змінений ключ зупиняє підключення
+fn verify_host_key(session: &Session, host: &str, port: u16, trusted_key: Option<&str>) -> io::Result<()> {
+    let Some((key, _)) = session.host_key() else {
+        return Err(auth_error("сервер не надав ключ"));
+    };
+
+    let known_hosts_path = home_dir().join(".ssh/known_hosts");
+    let mut known_hosts = session.known_hosts()?;
+
+    if known_hosts_path.exists() {
+        known_hosts.read_file(&known_hosts_path, KnownHostFileKind::OpenSSH)?;
+    }
+
+    match known_hosts.check_port(host, port, key) {
+        CheckResult::Match => Ok(()),
+        CheckResult::NotFound => {
+            let fingerprint = fingerprint(key);
+
+            if trusted_key != Some(fingerprint.as_str()) {
+                return Err(io::Error::new(ErrorKind::PermissionDenied, UnknownHostKey { host: host_pattern(host, port), fingerprint }));
+            }
+
+            append_known_host(&known_hosts_path, &known_hosts_line(host, port, key)?)
+        },
+        CheckResult::Mismatch => Err(auth_error(format!("ключ сервера {} не збігається з known_hosts", host))),
+        CheckResult::Failure => Err(auth_error("не вдалося перевірити ключ сервера")),
+    }
+}
+
+fn authenticate(session: &Session, user: &str, auth: &SftpAuth, identity_files: &[PathBuf]) -> io::Result<()> {
+    match auth {
+        SftpAuth::Password(password) => {
+            session.userauth_password(user, password)?;
+        },
+        SftpAuth::Key { path, passphrase } => {
+            let passphrase = (!passphrase.is_empty()).then_some(passphrase.as_str());
+
+            let keys: Vec<PathBuf> = match path {
+                Some(path) => vec![path.clone()],
+                None => {
+                    // без явно вказаного ключа спершу пробуємо агент
+                    if session.userauth_agent(user).is_ok() {
+                        return Ok(());
+                    }
+
+                    identity_files.iter()
+                        .cloned()
+                        .chain(["id_ed25519", "id_ecdsa", "id_rsa"].map(|name| home_dir().join(".ssh").join(name)))
+                        .filter(|key| key.exists())
+                        .collect()
+                },
+            };
+
+            for key in keys {
+                if session.userauth_pubkey_file(user, None, &key, passphrase).is_ok() {
+                    break;
+                }
+            }
+        },
+    }
+
+    if session.authenticated() {
+        Ok(())
+    } else {
+        Err(auth_error(format!("сервер відхилив вхід користувача {}", user)))
+    }
+}
+
+pub fn connect(params: &SftpParams) -> io::Result<SftpVfs> {
+    let config = resolve_host(&params.host);
+
+    let host_name = config.host_name.clone().unwrap_or_else(|| params.host.clone());
+    let port = params.port.or(config.port).unwrap_or(22);
+    let user = if params.user.trim().is_empty() {
+        config.user.clone().or_else(|| std::env::var("USER").ok()).unwrap_or_default()
+    } else {
+        params.user.trim().to_string()
+    };
+
+    let address = (host_name.as_str(), port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(ErrorKind::NotFound, format!("не вдалося знайти адресу {}", host_name)))?;
+
+    let stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)?;
+
+    let mut session = Session::new()?;
+    session.set_tcp_stream(stream);
+    session.set_timeout(CONNECT_TIMEOUT.as_millis() as u32);
+    session.handshake()?;
+
+    verify_host_key(&session, &host_name, port, params.trusted_key.as_deref())?;
+    authenticate(&session, &user, &params.auth, &config.identity_files)?;
+
+    let sftp = session.sftp()?;
+
+    let root = if port == 22 {
+        PathBuf::from(format!("{}{}@{}", SFTP_SCHEME, user, params.host))
+    } else {
+        PathBuf::from(format!("{}{}@{}:{}", SFTP_SCHEME, user, params.host, port))
+    };
+
+    let remote_home = sftp.realpath(Path::new(".")).unwrap_or_else(|_| PathBuf::from("/"));
+    let home = root.join(remote_home.strip_prefix("/").unwrap_or(&remote_home));
+
+    Ok(SftpVfs {
+        root,
+        home,
+        _session: session,
+        sftp,
+        listings: ListingCache::default(),
+    })
+}
+
+impl SftpVfs {
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    // домашня папка користувача на сервері, з неї починається перегляд
+    pub fn home(&self) -> &Path {
+        &self.home
+    }
+
+    fn remote_path(&self, path: &Path) -> io::Result<PathBuf> {
+        let relative = path.strip_prefix(&self.root).map_err(|_| {
+            io::Error::new(ErrorKind::InvalidInput, format!("{} не належить {}", path.display(), self.root.display()))
+        })?;
+
+        Ok(Path::new("/").join(relative))
+    }
+
+    fn to_entry(&self, path: &Path, stat: &FileStat) -> VfsEntry {
+        VfsEntry {
+            path: path.to_path_buf(),
+            name: entry_name(path),
+            is_dir: stat.is_dir(),
+            size: stat.size.unwrap_or(0),
+            modified: stat.mtime.map(|mtime| UNIX_EPOCH + Duration::from_secs(mtime)),
+        }
+    }
+
+    fn remove_remote(&self, remote: &Path) -> io::Result<()> {
+        let stat = self.sftp.lstat(remote)?;
+
+        if !stat.is_dir() {
+            return Ok(self.sftp.unlink(remote)?);
+        }
+
+        for (child, _) in self.sftp.readdir(remote)? {
+            self.remove_remote(&child)?;
+        }
+
+        Ok(self.sftp.rmdir(remote)?)
+    }
+}
+
+impl Vfs for SftpVfs {
+    fn list(&self, dir: &Path) -> io::Result<Vec<VfsEntry>> {
+        if let Some(entries) = self.listings.get(dir) {
+            return Ok(entries);
+        }
+
+        let mut entries = Vec::new();
+
+        for (remote, stat) in self.sftp.readdir(self.remote_path(dir)?)? {
+            let path = dir.join(remote.file_name().unwrap_or_default());
+
+            // посилання показуємо як те, на що вони вказують
+            let stat = if stat.file_type().is_symlink() {
+                self.sftp.stat(&remote).unwrap_or(stat)
+            } else {
+                stat
+            };
+
+            entries.push(self.to_entry(&path, &stat));
+        }
+
+        self.listings.insert(dir, &entries);
+
+        Ok(entries)
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<VfsEntry> {
+        let stat = self.sftp.stat(&self.remote_path(path)?)?;
+
+        Ok(self.to_entry(path, &stat))
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Box<dyn Read + Send>> {
+        Ok(Box::new(self.sftp.open(&self.remote_path(path)?)?))
+    }
+
+    fn write(&self, path: &Path) -> io::Result<Box<dyn Write + Send>> {
+        let file = self.sftp.open_mode(
+            &self.remote_path(path)?,
+            OpenFlags::WRITE | OpenFlags::CREATE | OpenFlags::TRUNCATE,
+            0o644,
+            OpenType::File,
+        )?;
+
+        self.listings.clear();
+
+        Ok(Box::new(file))
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        if self.sftp.lstat(&self.remote_path(to)?).is_ok() {
+            return Err(io::Error::new(ErrorKind::AlreadyExists, format!("{} вже існує", to.display())));
+        }
+
+        self.sftp.rename(&self.remote_path(from)?, &self.remote_path(to)?, None)?;
+        self.listings.clear();
+
+        Ok(())
+    }
+
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        let result = self.remove_remote(&self.remote_path(path)?);
+
+        self.listings.clear();
+
+        result
+    }
+
+    fn mkdir(&self, path: &Path) -> io::Result<()> {
+        let remote = self.remote_path(path)?;
+
+        // sftp не розрізняє помилки, тому зайняте ім'я перевіряємо окремо
+        if self.sftp.lstat(&remote).is_ok() {
+            return Err(io::Error::new(ErrorKind::AlreadyExists, format!("{} вже існує", path.display())));
+        }
+
+        self.sftp.mkdir(&remote, 0o755)?;
+        self.listings.clear();
+
+        Ok(())
+    }
+
+    fn set_modified(&self, path: &Path, modified: SystemTime) -> io::Result<()> {
+        let Ok(since_epoch) = modified.duration_since(UNIX_EPOCH) else {
+            return Ok(());
+        };
+
+        let remote = self.remote_path(path)?;
+        let mut stat = self.sftp.stat(&remote)?;
+
+        stat.mtime = Some(since_epoch.as_secs());
+        stat.atime = stat.atime.or(stat.mtime);
+
+        self.sftp.setstat(&remote, stat)?;
+        self.listings.clear();
+
+        Ok(())
+    }
+
+    fn invalidate(&self, _dir: &Path) {
+        self.listings.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vfs::check_round_trip;
+
+    use std::env;
+
+    // ключ і відбиток, отримані через ssh-keygen -lf
+    const KEY: &str = "AAAAC3NzaC1lZDI1NTE5AAAAINMm5Fn0gntWEgkBWmdrJ2pL82wswXWryrOs+uGh2JsL";
+
+    #[test]
+    fn fingerprint_matches_openssh() {
+        let key = STANDARD.decode(KEY).unwrap();
+
+        assert_eq!(fingerprint(&key), "SHA256:FS0btC5I50dU+7nePmdUMm60bnw8+yoY/+UsL0peXyU");
+    }
+
+    #[test]
+    fn known_hosts_line_uses_key_type_and_port() {
+        let key = STANDARD.decode(KEY).unwrap();
+
+        assert_eq!(known_hosts_line("example.com", 22, &key).unwrap(), format!("example.com ssh-ed25519 {}\n", KEY));
+        assert_eq!(known_hosts_line("example.com", 2222, &key).unwrap(), format!("[example.com]:2222 ssh-ed25519 {}\n", KEY));
+        assert_eq!(known_hosts_line("example.com", 22, &[0, 0, 0, 9, b'x']).unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn append_keeps_existing_lines() {
+        let dir = env::temp_dir().join(format!("file_explorer_known_hosts_{}", std::process::id()));
+        let path = dir.join(".ssh/known_hosts");
+
+        let _ = fs::remove_dir_all(&dir);
+
+        append_known_host(&path, "first ssh-ed25519 AAAA\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first ssh-ed25519 AAAA\n");
+
+        // рядок без завершального переносу не зливається з новим
+        fs::write(&path, "# коментар\nold ssh-rsa BBBB").unwrap();
+        append_known_host(&path, "new ssh-ed25519 CCCC\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# коментар\nold ssh-rsa BBBB\nnew ssh-ed25519 CCCC\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unknown_host_key_survives_io_error() {
+        let host_key = UnknownHostKey { host: "[example.com]:2222".to_string(), fingerprint: "SHA256:abc".to_string() };
+        let err = io::Error::new(ErrorKind::PermissionDenied, host_key.clone());
+
+        assert_eq!(unknown_host_key(&err), Some(&host_key));
+        assert_eq!(unknown_host_key(&io::Error::other("інша помилка")), None);
+    }
+
+    // cargo test -- --ignored з FILE_EXPLORER_SFTP_HOST, _USER, _PASSWORD і, за потреби, _PORT.
+    // якщо сервера ще немає в known_hosts, підтверджений відбиток передається в _FINGERPRINT
+    #[test]
+    #[ignore]
+    fn sftp_server_round_trip() {
+        let var = |name: &str| env::var(format!("FILE_EXPLORER_SFTP_{}", name)).ok();
+
+        let params = SftpParams {
+            host: var("HOST").expect("FILE_EXPLORER_SFTP_HOST не задано"),
+            user: var("USER").unwrap_or_default(),
+            port: var("PORT").and_then(|port| port.parse().ok()),
+            auth: match var("PASSWORD") {
+                Some(password) => SftpAuth::Password(password),
+                None => SftpAuth::Key { path: None, passphrase: String::new() },
+            },
+            trusted_key: var("FINGERPRINT"),
+        };
+
+        let vfs = connect(&params).unwrap();
+
+        check_round_trip(&vfs, &vfs.home().join(format!("file_explorer_test_{}", std::process::id())));
+    }
+}
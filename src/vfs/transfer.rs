@@ -0,0 +1,246 @@
+use super::{entry_name, Vfs};
+use crate::jobs::job::{BatchResult, JobContext};
+
+use std::{
+    io,
+    io::{ErrorKind, Read, Write},
+    path::{Path, PathBuf}
+};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TransferProgress {
+    pub files: u64,
+    pub bytes: u64,
+}
+
+fn copy_file(src: &dyn Vfs, source: &Path, dst: &dyn Vfs, target: &Path, progress: &mut TransferProgress, job: &mut JobContext<TransferProgress>) -> io::Result<()> {
+    let mut reader = src.read(source)?;
+    let mut writer = dst.write(target)?;
+    let mut buffer = vec![0; CHUNK_SIZE];
+
+    loop {
+        if job.is_cancelled() {
+            return Err(io::Error::from(ErrorKind::Interrupted));
+        }
+
+        let read = reader.read(&mut buffer)?;
+
+        if read == 0 {
+            break;
+        }
+
+        writer.write_all(&buffer[..read])?;
+
+        progress.bytes += read as u64;
+        job.report(*progress);
+    }
+
+    writer.flush()
+}
+
+fn copy_entry(src: &dyn Vfs, source: &Path, dst: &dyn Vfs, target: &Path, progress: &mut TransferProgress, result: &mut BatchResult, job: &mut JobContext<TransferProgress>) {
+    let entry = match src.stat(source) {
+        Ok(entry) => entry,
+        Err(err) => {
+            result.errors.push((source.to_path_buf(), err.to_string()));
+            return;
+        }
+    };
+
+    if entry.is_dir {
+        if let Err(err) = dst.mkdir(target) {
+            result.errors.push((source.to_path_buf(), err.to_string()));
+            return;
+        }
+
+        let children = match src.list(source) {
+            Ok(children) => children,
+            Err(err) => {
+                result.errors.push((source.to_path_buf(), err.to_string()));
+                return;
+            }
+        };
+
+        for child in children {
+            if job.is_cancelled() {
+                result.cancelled = true;
+                return;
+            }
+
+            copy_entry(src, &child.path, dst, &target.join(&child.name), progress, result, job);
+        }
+
+        return;
+    }
+
+    match copy_file(src, source, dst, target, progress, job) {
+        Ok(()) => {
+            // час зміни зберігаємо, щоб порівняння папок не вважало копію іншим файлом
+            if let Some(modified) = entry.modified {
+                let _ = dst.set_modified(target, modified);
+            }
+
+            result.done += 1;
+            progress.files += 1;
+            job.report(*progress);
+        },
+        Err(err) => {
+            // недокопійований файл не лишаємо
+            let _ = dst.remove(target);
+
+            if err.kind() == ErrorKind::Interrupted {
+                result.cancelled = true;
+            } else {
+                result.errors.push((source.to_path_buf(), err.to_string()));
+            }
+        }
+    }
+}
+
+// копіює елементи між різними просторами, наприклад з локальної папки на сервер;
+// при переміщенні джерело видаляється лише тоді, коли все скопіювалося без помилок
+pub fn transfer(sources: &[PathBuf], src: &dyn Vfs, dst: &dyn Vfs, target_dir: &Path, is_move: bool, job: &mut JobContext<TransferProgress>) -> BatchResult {
+    let mut result = BatchResult::default();
+    let mut progress = TransferProgress::default();
+
+    for source in sources {
+        if job.is_cancelled() {
+            result.cancelled = true;
+            break;
+        }
+
+        let target = target_dir.join(entry_name(source));
+
+        if dst.stat(&target).is_ok() {
+            result.errors.push((source.clone(), format!("{} вже існує", target.display())));
+            continue;
+        }
+
+        let errors_before = result.errors.len();
+
+        copy_entry(src, source, dst, &target, &mut progress, &mut result, job);
+
+        if result.cancelled {
+            break;
+        }
+
+        if is_move
+            && result.errors.len() == errors_before
+            && let Err(err) = src.remove(source)
+        {
+            result.errors.push((source.clone(), err.to_string()));
+        }
+    }
+
+    dst.invalidate(target_dir);
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::jobs::job::Job;
+    use crate::vfs::{local::LocalVfs, memory::MemoryVfs};
+
+    use eframe::egui;
+
+    use std::{env, fs, sync::Arc, thread, time::Duration};
+
+    fn run(sources: Vec<PathBuf>, src: Arc<dyn Vfs>, dst: Arc<dyn Vfs>, target_dir: &str, is_move: bool) -> BatchResult {
+        let target_dir = PathBuf::from(target_dir);
+        let mut job = Job::spawn(&egui::Context::default(), move |job| transfer(&sources, src.as_ref(), dst.as_ref(), &target_dir, is_move, job));
+
+        while !job.is_finished() {
+            thread::sleep(Duration::from_millis(10));
+            job.poll();
+        }
+
+        job.result.unwrap()
+    }
+
+    fn write_file(vfs: &dyn Vfs, path: &str, data: &str) {
+        let mut writer = vfs.write(Path::new(path)).unwrap();
+        writer.write_all(data.as_bytes()).unwrap();
+    }
+
+    fn read_file(vfs: &dyn Vfs, path: &str) -> String {
+        let mut data = String::new();
+        vfs.read(Path::new(path)).unwrap().read_to_string(&mut data).unwrap();
+
+        data
+    }
+
+    fn sample() -> Arc<MemoryVfs> {
+        let vfs = Arc::new(MemoryVfs::default());
+
+        vfs.mkdir(Path::new("/docs")).unwrap();
+        vfs.mkdir(Path::new("/docs/inner")).unwrap();
+        write_file(vfs.as_ref(), "/docs/inner/a.txt", "a");
+        write_file(vfs.as_ref(), "/docs/b.txt", "bb");
+
+        vfs
+    }
+
+    #[test]
+    fn copy_keeps_source_and_preserves_tree() {
+        let (src, dst) = (sample(), Arc::new(MemoryVfs::default()));
+
+        dst.mkdir(Path::new("/target")).unwrap();
+
+        let result = run(vec![PathBuf::from("/docs")], src.clone(), dst.clone(), "/target", false);
+
+        assert_eq!((result.done, result.errors.len()), (2, 0));
+        assert_eq!(read_file(dst.as_ref(), "/target/docs/inner/a.txt"), "a");
+        assert_eq!(read_file(dst.as_ref(), "/target/docs/b.txt"), "bb");
+        assert!(src.stat(Path::new("/docs/b.txt")).is_ok());
+    }
+
+    #[test]
+    fn move_removes_source_and_refuses_existing_target() {
+        let (src, dst) = (sample(), Arc::new(MemoryVfs::default()));
+
+        dst.mkdir(Path::new("/docs")).unwrap();
+        write_file(src.as_ref(), "/c.txt", "c");
+
+        let result = run(vec![PathBuf::from("/docs"), PathBuf::from("/c.txt")], src.clone(), dst.clone(), "/", true);
+
+        // наявна папка не перезаписується і джерело лишається на місці
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].0, PathBuf::from("/docs"));
+        assert!(src.stat(Path::new("/docs")).is_ok());
+
+        assert_eq!(read_file(dst.as_ref(), "/c.txt"), "c");
+        assert!(src.stat(Path::new("/c.txt")).is_err());
+    }
+
+    #[test]
+    fn copies_between_disk_and_memory() {
+        let dir = env::temp_dir().join(format!("file_explorer_transfer_{}", std::process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("up")).unwrap();
+        fs::write(dir.join("up/file.txt"), "disk").unwrap();
+
+        let (local, memory) = (Arc::new(LocalVfs), Arc::new(MemoryVfs::default()));
+
+        let result = run(vec![dir.join("up")], local.clone(), memory.clone(), "/", false);
+        assert_eq!(result.done, 1);
+        assert_eq!(read_file(memory.as_ref(), "/up/file.txt"), "disk");
+
+        // цільова папка на диску має існувати
+        let result = run(vec![PathBuf::from("/up")], memory.clone(), local.clone(), dir.join("down").to_str().unwrap(), false);
+        assert!(result.errors.iter().any(|(path, _)| path == Path::new("/up")));
+
+        fs::create_dir(dir.join("down")).unwrap();
+
+        let result = run(vec![PathBuf::from("/up")], memory, local, dir.join("down").to_str().unwrap(), false);
+        assert_eq!(result.done, 1);
+        assert_eq!(fs::read_to_string(dir.join("down/up/file.txt")).unwrap(), "disk");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,128 @@
+use super::{entry_name, read_only_error, Vfs, VfsEntry};
+use crate::xdg::data_dirs;
+
+use std::{
+    fs,
+    fs::File,
+    io,
+    io::{Read, Write},
+    path::{Path, PathBuf}
+};
+
+pub const TRASH_ROOT: &str = "trash:";
+
+// кошик користувача за специфікацією freedesktop: files/ — вміст, info/ — звідки елемент видалено
+#[derive(Debug, Default)]
+pub struct TrashVfs;
+
+fn trash_dir() -> PathBuf {
+    data_dirs().into_iter().next().unwrap_or_default().join("Trash")
+}
+
+fn relative(path: &Path) -> &Path {
+    path.strip_prefix(TRASH_ROOT).unwrap_or(path)
+}
+
+fn real_path(path: &Path) -> PathBuf {
+    trash_dir().join("files").join(relative(path))
+}
+
+fn to_entry(path: &Path, real: &Path) -> io::Result<VfsEntry> {
+    let metadata = fs::symlink_metadata(real)?;
+
+    Ok(VfsEntry {
+        path: path.to_path_buf(),
+        name: entry_name(path),
+        is_dir: metadata.is_dir(),
+        size: metadata.len(),
+        modified: metadata.modified().ok(),
+    })
+}
+
+impl Vfs for TrashVfs {
+    fn list(&self, dir: &Path) -> io::Result<Vec<VfsEntry>> {
+        let real_dir = real_path(dir);
+
+        // порожній кошик може ще не мати папки files
+        if relative(dir).as_os_str().is_empty() && !real_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = Vec::new();
+
+        for entry in fs::read_dir(&real_dir)? {
+            let entry = entry?;
+            let path = dir.join(entry.file_name());
+
+            if let Ok(vfs_entry) = to_entry(&path, &entry.path()) {
+                entries.push(vfs_entry);
+            }
+        }
+
+        Ok(entries)
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<VfsEntry> {
+        if relative(path).as_os_str().is_empty() {
+            return Ok(VfsEntry {
+                path: PathBuf::from(TRASH_ROOT),
+                name: "Кошик".to_string(),
+                is_dir: true,
+                size: 0,
+                modified: None,
+            });
+        }
+
+        to_entry(path, &real_path(path))
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Box<dyn Read + Send>> {
+        Ok(Box::new(File::open(real_path(path))?))
+    }
+
+    fn write(&self, path: &Path) -> io::Result<Box<dyn Write + Send>> {
+        Err(read_only_error(path))
+    }
+
+    fn rename(&self, from: &Path, _to: &Path) -> io::Result<()> {
+        Err(read_only_error(from))
+    }
+
+    // видалення з кошика остаточне; для елемента верхнього рівня прибираємо і його .trashinfo
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        let relative = relative(path);
+        let real = real_path(path);
+
+        if relative.as_os_str().is_empty() {
+            return Err(read_only_error(path));
+        }
+
+        if fs::symlink_metadata(&real)?.is_dir() {
+            fs::remove_dir_all(&real)?;
+        } else {
+            fs::remove_file(&real)?;
+        }
+
+        if relative.components().count() == 1 {
+            let info = trash_dir().join("info").join(format!("{}.trashinfo", relative.to_string_lossy()));
+
+            if let Err(err) = fs::remove_file(info) {
+                println!("{:#?}", err);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn mkdir(&self, path: &Path) -> io::Result<()> {
+        Err(read_only_error(path))
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    fn local_path(&self, path: &Path) -> Option<PathBuf> {
+        Some(real_path(path))
+    }
+}
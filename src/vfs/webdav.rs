@@ -0,0 +1,371 @@
+use super::{
+    cache::ListingCache,
+    entry_name,
+    http::{agent, decode, encode_path, http_error, SpoolWriter},
+    Vfs, VfsEntry
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::DateTime;
+use quick_xml::{events::Event, Reader};
+
+use std::{
+    fmt,
+    io,
+    io::{ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    time::SystemTime
+};
+
+pub const WEBDAV_SCHEME: &str = "webdav:";
+
+const PROPFIND_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:"><d:prop><d:resourcetype/><d:getcontentlength/><d:getlastmodified/></d:prop></d:propfind>"#;
+
+// один елемент з відповіді PROPFIND
+#[derive(Debug, Default)]
+struct DavResource {
+    href: String,
+    is_dir: bool,
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+pub struct WebDavVfs {
+    root: PathBuf,
+    // адреса спільної папки без "/" в кінці
+    base_url: String,
+    // шлях спільної папки на сервері, з ним порівнюються href з відповідей
+    base_path: String,
+    authorization: Option<String>,
+    agent: ureq::Agent,
+    listings: ListingCache,
+}
+
+impl fmt::Debug for WebDavVfs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebDavVfs").field("root", &self.root).field("base_url", &self.base_url).finish_non_exhaustive()
+    }
+}
+
+// "https://host/a/b" -> "/a/b", href у відповідях буває і повною адресою, і лише шляхом
+fn url_path(url: &str) -> &str {
+    match url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("", |start| &rest[start..]),
+        None => url,
+    }
+}
+
+fn parse_multistatus(body: &str) -> io::Result<Vec<DavResource>> {
+    let mut reader = Reader::from_str(body);
+    reader.config_mut().trim_text(true);
+
+    let invalid = |err: quick_xml::Error| io::Error::new(ErrorKind::InvalidData, err.to_string());
+
+    let mut resources = Vec::new();
+    let mut current: Option<DavResource> = None;
+    let mut element: Vec<u8> = Vec::new();
+
+    loop {
+        match reader.read_event().map_err(invalid)? {
+            Event::Start(start) => {
+                element = start.local_name().as_ref().to_vec();
+
+                match element.as_slice() {
+                    b"response" => current = Some(DavResource::default()),
+                    b"collection" => {
+                        if let Some(resource) = &mut current {
+                            resource.is_dir = true;
+                        }
+                    },
+                    _ => {},
+                }
+            },
+            Event::Empty(empty) => {
+                if empty.local_name().as_ref() == b"collection"
+                    && let Some(resource) = &mut current
+                {
+                    resource.is_dir = true;
+                }
+            },
+            Event::Text(text) => {
+                let Some(resource) = &mut current else {
+                    continue;
+                };
+
+                let text = text.unescape().map_err(invalid)?;
+
+                match element.as_slice() {
+                    b"href" => resource.href = text.to_string(),
+                    b"getcontentlength" => resource.size = text.trim().parse().unwrap_or(0),
+                    b"getlastmodified" => {
+                        resource.modified = DateTime::parse_from_rfc2822(text.trim()).ok().map(SystemTime::from);
+                    },
+                    _ => {},
+                }
+            },
+            Event::End(end) => {
+                if end.local_name().as_ref() == b"response"
+                    && let Some(resource) = current.take()
+                {
+                    resources.push(resource);
+                }
+
+                element.clear();
+            },
+            Event::Eof => break,
+            _ => {},
+        }
+    }
+
+    Ok(resources)
+}
+
+impl WebDavVfs {
+    // перевіряє адресу і дані входу одразу, щоб помилка з'явилась при підключенні, а не при перегляді
+    pub fn connect(root: PathBuf, url: &str, user: &str, password: &str) -> io::Result<Self> {
+        let url = url.trim().trim_end_matches('/');
+
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(io::Error::new(ErrorKind::InvalidInput, "адреса має починатися з http:// або https://"));
+        }
+
+        let authorization = (!user.is_empty()).then(|| format!("Basic {}", STANDARD.encode(format!("{}:{}", user, password))));
+
+        let vfs = Self {
+            base_path: decode(url_path(url)).trim_end_matches('/').to_string(),
+            base_url: url.to_string(),
+            root,
+            authorization,
+            agent: agent(),
+            listings: ListingCache::default(),
+        };
+
+        let root_entry = vfs.stat(&vfs.root)?;
+
+        if !root_entry.is_dir {
+            return Err(io::Error::new(ErrorKind::NotADirectory, format!("{} не є папкою", url)));
+        }
+
+        Ok(vfs)
+    }
+
+    fn relative<'a>(&self, path: &'a Path) -> io::Result<&'a Path> {
+        path.strip_prefix(&self.root).map_err(|_| {
+            io::Error::new(ErrorKind::InvalidInput, format!("{} не належить {}", path.display(), self.root.display()))
+        })
+    }
+
+    // папки на сервері адресуються з "/" в кінці, інакше частина серверів відповідає перенаправленням
+    fn url(&self, path: &Path, is_dir: bool) -> io::Result<String> {
+        let relative = encode_path(self.relative(path)?);
+
+        let mut url = if relative.is_empty() {
+            self.base_url.clone()
+        } else {
+            format!("{}/{}", self.base_url, relative)
+        };
+
+        if is_dir {
+            url.push('/');
+        }
+
+        Ok(url)
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        let request = self.agent.request(method, url);
+
+        match &self.authorization {
+            Some(authorization) => request.set("Authorization", authorization),
+            None => request,
+        }
+    }
+
+    fn propfind(&self, url: &str, depth: &str) -> io::Result<Vec<DavResource>> {
+        let response = self.request("PROPFIND", url)
+            .set("Depth", depth)
+            .set("Content-Type", "application/xml; charset=utf-8")
+            .send_string(PROPFIND_BODY)
+            .map_err(http_error)?;
+
+        parse_multistatus(&response.into_string()?)
+    }
+
+    fn to_entry(&self, resource: &DavResource) -> Option<VfsEntry> {
+        let href_path = decode(url_path(&resource.href));
+        let relative = href_path.trim_end_matches('/')
+            .strip_prefix(&self.base_path)
+            .filter(|relative| relative.is_empty() || relative.starts_with('/'))?;
+        let path = self.root.join(relative.trim_start_matches('/'));
+
+        Some(VfsEntry {
+            name: entry_name(&path),
+            is_dir: resource.is_dir,
+            size: resource.size,
+            modified: resource.modified,
+            path,
+        })
+    }
+}
+
+impl Vfs for WebDavVfs {
+    fn list(&self, dir: &Path) -> io::Result<Vec<VfsEntry>> {
+        if let Some(entries) = self.listings.get(dir) {
+            return Ok(entries);
+        }
+
+        // відповідь містить і саму папку, її пропускаємо
+        let entries: Vec<VfsEntry> = self.propfind(&self.url(dir, true)?, "1")?
+            .iter()
+            .filter_map(|resource| self.to_entry(resource))
+            .filter(|entry| entry.path != dir)
+            .collect();
+
+        self.listings.insert(dir, &entries);
+
+        Ok(entries)
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<VfsEntry> {
+        let is_root = self.relative(path)?.as_os_str().is_empty();
+        let resources = self.propfind(&self.url(path, is_root)?, "0")?;
+
+        resources.first()
+            .and_then(|resource| self.to_entry(resource))
+            .map(|entry| VfsEntry { path: path.to_path_buf(), name: entry_name(path), ..entry })
+            .ok_or_else(|| io::Error::new(ErrorKind::NotFound, format!("{} не знайдено", path.display())))
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Box<dyn Read + Send>> {
+        let response = self.request("GET", &self.url(path, false)?).call().map_err(http_error)?;
+
+        Ok(response.into_reader())
+    }
+
+    fn write(&self, path: &Path) -> io::Result<Box<dyn Write + Send>> {
+        let request = self.request("PUT", &self.url(path, false)?);
+
+        self.listings.clear();
+
+        Ok(Box::new(SpoolWriter::new(Box::new(move |file, length| {
+            request
+                .set("Content-Length", &length.to_string())
+                .send(file)
+                .map(|_| ())
+                .map_err(http_error)
+        }))?))
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let is_dir = self.stat(from)?.is_dir;
+
+        self.request("MOVE", &self.url(from, is_dir)?)
+            .set("Destination", &self.url(to, is_dir)?)
+            .set("Overwrite", "F")
+            .call()
+            .map_err(http_error)?;
+
+        self.listings.clear();
+
+        Ok(())
+    }
+
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        let is_dir = self.stat(path)?.is_dir;
+
+        self.request("DELETE", &self.url(path, is_dir)?).call().map_err(http_error)?;
+        self.listings.clear();
+
+        Ok(())
+    }
+
+    fn mkdir(&self, path: &Path) -> io::Result<()> {
+        // на зайняте ім'я MKCOL відповідає 405, як і на інші заборони
+        if self.stat(path).is_ok() {
+            return Err(io::Error::new(ErrorKind::AlreadyExists, format!("{} вже існує", path.display())));
+        }
+
+        self.request("MKCOL", &self.url(path, true)?).call().map_err(http_error)?;
+        self.listings.clear();
+
+        Ok(())
+    }
+
+    fn invalidate(&self, _dir: &Path) {
+        self.listings.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vfs::check_round_trip;
+
+    use std::{env, time::Duration};
+
+    fn summary(resources: &[DavResource]) -> Vec<(&str, bool, u64)> {
+        resources.iter().map(|resource| (resource.href.as_str(), resource.is_dir, resource.size)).collect()
+    }
+
+    #[test]
+    fn parse_multistatus_reads_resources() {
+        let body = r#"<?xml version="1.0" encoding="utf-8"?>
+            <d:multistatus xmlns:d="DAV:" xmlns:oc="http://owncloud.org/ns">
+                <d:response>
+                    <d:href>/remote.php/dav/files/user/Docs/</d:href>
+                    <d:propstat>
+                        <d:prop>
+                            <d:resourcetype><d:collection/></d:resourcetype>
+                            <d:getlastmodified>Tue, 02 Jan 2024 03:04:05 GMT</d:getlastmodified>
+                        </d:prop>
+                        <d:status>HTTP/1.1 200 OK</d:status>
+                    </d:propstat>
+                </d:response>
+                <d:response>
+                    <d:href>/remote.php/dav/files/user/Docs/%D0%B7%D0%B2%D1%96%D1%82%20&amp;%20plan.txt</d:href>
+                    <d:propstat>
+                        <d:prop>
+                            <d:resourcetype/>
+                            <d:getcontentlength>2048</d:getcontentlength>
+                        </d:prop>
+                    </d:propstat>
+                </d:response>
+                <D:response xmlns:D="DAV:">
+                    <D:href>https://dav.example.com/share/Docs/inner/</D:href>
+                    <D:propstat><D:prop><D:resourcetype><D:collection></D:collection></D:resourcetype></D:prop></D:propstat>
+                </D:response>
+            </d:multistatus>"#;
+
+        let resources = parse_multistatus(body).unwrap();
+
+        assert_eq!(summary(&resources), [
+            ("/remote.php/dav/files/user/Docs/", true, 0),
+            ("/remote.php/dav/files/user/Docs/%D0%B7%D0%B2%D1%96%D1%82%20&%20plan.txt", false, 2048),
+            ("https://dav.example.com/share/Docs/inner/", true, 0),
+        ]);
+        assert_eq!(resources[0].modified, Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_704_164_645)));
+        assert_eq!(resources[1].modified, None);
+
+        assert!(parse_multistatus("<d:multistatus xmlns:d=\"DAV:\"><d:response></d:multistatus>").is_err());
+    }
+
+    #[test]
+    fn url_path_strips_scheme_and_host() {
+        assert_eq!(url_path("https://dav.example.com/share/Docs/"), "/share/Docs/");
+        assert_eq!(url_path("https://dav.example.com"), "");
+        assert_eq!(url_path("/share/Docs/"), "/share/Docs/");
+    }
+
+    // cargo test -- --ignored з FILE_EXPLORER_WEBDAV_URL, _USER і _PASSWORD
+    #[test]
+    #[ignore]
+    fn webdav_server_round_trip() {
+        let var = |name: &str| env::var(format!("FILE_EXPLORER_WEBDAV_{}", name)).unwrap_or_default();
+
+        let root = PathBuf::from(format!("{}test", WEBDAV_SCHEME));
+        let vfs = WebDavVfs::connect(root.clone(), &var("URL"), &var("USER"), &var("PASSWORD")).unwrap();
+
+        check_round_trip(&vfs, &root.join(format!("file_explorer_test_{}", std::process::id())));
+    }
+}
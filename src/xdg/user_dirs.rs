@@ -0,0 +1,54 @@
+use crate::xdg::{config_home, home_dir};
+
+use std::{
+    fs,
+    path::PathBuf
+};
+
+// user-dirs.dirs містить рядки виду XDG_DESKTOP_DIR="$HOME/Desktop"
+pub fn user_dir(name: &str) -> Option<PathBuf> {
+    let key = format!("XDG_{}_DIR", name);
+    let content = fs::read_to_string(config_home().join("user-dirs.dirs")).ok()?;
+
+    for line in content.lines() {
+        let line = line.trim();
+
+        if line.starts_with('#') {
+            continue;
+        }
+
+        if let Some((line_key, value)) = line.split_once('=')
+            && line_key.trim() == key
+        {
+            return Some(parse_value(value));
+        }
+    }
+
+    None
+}
+
+pub fn templates_dir() -> PathBuf {
+    user_dir("TEMPLATES").unwrap_or_else(|| home_dir().join("Templates"))
+}
+
+fn parse_value(value: &str) -> PathBuf {
+    let value = value.trim().trim_matches('"');
+
+    if let Some(rest) = value.strip_prefix("$HOME") {
+        home_dir().join(rest.trim_start_matches('/'))
+    } else {
+        PathBuf::from(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_value_expands_home() {
+        assert_eq!(parse_value("\"$HOME/Desktop\""), home_dir().join("Desktop"));
+        assert_eq!(parse_value(" \"/srv/music\" "), PathBuf::from("/srv/music"));
+        assert_eq!(parse_value("\"$HOME/\""), home_dir());
+    }
+}